@@ -0,0 +1,88 @@
+use std::hint::black_box;
+use std::sync::Arc;
+use std::thread;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use lin_toolbox_lib::{AtomicSnowflake, Snowflake};
+
+/// 每个线程在一次迭代中生成的ID数量
+const IDS_PER_THREAD: u64 = 10_000;
+
+fn bench_single_thread(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snowflake_single_thread");
+    group.throughput(Throughput::Elements(1));
+
+    let mutex_generator = Snowflake::new(1, 1);
+    group.bench_function("mutex", |b| {
+        b.iter(|| black_box(mutex_generator.next_id().unwrap()))
+    });
+
+    let atomic_generator = AtomicSnowflake::new(1, 1);
+    group.bench_function("atomic", |b| {
+        b.iter(|| black_box(atomic_generator.next_id().unwrap()))
+    });
+
+    group.finish();
+}
+
+fn bench_multi_thread(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snowflake_multi_thread");
+    group.sample_size(20);
+
+    for threads in [2u64, 4, 8] {
+        group.throughput(Throughput::Elements(threads * IDS_PER_THREAD));
+
+        let mutex_generator = Arc::new(Snowflake::new(2, 2));
+        group.bench_with_input(
+            BenchmarkId::new("mutex", threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    run_threads(threads, {
+                        let generator = Arc::clone(&mutex_generator);
+                        move || generator.next_id().unwrap()
+                    })
+                })
+            },
+        );
+
+        let atomic_generator = Arc::new(AtomicSnowflake::new(2, 2));
+        group.bench_with_input(
+            BenchmarkId::new("atomic", threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    run_threads(threads, {
+                        let generator = Arc::clone(&atomic_generator);
+                        move || generator.next_id().unwrap()
+                    })
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// 启动 `threads` 个线程，每个线程调用 `IDS_PER_THREAD` 次 `next_id`
+fn run_threads<F>(threads: u64, next_id: F)
+where
+    F: Fn() -> u64 + Clone + Send + 'static,
+{
+    let handles: Vec<_> = (0..threads)
+        .map(|_| {
+            let next_id = next_id.clone();
+            thread::spawn(move || {
+                for _ in 0..IDS_PER_THREAD {
+                    black_box(next_id());
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+criterion_group!(benches, bench_single_thread, bench_multi_thread);
+criterion_main!(benches);
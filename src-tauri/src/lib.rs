@@ -1,9 +1,30 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 pub mod utils {
+    pub mod base_converter;
+    pub mod curl_converter;
+    pub mod db_checker;
+    pub mod dns_util;
+    pub mod http_client;
+    pub mod id_generator;
+    pub mod local_ports;
+    pub mod migrations;
+    pub mod monitor;
+    pub mod net_interfaces;
+    pub mod netcat;
     pub mod network_util;
+    pub mod port_forward;
+    pub mod port_scanner;
+    pub mod proxy;
     pub mod random_util;
     pub mod scheduled_tasks;
+    pub mod service_probe;
     pub mod snowflake;
+    pub mod subnet;
+    pub mod tcping;
+    pub mod tls_inspector;
+    pub mod udp_probe;
+    pub mod websocket;
+    pub mod wol;
 }
 
 use std::sync::Arc;
@@ -16,23 +37,102 @@ pub use utils::scheduled_tasks::{
     cancel_cron_task, cancel_reminder, schedule_cron_task, schedule_reminder, send_notification,
 };
 
+use utils::base_converter::convert_number;
+use utils::curl_converter::{generate_http_snippet, parse_curl_command};
+use utils::db_checker::check_database;
+use utils::dns_util::dns_lookup;
+use utils::http_client::send_http_request;
+use utils::id_generator::{decode_id, generate_ids};
+use utils::local_ports::{kill_process, list_local_sockets};
+use utils::migrations::{migrations, DB_URL};
+use utils::monitor::{check_monitor, running_monitors, start_monitor, stop_monitor};
+use utils::net_interfaces::get_network_info;
+use utils::netcat::{
+    close_netcat_session, list_netcat_sessions, open_netcat_session, send_netcat_data,
+};
 use utils::network_util::is_port_open;
-use utils::snowflake::generate_snowflake_id;
+use utils::port_forward::{self, list_port_forwards, start_port_forward, stop_port_forward};
+use utils::port_scanner::{cancel_port_scan, start_port_scan};
+use utils::proxy::{get_proxy_config, set_proxy_config};
+use utils::service_probe::grab_banner;
+use utils::snowflake::{generate_snowflake_id, snowflake_id_range};
+use utils::subnet::{check_cidr_overlap, ip_in_range, split_subnet, subnet_info, summarize_cidrs};
+use utils::tcping::{start_tcping, stop_tcping};
+use utils::tls_inspector::{inspect_tls, parse_certificates};
+use utils::udp_probe::udp_probe;
+use utils::websocket::{ws_close, ws_connect, ws_send, ws_sessions};
+use utils::wol::{self, set_wol_tray_devices, wake_on_lan};
 
 use tauri::{
     image::Image,
-    menu::{IconMenuItem, Menu},
+    menu::{IconMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager,
+    AppHandle, Manager,
 };
 
-pub use utils::snowflake::Snowflake;
+pub use utils::snowflake::{AtomicSnowflake, Snowflake};
+
+/// 系统托盘ID
+const TRAY_ID: &str = "main";
+
+/// 构建托盘菜单，已保存的网络唤醒设备与运行中的端口转发显示在子菜单中
+fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let exit_icon_image = Image::from_bytes(include_bytes!("../icons/exit.png")).unwrap();
+    let show_hide_icon_image = Image::from_bytes(include_bytes!("../icons/ShowHide.png")).unwrap();
+    let show_i = IconMenuItem::with_id(
+        app,
+        "show",
+        "显示/隐藏",
+        true,
+        Some(show_hide_icon_image),
+        None::<&str>,
+    )?;
+    let quit_i = IconMenuItem::with_id(
+        app,
+        "quit",
+        "退出程序",
+        true,
+        Some(exit_icon_image),
+        None::<&str>,
+    )?;
+    let menu = Menu::with_items(app, &[&show_i, &quit_i])?;
+    let devices = wol::tray_devices();
+    if !devices.is_empty() {
+        let wol_menu = Submenu::new(app, "网络唤醒", true)?;
+        for (id, name) in devices {
+            wol_menu.append(&MenuItem::with_id(app, id, name, true, None::<&str>)?)?;
+        }
+        menu.insert(&wol_menu, 0)?;
+        menu.insert(&PredefinedMenuItem::separator(app)?, 1)?;
+    }
+    let forwards = port_forward::tray_forwards();
+    if !forwards.is_empty() {
+        let forward_menu = Submenu::new(app, "端口转发", true)?;
+        for (id, name) in forwards {
+            forward_menu.append(&MenuItem::with_id(app, id, name, true, None::<&str>)?)?;
+        }
+        menu.insert(&forward_menu, 0)?;
+        menu.insert(&PredefinedMenuItem::separator(app)?, 1)?;
+    }
+    Ok(menu)
+}
+
+/// 重新构建托盘菜单
+pub(crate) fn refresh_tray_menu(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        tray.set_menu(Some(build_tray_menu(app)?))?;
+    }
+    Ok(())
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .manage(Arc::new(Snowflake::new(1, 2)))
-        .plugin(tauri_plugin_sql::Builder::default().build())
+        .plugin(
+            tauri_plugin_sql::Builder::default()
+                .add_migrations(DB_URL, migrations())
+                .build(),
+        )
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_notification::init())
         .invoke_handler(tauri::generate_handler![
@@ -42,7 +142,51 @@ pub fn run() {
             build_bank_info,
             build_table_data,
             generate_snowflake_id,
+            snowflake_id_range,
+            generate_ids,
+            decode_id,
+            convert_number,
             is_port_open,
+            set_proxy_config,
+            get_proxy_config,
+            list_local_sockets,
+            kill_process,
+            get_network_info,
+            open_netcat_session,
+            send_netcat_data,
+            close_netcat_session,
+            list_netcat_sessions,
+            ws_connect,
+            ws_send,
+            ws_close,
+            ws_sessions,
+            wake_on_lan,
+            set_wol_tray_devices,
+            grab_banner,
+            inspect_tls,
+            parse_certificates,
+            start_port_scan,
+            cancel_port_scan,
+            start_port_forward,
+            stop_port_forward,
+            list_port_forwards,
+            start_tcping,
+            stop_tcping,
+            subnet_info,
+            split_subnet,
+            summarize_cidrs,
+            check_cidr_overlap,
+            ip_in_range,
+            dns_lookup,
+            udp_probe,
+            send_http_request,
+            parse_curl_command,
+            generate_http_snippet,
+            check_database,
+            start_monitor,
+            stop_monitor,
+            check_monitor,
+            running_monitors,
             schedule_reminder,
             send_notification,
             schedule_cron_task,
@@ -50,6 +194,13 @@ pub fn run() {
             cancel_reminder
         ])
         .setup(|app| {
+            let snowflake_state_file = app.path().app_data_dir()?.join("snowflake.state");
+            app.manage(Arc::new(Snowflake::with_state_file(
+                1,
+                2,
+                snowflake_state_file,
+            )));
+
             tauri::async_runtime::spawn(async {
                 use crate::utils::scheduled_tasks::SCHEDULER;
 
@@ -65,30 +216,9 @@ pub fn run() {
                 }
             });
 
-            let exit_icon_image = Image::from_bytes(include_bytes!("../icons/exit.png")).unwrap();
-            let show_hide_icon_image =
-                Image::from_bytes(include_bytes!("../icons/ShowHide.png")).unwrap();
-            let show_i = IconMenuItem::with_id(
-                app,
-                "show",
-                "显示/隐藏",
-                true,
-                Some(show_hide_icon_image),
-                None::<&str>,
-            )
-            .unwrap();
-            let quit_i = IconMenuItem::with_id(
-                app,
-                "quit",
-                &"退出程序",
-                true,
-                Some(exit_icon_image),
-                None::<&str>,
-            )
-            .unwrap();
-            let menu = Menu::with_items(app, &[&show_i, &quit_i])?;
+            let menu = build_tray_menu(app.handle())?;
             // 创建系统托盘
-            let _tray = TrayIconBuilder::new()
+            let _tray = TrayIconBuilder::with_id(TRAY_ID)
                 // 添加托盘图标
                 .icon(Image::from_bytes(include_bytes!("../icons/ico.png")).expect("REASON"))
                 // .icon(app.default_window_icon().unwrap().clone())
@@ -146,6 +276,12 @@ pub fn run() {
                     "quit" => {
                         app.exit(0);
                     }
+                    id if id.starts_with(wol::TRAY_MENU_PREFIX) => {
+                        wol::wake_from_tray(app.clone(), id);
+                    }
+                    id if id.starts_with(port_forward::TRAY_MENU_PREFIX) => {
+                        port_forward::stop_from_tray(app, id);
+                    }
                     _ => {}
                 })
                 .build(app)?;
@@ -0,0 +1,478 @@
+use crate::utils::id_generator::{crockford_digit, BASE62_ALPHABET, CROCKFORD_ALPHABET};
+use serde::{Deserialize, Serialize};
+
+/// Base58 比特币字符表
+const BASE58_BITCOIN_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+/// 支持的定长整数位宽
+const FIXED_WIDTHS: [u32; 5] = [8, 16, 32, 64, 128];
+
+/// 数字编码方式
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NumberEncoding {
+    /// 2-62 进制。36 进制及以下使用 0-9a-z 且输入不区分大小写，以上使用 0-9A-Za-z
+    Radix { base: u32 },
+    /// Crockford Base32，输入不区分大小写，兼容 I/L → 1、O → 0 并忽略连字符
+    Crockford32,
+    /// Base58 比特币字符表
+    Base58Bitcoin,
+    /// 自定义字符表，字符在表中的位置即为数值
+    Custom { alphabet: String },
+}
+
+impl NumberEncoding {
+    fn alphabet(&self) -> Result<Vec<char>, String> {
+        let alphabet: Vec<char> = match self {
+            NumberEncoding::Radix { base } => {
+                if !(2..=62).contains(base) {
+                    return Err(format!("进制应在 2-62 之间: {}", base));
+                }
+                let base = *base as usize;
+                if base <= 36 {
+                    "0123456789abcdefghijklmnopqrstuvwxyz"
+                        .chars()
+                        .take(base)
+                        .collect()
+                } else {
+                    BASE62_ALPHABET
+                        .iter()
+                        .take(base)
+                        .map(|&b| b as char)
+                        .collect()
+                }
+            }
+            NumberEncoding::Crockford32 => CROCKFORD_ALPHABET.iter().map(|&b| b as char).collect(),
+            NumberEncoding::Base58Bitcoin => BASE58_BITCOIN_ALPHABET.chars().collect(),
+            NumberEncoding::Custom { alphabet } => {
+                let chars: Vec<char> = alphabet.chars().collect();
+                if chars.len() < 2 || chars.len() > 256 {
+                    return Err("自定义字符表长度应在 2-256 之间".to_string());
+                }
+                // 输入中的空白与 '_' 作为分隔符忽略，'-' 为负号
+                if chars
+                    .iter()
+                    .any(|c| c.is_whitespace() || *c == '-' || *c == '_')
+                {
+                    return Err("自定义字符表不能包含空白字符、'-' 或 '_'".to_string());
+                }
+                let mut sorted = chars.clone();
+                sorted.sort_unstable();
+                sorted.dedup();
+                if sorted.len() != chars.len() {
+                    return Err("自定义字符表中存在重复字符".to_string());
+                }
+                chars
+            }
+        };
+        Ok(alphabet)
+    }
+
+    /// 解析单个字符对应的数值
+    fn digit(&self, alphabet: &[char], c: char) -> Option<u32> {
+        match self {
+            NumberEncoding::Radix { base } if *base <= 36 => {
+                let d = c.to_digit(36)?;
+                (d < *base).then_some(d)
+            }
+            NumberEncoding::Crockford32 => crockford_digit(c).map(u32::from),
+            _ => alphabet.iter().position(|&a| a == c).map(|p| p as u32),
+        }
+    }
+
+    /// 该编码允许的数字前缀，如十六进制的 `0x`
+    fn prefix(&self) -> Option<&'static str> {
+        match self {
+            NumberEncoding::Radix { base: 2 } => Some("0b"),
+            NumberEncoding::Radix { base: 8 } => Some("0o"),
+            NumberEncoding::Radix { base: 16 } => Some("0x"),
+            _ => None,
+        }
+    }
+}
+
+/// 转换结果
+#[derive(Serialize, Debug)]
+pub struct ConvertedNumber {
+    encoding: NumberEncoding,
+    value: String,
+}
+
+/// 定长整数视图
+#[derive(Serialize, Debug)]
+pub struct FixedWidthView {
+    width: u32,
+    /// 按无符号解释的十进制值
+    unsigned: String,
+    /// 按有符号（补码）解释的十进制值
+    signed: String,
+    /// 大端字节序的十六进制字节
+    big_endian_bytes: String,
+    /// 小端字节序的十六进制字节
+    little_endian_bytes: String,
+    /// 按相反字节序读取时的无符号值
+    swapped_unsigned: String,
+    /// 按相反字节序读取时的有符号值
+    swapped_signed: String,
+}
+
+/// 数字转换结果
+#[derive(Serialize, Debug)]
+pub struct NumberConversion {
+    /// 十进制值，前端以字符串展示以避免超过 2^53 后丢失精度
+    decimal: String,
+    values: Vec<ConvertedNumber>,
+    fixed_width: Option<FixedWidthView>,
+}
+
+/// 任意精度整数进制转换
+/// - value: 输入值，可带 '-' 号，忽略空白与 '_'
+/// - from: 输入值的编码
+/// - targets: 需要输出的编码
+/// - width: 可选的定长位宽（8/16/32/64/128），给出有符号/无符号与字节序解释
+#[tauri::command]
+pub fn convert_number(
+    value: String,
+    from: NumberEncoding,
+    targets: Vec<NumberEncoding>,
+    width: Option<u32>,
+) -> Result<NumberConversion, String> {
+    let (negative, magnitude) = parse_number(&value, &from)?;
+
+    let values = targets
+        .into_iter()
+        .map(|encoding| {
+            let value = format_number(negative, &magnitude, &encoding)?;
+            Ok(ConvertedNumber { encoding, value })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let fixed_width = width
+        .map(|width| fixed_width_view(negative, &magnitude, width))
+        .transpose()?;
+
+    Ok(NumberConversion {
+        decimal: format_number(negative, &magnitude, &NumberEncoding::Radix { base: 10 })?,
+        values,
+        fixed_width,
+    })
+}
+
+/// 解析为 (是否为负数, 绝对值)
+fn parse_number(value: &str, encoding: &NumberEncoding) -> Result<(bool, BigUint), String> {
+    let alphabet = encoding.alphabet()?;
+    let base = alphabet.len() as u32;
+
+    let cleaned: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '_')
+        .collect();
+    let (negative, mut digits) = match cleaned.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, cleaned.as_str()),
+    };
+    if let Some(prefix) = encoding.prefix() {
+        if digits
+            .get(..2)
+            .is_some_and(|p| p.eq_ignore_ascii_case(prefix))
+        {
+            digits = &digits[2..];
+        }
+    }
+
+    let mut magnitude = BigUint::zero();
+    let mut has_digit = false;
+    for c in digits.chars() {
+        if c == '-' && *encoding == NumberEncoding::Crockford32 {
+            continue;
+        }
+        let digit = encoding
+            .digit(&alphabet, c)
+            .ok_or_else(|| format!("非法字符: '{}'", c))?;
+        magnitude.mul_add(base, digit);
+        has_digit = true;
+    }
+    if !has_digit {
+        return Err("请输入数字".to_string());
+    }
+    Ok((negative && !magnitude.is_zero(), magnitude))
+}
+
+fn format_number(
+    negative: bool,
+    magnitude: &BigUint,
+    encoding: &NumberEncoding,
+) -> Result<String, String> {
+    let alphabet = encoding.alphabet()?;
+    let base = alphabet.len() as u32;
+
+    let mut number = magnitude.clone();
+    let mut digits = Vec::new();
+    loop {
+        digits.push(alphabet[number.div_rem(base) as usize]);
+        if number.is_zero() {
+            break;
+        }
+    }
+    if negative {
+        digits.push('-');
+    }
+    Ok(digits.iter().rev().collect())
+}
+
+fn fixed_width_view(
+    negative: bool,
+    magnitude: &BigUint,
+    width: u32,
+) -> Result<FixedWidthView, String> {
+    if !FIXED_WIDTHS.contains(&width) {
+        return Err(format!("位宽应为 {:?} 之一", FIXED_WIDTHS));
+    }
+    let out_of_range = || format!("数值超出 {} 位整数范围", width);
+    let magnitude = magnitude.to_u128().ok_or_else(out_of_range)?;
+    let mask = if width == 128 {
+        u128::MAX
+    } else {
+        (1u128 << width) - 1
+    };
+
+    // 负数按补码表示，取值范围 [-2^(width-1), 2^width - 1]
+    let bits = if negative {
+        if magnitude > 1u128 << (width - 1) {
+            return Err(out_of_range());
+        }
+        magnitude.wrapping_neg() & mask
+    } else {
+        if magnitude > mask {
+            return Err(out_of_range());
+        }
+        magnitude
+    };
+
+    let bytes = (width / 8) as usize;
+    let big_endian = &bits.to_be_bytes()[16 - bytes..];
+    let little_endian: Vec<u8> = big_endian.iter().rev().copied().collect();
+    let swapped = little_endian
+        .iter()
+        .fold(0u128, |acc, &b| (acc << 8) | b as u128);
+
+    Ok(FixedWidthView {
+        width,
+        unsigned: bits.to_string(),
+        signed: to_signed(bits, width).to_string(),
+        big_endian_bytes: to_hex_bytes(big_endian),
+        little_endian_bytes: to_hex_bytes(&little_endian),
+        swapped_unsigned: swapped.to_string(),
+        swapped_signed: to_signed(swapped, width).to_string(),
+    })
+}
+
+/// 将低 `width` 位按补码解释为有符号数
+fn to_signed(bits: u128, width: u32) -> i128 {
+    let shift = 128 - width;
+    ((bits << shift) as i128) >> shift
+}
+
+fn to_hex_bytes(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// 简单的任意精度无符号整数，使用小端序的 u32 分段存储
+#[derive(Clone, Debug)]
+struct BigUint(Vec<u32>);
+
+impl BigUint {
+    fn zero() -> Self {
+        BigUint(Vec::new())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|&limb| limb == 0)
+    }
+
+    /// self = self * m + a
+    fn mul_add(&mut self, m: u32, a: u32) {
+        let mut carry = a as u64;
+        for limb in self.0.iter_mut() {
+            let v = *limb as u64 * m as u64 + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        if carry != 0 {
+            self.0.push(carry as u32);
+        }
+    }
+
+    /// self = self / d，返回余数
+    fn div_rem(&mut self, d: u32) -> u32 {
+        let mut remainder = 0u64;
+        for limb in self.0.iter_mut().rev() {
+            let v = (remainder << 32) | *limb as u64;
+            *limb = (v / d as u64) as u32;
+            remainder = v % d as u64;
+        }
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+        remainder as u32
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        let mut limbs = self.0.clone();
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        if limbs.len() > 4 {
+            return None;
+        }
+        Some(
+            limbs
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &limb| (acc << 32) | limb as u128),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn radix(base: u32) -> NumberEncoding {
+        NumberEncoding::Radix { base }
+    }
+
+    fn convert(value: &str, from: NumberEncoding, to: NumberEncoding) -> Result<String, String> {
+        let result = convert_number(value.to_string(), from, vec![to], None)?;
+        Ok(result.values.into_iter().next().unwrap().value)
+    }
+
+    /// 超过 2^53 的雪花ID在各进制间转换不丢失精度
+    #[test]
+    fn test_large_snowflake_id_roundtrip() {
+        let id = "9007199254740993123456789";
+        for base in [2, 8, 16, 36, 62] {
+            let encoded = convert(id, radix(10), radix(base)).unwrap();
+            assert_eq!(convert(&encoded, radix(base), radix(10)).unwrap(), id);
+        }
+        assert_eq!(
+            convert("9007199254740993", radix(10), radix(16)).unwrap(),
+            "20000000000001"
+        );
+    }
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(convert("0xFF", radix(16), radix(2)).unwrap(), "11111111");
+        assert_eq!(convert("zz", radix(36), radix(10)).unwrap(), "1295");
+        assert_eq!(convert("ZZ", radix(36), radix(10)).unwrap(), "1295");
+        assert_eq!(convert("61", radix(10), radix(62)).unwrap(), "z");
+        assert_eq!(convert("-255", radix(10), radix(16)).unwrap(), "-ff");
+        assert_eq!(convert("-0", radix(10), radix(10)).unwrap(), "0");
+        assert_eq!(
+            convert("1_000 000", radix(10), radix(10)).unwrap(),
+            "1000000"
+        );
+    }
+
+    #[test]
+    fn test_crockford_and_base58() {
+        assert_eq!(
+            convert("32", radix(10), NumberEncoding::Crockford32).unwrap(),
+            "10"
+        );
+        assert_eq!(
+            convert("1O-l", NumberEncoding::Crockford32, radix(10)).unwrap(),
+            "1025"
+        );
+        assert_eq!(
+            convert("58", radix(10), NumberEncoding::Base58Bitcoin).unwrap(),
+            "21"
+        );
+        assert_eq!(
+            convert("0", radix(10), NumberEncoding::Base58Bitcoin).unwrap(),
+            "1"
+        );
+        assert!(convert("0", NumberEncoding::Base58Bitcoin, radix(10)).is_err());
+    }
+
+    #[test]
+    fn test_custom_alphabet() {
+        let custom = NumberEncoding::Custom {
+            alphabet: "ab".to_string(),
+        };
+        assert_eq!(convert("5", radix(10), custom.clone()).unwrap(), "bab");
+        assert_eq!(convert("bab", custom, radix(10)).unwrap(), "5");
+        assert!(convert(
+            "1",
+            radix(10),
+            NumberEncoding::Custom {
+                alphabet: "aa".to_string()
+            }
+        )
+        .is_err());
+        // '_' 会被当作分隔符忽略，不能出现在字符表中
+        let underscore = NumberEncoding::Custom {
+            alphabet: "_ab".to_string(),
+        };
+        assert!(convert("a_b", underscore.clone(), radix(10)).is_err());
+        assert!(convert("5", radix(10), underscore).is_err());
+        assert!(convert(
+            "1",
+            radix(10),
+            NumberEncoding::Custom {
+                alphabet: "a b".to_string()
+            }
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(convert("12", radix(2), radix(10)).is_err());
+        assert!(convert("", radix(10), radix(10)).is_err());
+        assert!(convert("1", radix(63), radix(10)).is_err());
+        // 非 ASCII 字符不能在检查前缀时被截断
+        for base in [2, 8, 16] {
+            assert!(convert("é1", radix(base), radix(10)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_fixed_width() {
+        let view = convert_number("-1".to_string(), radix(10), vec![], Some(8))
+            .unwrap()
+            .fixed_width
+            .unwrap();
+        assert_eq!(view.unsigned, "255");
+        assert_eq!(view.signed, "-1");
+
+        let view = convert_number("0x0102".to_string(), radix(16), vec![], Some(16))
+            .unwrap()
+            .fixed_width
+            .unwrap();
+        assert_eq!(view.big_endian_bytes, "01 02");
+        assert_eq!(view.little_endian_bytes, "02 01");
+        assert_eq!(view.swapped_unsigned, "513");
+
+        let view = convert_number("0x80".to_string(), radix(16), vec![], Some(8))
+            .unwrap()
+            .fixed_width
+            .unwrap();
+        assert_eq!(view.signed, "-128");
+
+        let max = u128::MAX.to_string();
+        let view = convert_number(max, radix(10), vec![], Some(128))
+            .unwrap()
+            .fixed_width
+            .unwrap();
+        assert_eq!(view.signed, "-1");
+
+        assert!(convert_number("256".to_string(), radix(10), vec![], Some(8)).is_err());
+        assert!(convert_number("-129".to_string(), radix(10), vec![], Some(8)).is_err());
+        assert!(convert_number("1".to_string(), radix(10), vec![], Some(24)).is_err());
+    }
+}
@@ -0,0 +1,1467 @@
+use crate::utils::http_client::{
+    build_url, HttpAuth, HttpBody, HttpRequest, KeyValue, MultipartPart,
+};
+use serde::Deserialize;
+use std::fmt::Write;
+use url::Url;
+
+/// 生成代码片段的目标语言
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SnippetLanguage {
+    Curl,
+    Httpie,
+    RustReqwest,
+    JavaOkhttp,
+    PythonRequests,
+    JsFetch,
+}
+
+/// 解析 cURL 命令为 HTTP 请求
+#[tauri::command]
+pub fn parse_curl_command(command: String) -> Result<HttpRequest, String> {
+    parse_curl(&command)
+}
+
+/// 根据 HTTP 请求生成 cURL、HTTPie 命令或代码片段
+#[tauri::command]
+pub fn generate_http_snippet(
+    request: HttpRequest,
+    language: SnippetLanguage,
+) -> Result<String, String> {
+    let prepared = Prepared::new(&request)?;
+    Ok(match language {
+        SnippetLanguage::Curl => to_curl(&prepared),
+        SnippetLanguage::Httpie => to_httpie(&prepared),
+        SnippetLanguage::RustReqwest => to_reqwest(&prepared),
+        SnippetLanguage::JavaOkhttp => to_okhttp(&prepared),
+        SnippetLanguage::PythonRequests => to_python_requests(&prepared),
+        SnippetLanguage::JsFetch => to_fetch(&prepared),
+    })
+}
+
+/// 按 POSIX shell 规则拆分命令行，支持单双引号、`$'...'` 与反斜杠续行
+pub fn split_command(command: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = command.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("单引号未闭合".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err("双引号未闭合".to_string()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("双引号未闭合".to_string()),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                current.push_str(&ansi_c_quoted(&mut chars)?);
+            }
+            '\\' => match chars.next() {
+                // 反斜杠续行
+                Some('\n') => {}
+                Some('\r') => {
+                    chars.next_if_eq(&'\n');
+                }
+                Some(c) => {
+                    in_word = true;
+                    current.push(c);
+                }
+                None => {}
+            },
+            '#' if !in_word => {
+                // 注释直到行尾
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// 解析 `$'...'` 中的转义字符，`\xHH` 按字节拼接后再按 UTF-8 解码
+fn ansi_c_quoted(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<String, String> {
+    let mut bytes = Vec::new();
+    let push_char = |bytes: &mut Vec<u8>, c: char| {
+        let mut buf = [0u8; 4];
+        bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    };
+    let take_digits = |chars: &mut std::iter::Peekable<std::str::Chars>, radix: u32, max: usize| {
+        let mut digits = String::new();
+        while digits.len() < max {
+            match chars.peek() {
+                Some(c) if c.is_digit(radix) => digits.push(*c),
+                _ => break,
+            }
+            chars.next();
+        }
+        u32::from_str_radix(&digits, radix).ok()
+    };
+    loop {
+        match chars.next() {
+            Some('\'') => break,
+            Some('\\') => match chars.next() {
+                Some('n') => bytes.push(b'\n'),
+                Some('t') => bytes.push(b'\t'),
+                Some('r') => bytes.push(b'\r'),
+                Some('a') => bytes.push(0x07),
+                Some('b') => bytes.push(0x08),
+                Some('e') | Some('E') => bytes.push(0x1b),
+                Some('f') => bytes.push(0x0c),
+                Some('v') => bytes.push(0x0b),
+                Some('x') => match take_digits(chars, 16, 2) {
+                    Some(b) => bytes.push(b as u8),
+                    None => bytes.extend_from_slice(b"\\x"),
+                },
+                Some(c @ ('u' | 'U')) => {
+                    let max = if c == 'u' { 4 } else { 8 };
+                    match take_digits(chars, 16, max).and_then(char::from_u32) {
+                        Some(c) => push_char(&mut bytes, c),
+                        None => {
+                            bytes.push(b'\\');
+                            push_char(&mut bytes, c);
+                        }
+                    }
+                }
+                Some(c @ '0'..='7') => {
+                    let mut value = c.to_digit(8).unwrap_or(0);
+                    for _ in 0..2 {
+                        match chars.peek().and_then(|c| c.to_digit(8)) {
+                            Some(d) => {
+                                value = value * 8 + d;
+                                chars.next();
+                            }
+                            None => break,
+                        }
+                    }
+                    bytes.push(value as u8);
+                }
+                Some(c) => push_char(&mut bytes, c),
+                None => return Err("引号未闭合".to_string()),
+            },
+            Some(c) => push_char(&mut bytes, c),
+            None => return Err("引号未闭合".to_string()),
+        }
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// 需要参数值的 cURL 短选项
+const SHORT_WITH_VALUE: &str = "XHdFuxmAebowcEKTrCUQtyYz";
+
+/// 需要参数值的 cURL 长选项，未列出的长选项视为开关
+const LONG_WITH_VALUE: &[&str] = &[
+    "request",
+    "header",
+    "data",
+    "data-ascii",
+    "data-binary",
+    "data-raw",
+    "data-urlencode",
+    "json",
+    "form",
+    "form-string",
+    "user",
+    "oauth2-bearer",
+    "proxy",
+    "proxy-user",
+    "max-time",
+    "max-redirs",
+    "user-agent",
+    "referer",
+    "cookie",
+    "url",
+    "output",
+    "write-out",
+    "cookie-jar",
+    "cert",
+    "cert-type",
+    "key",
+    "key-type",
+    "cacert",
+    "capath",
+    "config",
+    "upload-file",
+    "range",
+    "continue-at",
+    "quote",
+    "time-cond",
+    "connect-timeout",
+    "retry",
+    "retry-delay",
+    "retry-max-time",
+    "resolve",
+    "connect-to",
+    "interface",
+    "limit-rate",
+    "dump-header",
+    "trace",
+    "trace-ascii",
+    "stderr",
+    "max-filesize",
+    "local-port",
+    "dns-servers",
+    "ciphers",
+    "tls-max",
+    "keepalive-time",
+    "expect100-timeout",
+    "unix-socket",
+    "abstract-unix-socket",
+    "aws-sigv4",
+    "request-target",
+    "proto",
+    "proto-redir",
+    "noproxy",
+    "socks4",
+    "socks4a",
+    "socks5",
+    "socks5-hostname",
+    "preproxy",
+    "speed-limit",
+    "speed-time",
+];
+
+fn short_to_long(c: char) -> Option<&'static str> {
+    Some(match c {
+        'X' => "request",
+        'H' => "header",
+        'd' => "data",
+        'F' => "form",
+        'u' => "user",
+        'k' => "insecure",
+        'L' => "location",
+        'x' => "proxy",
+        'm' => "max-time",
+        'G' => "get",
+        'I' => "head",
+        'A' => "user-agent",
+        'e' => "referer",
+        'b' => "cookie",
+        _ => return None,
+    })
+}
+
+/// 解析过程中收集的 cURL 参数
+#[derive(Default)]
+struct CurlOptions {
+    method: Option<String>,
+    url: Option<String>,
+    headers: Vec<KeyValue>,
+    data: Vec<String>,
+    json: bool,
+    parts: Vec<MultipartPart>,
+    user: Option<String>,
+    bearer: Option<String>,
+    insecure: bool,
+    compressed: bool,
+    location: bool,
+    max_redirs: Option<usize>,
+    proxy: Option<String>,
+    proxy_user: Option<String>,
+    max_time: Option<f64>,
+    get: bool,
+    head: bool,
+}
+
+impl CurlOptions {
+    fn apply(&mut self, name: &str, value: Option<String>) -> Result<(), String> {
+        let value = value.unwrap_or_default();
+        match name {
+            "request" => self.method = Some(value.to_uppercase()),
+            "header" => {
+                if let Some((name, value)) = value.split_once(':') {
+                    self.headers.push(KeyValue::new(name.trim(), value.trim()));
+                } else if let Some(name) = value.strip_suffix(';') {
+                    // `-H 'Name;'` 表示发送空值的请求头
+                    self.headers.push(KeyValue::new(name.trim(), ""));
+                }
+            }
+            "data" | "data-ascii" | "data-binary" => {
+                if let Some(file) = value.strip_prefix('@') {
+                    return Err(format!("暂不支持从文件读取请求体: {}", file));
+                }
+                // -d 会去掉换行符，--data-binary 原样发送
+                let value = if name == "data-binary" {
+                    value
+                } else {
+                    value.replace(['\r', '\n'], "")
+                };
+                self.data.push(value);
+            }
+            "data-raw" => self.data.push(value),
+            "json" => {
+                if let Some(file) = value.strip_prefix('@') {
+                    return Err(format!("暂不支持从文件读取请求体: {}", file));
+                }
+                self.json = true;
+                self.data.push(value);
+            }
+            "data-urlencode" => self.data.push(urlencode_data(&value)?),
+            "form" | "form-string" => self.parts.push(form_part(&value, name == "form")?),
+            "user" => self.user = Some(value),
+            "oauth2-bearer" => self.bearer = Some(value),
+            "insecure" => self.insecure = true,
+            "compressed" => self.compressed = true,
+            "location" | "location-trusted" => self.location = true,
+            "max-redirs" => {
+                self.max_redirs = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("无效的重定向次数: {}", value))?,
+                )
+            }
+            "proxy" => self.proxy = Some(value),
+            "proxy-user" => self.proxy_user = Some(value),
+            "max-time" => {
+                self.max_time = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("无效的超时时间: {}", value))?,
+                )
+            }
+            "get" => self.get = true,
+            "head" => self.head = true,
+            "user-agent" => self.headers.push(KeyValue::new("User-Agent", value)),
+            "referer" => self.headers.push(KeyValue::new("Referer", value)),
+            // 不含 `=` 时是 cookie 文件，忽略
+            "cookie" if value.contains('=') => self.headers.push(KeyValue::new("Cookie", value)),
+            "url" => {
+                self.url.get_or_insert(value);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|h| h.name.eq_ignore_ascii_case(name))
+            .map(|h| h.value.as_str())
+    }
+
+    fn into_request(mut self) -> Result<HttpRequest, String> {
+        let mut url = self.url.take().ok_or("未找到请求地址")?;
+        if !self.data.is_empty() && !self.parts.is_empty() {
+            return Err("不能同时使用 -d 与 -F".to_string());
+        }
+        let mut body = HttpBody::None;
+        if !self.parts.is_empty() {
+            body = HttpBody::Multipart {
+                parts: std::mem::take(&mut self.parts),
+            };
+        } else if !self.data.is_empty() && self.get {
+            // -G 把请求体拼接到查询参数
+            let separator = if url.contains('?') { '&' } else { '?' };
+            url = format!("{}{}{}", url, separator, self.data.join("&"));
+        } else if !self.data.is_empty() {
+            body = self.data_body();
+        }
+        if self.json {
+            if self.header("content-type").is_none() {
+                self.headers
+                    .push(KeyValue::new("Content-Type", "application/json"));
+            }
+            if self.header("accept").is_none() {
+                self.headers
+                    .push(KeyValue::new("Accept", "application/json"));
+            }
+        }
+
+        let method = match self.method.take() {
+            Some(method) => method,
+            None if self.head => "HEAD".to_string(),
+            None if body != HttpBody::None => "POST".to_string(),
+            None => "GET".to_string(),
+        };
+        let auth = match (self.user.take(), self.bearer.take()) {
+            (Some(user), _) => {
+                let (username, password) = user.split_once(':').unwrap_or((&user, ""));
+                Some(HttpAuth::Basic {
+                    username: username.to_string(),
+                    password: password.to_string(),
+                })
+            }
+            (None, Some(token)) => Some(HttpAuth::Bearer { token }),
+            (None, None) => None,
+        };
+        let proxy = match (self.proxy.take(), self.proxy_user.take()) {
+            (Some(proxy), Some(user)) => Some(proxy_with_user(&proxy, &user)?),
+            (proxy, _) => proxy,
+        };
+
+        Ok(HttpRequest {
+            method,
+            url,
+            headers: self.headers,
+            query: Vec::new(),
+            body,
+            auth,
+            follow_redirects: Some(self.location),
+            max_redirects: self.max_redirs,
+            proxy,
+            timeout_ms: self.max_time.map(|secs| (secs * 1000.0).round() as u64),
+            verify_tls: Some(!self.insecure),
+            compressed: Some(self.compressed),
+        })
+    }
+
+    /// 按 Content-Type 推断 -d 请求体的类型
+    fn data_body(&self) -> HttpBody {
+        let content_type = self.header("content-type").map(str::to_ascii_lowercase);
+        if self.json {
+            return HttpBody::Json {
+                content: self.data.concat(),
+            };
+        }
+        let content = self.data.join("&");
+        let looks_like_json = serde_json::from_str::<serde_json::Value>(&content)
+            .map(|v| v.is_object() || v.is_array())
+            .unwrap_or(false);
+        match content_type.as_deref() {
+            Some(ct) if ct.contains("json") => return HttpBody::Json { content },
+            None if looks_like_json => return HttpBody::Json { content },
+            None => {}
+            Some(ct) if ct.contains("application/x-www-form-urlencoded") => {}
+            Some(_) => {
+                return HttpBody::Raw {
+                    content,
+                    content_type: None,
+                }
+            }
+        }
+        if content.split('&').all(|pair| pair.contains('=')) {
+            HttpBody::Form {
+                fields: url::form_urlencoded::parse(content.as_bytes())
+                    .map(|(name, value)| KeyValue::new(name, value))
+                    .collect(),
+            }
+        } else {
+            HttpBody::Raw {
+                content,
+                content_type: Some("application/x-www-form-urlencoded".to_string()),
+            }
+        }
+    }
+}
+
+/// 按 `--data-urlencode` 的规则编码：`name=content`、`=content` 或 `content`
+fn urlencode_data(value: &str) -> Result<String, String> {
+    let encode = |s: &str| url::form_urlencoded::byte_serialize(s.as_bytes()).collect::<String>();
+    // `@file` 与 `name@file` 从文件读取
+    let from_file = match (value.find('@'), value.find('=')) {
+        (Some(at), Some(eq)) => at < eq,
+        (at, _) => at.is_some(),
+    };
+    if from_file {
+        return Err(format!("暂不支持从文件读取请求体: {}", value));
+    }
+    Ok(match value.split_once('=') {
+        Some(("", content)) => encode(content),
+        Some((name, content)) => format!("{}={}", name, encode(content)),
+        None => encode(value),
+    })
+}
+
+/// 解析 `-F name=value`、`-F name=@file;type=xxx`
+fn form_part(value: &str, allow_file: bool) -> Result<MultipartPart, String> {
+    let (name, content) = value
+        .split_once('=')
+        .ok_or_else(|| format!("无效的表单参数: {}", value))?;
+    let mut part = MultipartPart {
+        name: name.to_string(),
+        value: None,
+        file_path: None,
+        content_type: None,
+        enabled: true,
+    };
+    match content.strip_prefix('@').filter(|_| allow_file) {
+        Some(file) => {
+            let mut segments = file.split(';');
+            part.file_path = segments.next().map(str::to_string);
+            for segment in segments {
+                if let Some(content_type) = segment.trim().strip_prefix("type=") {
+                    part.content_type = Some(content_type.to_string());
+                }
+            }
+        }
+        None => {
+            let (content, content_type) = match content.split_once(";type=") {
+                Some((content, content_type)) if allow_file => (content, Some(content_type)),
+                _ => (content, None),
+            };
+            if allow_file && content.starts_with('<') {
+                return Err(format!("暂不支持从文件读取表单字段: {}", &content[1..]));
+            }
+            part.value = Some(content.to_string());
+            part.content_type = content_type.map(str::to_string);
+        }
+    }
+    Ok(part)
+}
+
+fn proxy_with_user(proxy: &str, user: &str) -> Result<String, String> {
+    let with_scheme = if proxy.contains("://") {
+        proxy.to_string()
+    } else {
+        format!("http://{}", proxy)
+    };
+    let mut url = Url::parse(&with_scheme).map_err(|e| format!("无效的代理地址: {}", e))?;
+    let (username, password) = user.split_once(':').unwrap_or((user, ""));
+    url.set_username(username)
+        .and_then(|_| url.set_password(Some(password).filter(|p| !p.is_empty())))
+        .map_err(|_| format!("无效的代理地址: {}", proxy))?;
+    Ok(url.to_string().trim_end_matches('/').to_string())
+}
+
+/// 解析 cURL 命令，未支持的开关参数会被忽略
+pub fn parse_curl(command: &str) -> Result<HttpRequest, String> {
+    let mut args = split_command(command)?.into_iter().peekable();
+    match args.peek() {
+        Some(first) if first == "curl" || first.ends_with("/curl") || first == "curl.exe" => {
+            args.next();
+        }
+        Some(_) => {}
+        None => return Err("cURL 命令不能为空".to_string()),
+    }
+    let mut options = CurlOptions::default();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            for url in args.by_ref() {
+                options.apply("url", Some(url))?;
+            }
+        } else if let Some(name) = arg.strip_prefix("--") {
+            let value = if LONG_WITH_VALUE.contains(&name) {
+                Some(args.next().ok_or_else(|| format!("参数 {} 缺少值", arg))?)
+            } else {
+                None
+            };
+            options.apply(name, value)?;
+        } else if arg.len() > 1 && arg.starts_with('-') {
+            // 合并的短选项，如 -sSL、-XPOST
+            let flags: Vec<char> = arg.chars().skip(1).collect();
+            for (i, flag) in flags.iter().enumerate() {
+                if SHORT_WITH_VALUE.contains(*flag) {
+                    let rest: String = flags[i + 1..].iter().collect();
+                    let value = if rest.is_empty() {
+                        args.next()
+                            .ok_or_else(|| format!("参数 -{} 缺少值", flag))?
+                    } else {
+                        rest
+                    };
+                    if let Some(name) = short_to_long(*flag) {
+                        options.apply(name, Some(value))?;
+                    }
+                    break;
+                }
+                if let Some(name) = short_to_long(*flag) {
+                    options.apply(name, None)?;
+                }
+            }
+        } else {
+            options.apply("url", Some(arg))?;
+        }
+    }
+    options.into_request()
+}
+
+/// 生成代码片段所需的请求信息
+struct Prepared<'a> {
+    method: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    request: &'a HttpRequest,
+}
+
+impl<'a> Prepared<'a> {
+    fn new(request: &'a HttpRequest) -> Result<Self, String> {
+        let method = request.method.trim().to_uppercase();
+        let method = if method.is_empty() {
+            "GET".to_string()
+        } else {
+            method
+        };
+        let url = build_url(&request.url, &request.query)?.to_string();
+        let mut headers: Vec<(String, String)> = request
+            .headers
+            .iter()
+            .filter(|h| h.enabled && !h.name.trim().is_empty())
+            .map(|h| (h.name.trim().to_string(), h.value.trim().to_string()))
+            .collect();
+        let content_type = match &request.body {
+            HttpBody::Json { .. } => Some("application/json".to_string()),
+            HttpBody::Raw { content_type, .. } => content_type.clone(),
+            _ => None,
+        };
+        if let Some(content_type) = content_type {
+            if !headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            {
+                headers.push(("Content-Type".to_string(), content_type));
+            }
+        }
+        if let Some(HttpAuth::Bearer { token }) = &request.auth {
+            if !headers
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+            {
+                headers.push((
+                    "Authorization".to_string(),
+                    format!("Bearer {}", token.trim()),
+                ));
+            }
+        }
+        Ok(Self {
+            method,
+            url,
+            headers,
+            request,
+        })
+    }
+
+    fn body(&self) -> &HttpBody {
+        &self.request.body
+    }
+
+    /// JSON 与原始文本请求体
+    fn text_body(&self) -> Option<&str> {
+        match self.body() {
+            HttpBody::Json { content } | HttpBody::Raw { content, .. } => Some(content),
+            _ => None,
+        }
+    }
+
+    fn form_fields(&self) -> Vec<&KeyValue> {
+        match self.body() {
+            HttpBody::Form { fields } => fields.iter().filter(|f| f.enabled).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn parts(&self) -> Vec<&MultipartPart> {
+        match self.body() {
+            HttpBody::Multipart { parts } => parts.iter().filter(|p| p.enabled).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn has_body(&self) -> bool {
+        *self.body() != HttpBody::None
+    }
+
+    fn basic_auth(&self) -> Option<(&str, &str)> {
+        match &self.request.auth {
+            Some(HttpAuth::Basic { username, password }) => Some((username, password)),
+            _ => None,
+        }
+    }
+
+    fn follow_redirects(&self) -> bool {
+        self.request.follow_redirects.unwrap_or(true)
+    }
+
+    fn verify_tls(&self) -> bool {
+        self.request.verify_tls.unwrap_or(true)
+    }
+
+    fn proxy(&self) -> Option<&str> {
+        self.request
+            .proxy
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+    }
+}
+
+fn file_path(part: &MultipartPart) -> Option<&str> {
+    part.file_path.as_deref().filter(|p| !p.is_empty())
+}
+
+fn file_name(path: &str) -> String {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// shell 单引号转义
+fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@%+=,".contains(c))
+    {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', r"'\''"))
+    }
+}
+
+/// 双引号字符串字面量，适用于 Python、Java 与 JavaScript
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+fn to_curl(p: &Prepared) -> String {
+    let mut args = vec!["curl".to_string()];
+    let default_method = if p.has_body() { "POST" } else { "GET" };
+    if p.method == "HEAD" {
+        args.push("--head".to_string());
+    } else if p.method != default_method {
+        args.push(format!("-X {}", p.method));
+    }
+    args.push(shell_quote(&p.url));
+    for (name, value) in &p.headers {
+        args.push(format!(
+            "-H {}",
+            shell_quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if let Some((username, password)) = p.basic_auth() {
+        args.push(format!(
+            "-u {}",
+            shell_quote(&format!("{}:{}", username, password))
+        ));
+    }
+    if let Some(content) = p.text_body() {
+        args.push(format!("--data-raw {}", shell_quote(content)));
+    }
+    for field in p.form_fields() {
+        args.push(format!(
+            "--data-urlencode {}",
+            shell_quote(&format!("{}={}", field.name, field.value))
+        ));
+    }
+    for part in p.parts() {
+        let arg = match file_path(part) {
+            Some(path) => {
+                let mut value = format!("{}=@{}", part.name, path);
+                if let Some(content_type) = &part.content_type {
+                    value.push_str(&format!(";type={}", content_type));
+                }
+                format!("-F {}", shell_quote(&value))
+            }
+            None => format!(
+                "--form-string {}",
+                shell_quote(&format!(
+                    "{}={}",
+                    part.name,
+                    part.value.as_deref().unwrap_or("")
+                ))
+            ),
+        };
+        args.push(arg);
+    }
+    if p.follow_redirects() {
+        args.push("-L".to_string());
+        if let Some(max) = p.request.max_redirects {
+            args.push(format!("--max-redirs {}", max));
+        }
+    }
+    if !p.verify_tls() {
+        args.push("-k".to_string());
+    }
+    if p.request.compressed.unwrap_or(false) {
+        args.push("--compressed".to_string());
+    }
+    if let Some(proxy) = p.proxy() {
+        args.push(format!("-x {}", shell_quote(proxy)));
+    }
+    if let Some(timeout) = p.request.timeout_ms {
+        args.push(format!("-m {}", timeout as f64 / 1000.0));
+    }
+    args.join(" \\\n  ")
+}
+
+fn to_httpie(p: &Prepared) -> String {
+    let mut args = vec!["http".to_string()];
+    let fields = p.form_fields();
+    let parts = p.parts();
+    if !fields.is_empty() {
+        args.push("--form".to_string());
+    } else if !parts.is_empty() {
+        args.push("--multipart".to_string());
+    }
+    if let Some(content) = p.text_body() {
+        args.push(format!("--raw {}", shell_quote(content)));
+    }
+    if let Some((username, password)) = p.basic_auth() {
+        args.push(format!(
+            "-a {}",
+            shell_quote(&format!("{}:{}", username, password))
+        ));
+    }
+    if p.follow_redirects() {
+        args.push("--follow".to_string());
+        if let Some(max) = p.request.max_redirects {
+            args.push(format!("--max-redirects={}", max));
+        }
+    }
+    if !p.verify_tls() {
+        args.push("--verify=no".to_string());
+    }
+    if let Some(proxy) = p.proxy() {
+        args.push(shell_quote(&format!("--proxy=http:{}", proxy)));
+        args.push(shell_quote(&format!("--proxy=https:{}", proxy)));
+    }
+    if let Some(timeout) = p.request.timeout_ms {
+        args.push(format!("--timeout={}", timeout as f64 / 1000.0));
+    }
+    args.push(p.method.clone());
+    args.push(shell_quote(&p.url));
+    for (name, value) in &p.headers {
+        args.push(shell_quote(&format!("{}:{}", name, value)));
+    }
+    for field in fields {
+        args.push(shell_quote(&format!("{}={}", field.name, field.value)));
+    }
+    for part in parts {
+        let item = match file_path(part) {
+            Some(path) => match &part.content_type {
+                Some(content_type) => format!("{}@{};type={}", part.name, path, content_type),
+                None => format!("{}@{}", part.name, path),
+            },
+            None => format!("{}={}", part.name, part.value.as_deref().unwrap_or("")),
+        };
+        args.push(shell_quote(&item));
+    }
+    args.join(" \\\n  ")
+}
+
+/// Rust 字符串字面量
+fn rust_quote(s: &str) -> String {
+    format!("{:?}", s)
+}
+
+fn to_reqwest(p: &Prepared) -> String {
+    let mut out = String::new();
+    let parts = p.parts();
+    if !parts.is_empty() {
+        out.push_str("let form = reqwest::multipart::Form::new()");
+        for part in &parts {
+            match (file_path(part), &part.content_type) {
+                (Some(path), _) => {
+                    let _ = write!(
+                        out,
+                        "\n    .file({}, {})\n    .await?",
+                        rust_quote(&part.name),
+                        rust_quote(path)
+                    );
+                }
+                (None, Some(content_type)) => {
+                    let _ = write!(
+                        out,
+                        "\n    .part({}, reqwest::multipart::Part::text({}).mime_str({})?)",
+                        rust_quote(&part.name),
+                        rust_quote(part.value.as_deref().unwrap_or("")),
+                        rust_quote(content_type)
+                    );
+                }
+                (None, None) => {
+                    let _ = write!(
+                        out,
+                        "\n    .text({}, {})",
+                        rust_quote(&part.name),
+                        rust_quote(part.value.as_deref().unwrap_or(""))
+                    );
+                }
+            }
+        }
+        out.push_str(";\n");
+    }
+
+    out.push_str("let client = reqwest::Client::builder()");
+    if !p.verify_tls() {
+        out.push_str("\n    .danger_accept_invalid_certs(true)");
+    }
+    if !p.follow_redirects() {
+        out.push_str("\n    .redirect(reqwest::redirect::Policy::none())");
+    } else if let Some(max) = p.request.max_redirects {
+        let _ = write!(
+            out,
+            "\n    .redirect(reqwest::redirect::Policy::limited({}))",
+            max
+        );
+    }
+    if let Some(proxy) = p.proxy() {
+        let _ = write!(
+            out,
+            "\n    .proxy(reqwest::Proxy::all({})?)",
+            rust_quote(proxy)
+        );
+    }
+    if let Some(timeout) = p.request.timeout_ms {
+        let _ = write!(
+            out,
+            "\n    .timeout(std::time::Duration::from_millis({}))",
+            timeout
+        );
+    }
+    out.push_str("\n    .build()?;\n");
+
+    let method = match p.method.as_str() {
+        "GET" | "POST" | "PUT" | "DELETE" | "HEAD" | "OPTIONS" | "CONNECT" | "PATCH" | "TRACE" => {
+            format!("reqwest::Method::{}", p.method)
+        }
+        other => format!("reqwest::Method::from_bytes(b{})?", rust_quote(other)),
+    };
+    let _ = write!(
+        out,
+        "let response = client\n    .request({}, {})",
+        method,
+        rust_quote(&p.url)
+    );
+    for (name, value) in &p.headers {
+        let _ = write!(
+            out,
+            "\n    .header({}, {})",
+            rust_quote(name),
+            rust_quote(value)
+        );
+    }
+    if let Some((username, password)) = p.basic_auth() {
+        let _ = write!(
+            out,
+            "\n    .basic_auth({}, Some({}))",
+            rust_quote(username),
+            rust_quote(password)
+        );
+    }
+    if let Some(content) = p.text_body() {
+        let _ = write!(out, "\n    .body({})", rust_quote(content));
+    }
+    let fields = p.form_fields();
+    if !fields.is_empty() {
+        let pairs: Vec<String> = fields
+            .iter()
+            .map(|f| format!("({}, {})", rust_quote(&f.name), rust_quote(&f.value)))
+            .collect();
+        let _ = write!(out, "\n    .form(&[{}])", pairs.join(", "));
+    }
+    if !parts.is_empty() {
+        out.push_str("\n    .multipart(form)");
+    }
+    out.push_str("\n    .send()\n    .await?;\n");
+    out.push_str("println!(\"{}\", response.status());\n");
+    out.push_str("println!(\"{}\", response.text().await?);");
+    out
+}
+
+fn to_okhttp(p: &Prepared) -> String {
+    let mut out = String::new();
+    out.push_str("OkHttpClient client = new OkHttpClient.Builder()");
+    if !p.follow_redirects() {
+        out.push_str("\n    .followRedirects(false)");
+    }
+    if let Some(timeout) = p.request.timeout_ms {
+        let _ = write!(
+            out,
+            "\n    .callTimeout({}, TimeUnit.MILLISECONDS)",
+            timeout
+        );
+    }
+    if let Some(proxy) = p.proxy() {
+        let with_scheme = if proxy.contains("://") {
+            proxy.to_string()
+        } else {
+            format!("http://{}", proxy)
+        };
+        if let Ok(url) = Url::parse(&with_scheme) {
+            let _ = write!(
+                out,
+                "\n    .proxy(new Proxy(Proxy.Type.HTTP, new InetSocketAddress({}, {})))",
+                quote(url.host_str().unwrap_or("")),
+                url.port_or_known_default().unwrap_or(80)
+            );
+        }
+    }
+    out.push_str("\n    .build();\n");
+    if !p.verify_tls() {
+        out.push_str(
+            "// OkHttp 需要自定义 SSLSocketFactory 与 HostnameVerifier 才能跳过证书校验\n",
+        );
+    }
+    out.push('\n');
+
+    let fields = p.form_fields();
+    let parts = p.parts();
+    // OkHttp 的 GET、HEAD 请求不能带请求体
+    let allows_body = p.method != "GET" && p.method != "HEAD";
+    let body = if !allows_body {
+        "null".to_string()
+    } else if let Some(content) = p.text_body() {
+        let content_type = p
+            .headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| format!("MediaType.parse({})", quote(value)))
+            .unwrap_or_else(|| "null".to_string());
+        let _ = writeln!(
+            out,
+            "RequestBody body = RequestBody.create({}, {});",
+            quote(content),
+            content_type
+        );
+        "body".to_string()
+    } else if !fields.is_empty() {
+        out.push_str("RequestBody body = new FormBody.Builder()");
+        for field in fields {
+            let _ = write!(
+                out,
+                "\n    .add({}, {})",
+                quote(&field.name),
+                quote(&field.value)
+            );
+        }
+        out.push_str("\n    .build();\n");
+        "body".to_string()
+    } else if !parts.is_empty() {
+        out.push_str(
+            "RequestBody body = new MultipartBody.Builder()\n    .setType(MultipartBody.FORM)",
+        );
+        for part in parts {
+            match file_path(part) {
+                Some(path) => {
+                    let _ = write!(
+                        out,
+                        "\n    .addFormDataPart({}, {}, RequestBody.create(new File({}), MediaType.parse({})))",
+                        quote(&part.name),
+                        quote(&file_name(path)),
+                        quote(path),
+                        quote(part.content_type.as_deref().unwrap_or("application/octet-stream"))
+                    );
+                }
+                None => {
+                    let _ = write!(
+                        out,
+                        "\n    .addFormDataPart({}, {})",
+                        quote(&part.name),
+                        quote(part.value.as_deref().unwrap_or(""))
+                    );
+                }
+            }
+        }
+        out.push_str("\n    .build();\n");
+        "body".to_string()
+    } else if matches!(p.method.as_str(), "POST" | "PUT" | "PATCH") {
+        "RequestBody.create(new byte[0], null)".to_string()
+    } else {
+        "null".to_string()
+    };
+
+    let _ = write!(
+        out,
+        "Request request = new Request.Builder()\n    .url({})\n    .method({}, {})",
+        quote(&p.url),
+        quote(&p.method),
+        body
+    );
+    for (name, value) in &p.headers {
+        let _ = write!(out, "\n    .addHeader({}, {})", quote(name), quote(value));
+    }
+    if let Some((username, password)) = p.basic_auth() {
+        let _ = write!(
+            out,
+            "\n    .addHeader(\"Authorization\", Credentials.basic({}, {}))",
+            quote(username),
+            quote(password)
+        );
+    }
+    out.push_str("\n    .build();\n\n");
+    out.push_str("try (Response response = client.newCall(request).execute()) {\n");
+    out.push_str("    System.out.println(response.code());\n");
+    out.push_str("    System.out.println(response.body().string());\n");
+    out.push('}');
+    out
+}
+
+fn to_python_requests(p: &Prepared) -> String {
+    let mut out = String::from("import requests\n\n");
+    let _ = writeln!(out, "url = {}", quote(&p.url));
+    let mut args = vec![quote(&p.method), "url".to_string()];
+    if !p.headers.is_empty() {
+        out.push_str("headers = {\n");
+        for (name, value) in &p.headers {
+            let _ = writeln!(out, "    {}: {},", quote(name), quote(value));
+        }
+        out.push_str("}\n");
+        args.push("headers=headers".to_string());
+    }
+    if let Some(content) = p.text_body() {
+        let _ = writeln!(out, "data = {}", quote(content));
+        args.push("data=data.encode(\"utf-8\")".to_string());
+    }
+    let fields = p.form_fields();
+    if !fields.is_empty() {
+        out.push_str("data = [\n");
+        for field in fields {
+            let _ = writeln!(
+                out,
+                "    ({}, {}),",
+                quote(&field.name),
+                quote(&field.value)
+            );
+        }
+        out.push_str("]\n");
+        args.push("data=data".to_string());
+    }
+    let parts = p.parts();
+    if !parts.is_empty() {
+        out.push_str("files = [\n");
+        for part in parts {
+            let content_type = part
+                .content_type
+                .as_deref()
+                .map(|t| format!(", {}", quote(t)))
+                .unwrap_or_default();
+            match file_path(part) {
+                Some(path) => {
+                    let _ = writeln!(
+                        out,
+                        "    ({}, ({}, open({}, \"rb\"){})),",
+                        quote(&part.name),
+                        quote(&file_name(path)),
+                        quote(path),
+                        content_type
+                    );
+                }
+                None => {
+                    let _ = writeln!(
+                        out,
+                        "    ({}, (None, {}{})),",
+                        quote(&part.name),
+                        quote(part.value.as_deref().unwrap_or("")),
+                        content_type
+                    );
+                }
+            }
+        }
+        out.push_str("]\n");
+        args.push("files=files".to_string());
+    }
+    if let Some((username, password)) = p.basic_auth() {
+        args.push(format!("auth=({}, {})", quote(username), quote(password)));
+    }
+    if !p.verify_tls() {
+        args.push("verify=False".to_string());
+    }
+    if !p.follow_redirects() {
+        args.push("allow_redirects=False".to_string());
+    }
+    if let Some(timeout) = p.request.timeout_ms {
+        args.push(format!("timeout={}", timeout as f64 / 1000.0));
+    }
+    if let Some(proxy) = p.proxy() {
+        args.push(format!(
+            "proxies={{\"http\": {proxy}, \"https\": {proxy}}}",
+            proxy = quote(proxy)
+        ));
+    }
+    out.push_str("\nresponse = requests.request(\n");
+    for arg in args {
+        let _ = writeln!(out, "    {},", arg);
+    }
+    out.push_str(")\n");
+    out.push_str("print(response.status_code)\n");
+    out.push_str("print(response.text)");
+    out
+}
+
+fn to_fetch(p: &Prepared) -> String {
+    let mut out = String::new();
+    let parts = p.parts();
+    if parts.iter().any(|part| file_path(part).is_some()) {
+        out.push_str("import fs from \"node:fs\";\n\n");
+    }
+    if !parts.is_empty() {
+        out.push_str("const formData = new FormData();\n");
+        for part in &parts {
+            match file_path(part) {
+                Some(path) => {
+                    let options = part
+                        .content_type
+                        .as_deref()
+                        .map(|t| format!(", {{type: {}}}", quote(t)))
+                        .unwrap_or_default();
+                    let _ = writeln!(
+                        out,
+                        "formData.append({}, await fs.openAsBlob({}{}), {});",
+                        quote(&part.name),
+                        quote(path),
+                        options,
+                        quote(&file_name(path))
+                    );
+                }
+                None => {
+                    let _ = writeln!(
+                        out,
+                        "formData.append({}, {});",
+                        quote(&part.name),
+                        quote(part.value.as_deref().unwrap_or(""))
+                    );
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    let _ = write!(
+        out,
+        "const response = await fetch({}, {{\n  method: {},\n",
+        quote(&p.url),
+        quote(&p.method)
+    );
+    let mut headers: Vec<String> = p
+        .headers
+        .iter()
+        .map(|(name, value)| format!("    {}: {},", quote(name), quote(value)))
+        .collect();
+    if let Some((username, password)) = p.basic_auth() {
+        headers.push(format!(
+            "    \"Authorization\": \"Basic \" + btoa({}),",
+            quote(&format!("{}:{}", username, password))
+        ));
+    }
+    if !headers.is_empty() {
+        let _ = writeln!(out, "  headers: {{\n{}\n  }},", headers.join("\n"));
+    }
+    if let Some(content) = p.text_body() {
+        let _ = writeln!(out, "  body: {},", quote(content));
+    }
+    let fields = p.form_fields();
+    if !fields.is_empty() {
+        let pairs: Vec<String> = fields
+            .iter()
+            .map(|f| format!("[{}, {}]", quote(&f.name), quote(&f.value)))
+            .collect();
+        let _ = writeln!(out, "  body: new URLSearchParams([{}]),", pairs.join(", "));
+    }
+    if !parts.is_empty() {
+        out.push_str("  body: formData,\n");
+    }
+    if !p.follow_redirects() {
+        out.push_str("  redirect: \"manual\",\n");
+    }
+    if let Some(timeout) = p.request.timeout_ms {
+        let _ = writeln!(out, "  signal: AbortSignal.timeout({}),", timeout);
+    }
+    out.push_str("});\n");
+    out.push_str("console.log(response.status);\n");
+    out.push_str("console.log(await response.text());");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_command() {
+        let args = split_command(
+            "curl 'https://a.test/x?y=1' \\\n  -H \"X-Quote: \\\"q\\\" \\$HOME\" \\\r\n  --data-raw $'{\"k\":\"\\u4e2d\\xe6\\x96\\x87\\n\"}' # 注释\n",
+        )
+        .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "curl",
+                "https://a.test/x?y=1",
+                "-H",
+                "X-Quote: \"q\" $HOME",
+                "--data-raw",
+                "{\"k\":\"中文\n\"}",
+            ]
+        );
+        assert!(split_command("curl 'abc").is_err());
+    }
+
+    #[test]
+    fn test_parse_devtools_curl() {
+        let request = parse_curl(
+            r#"curl 'https://api.test/v1/items' \
+  -H 'accept: application/json' \
+  -H 'content-type: application/json' \
+  -b 'sid=abc' \
+  --data-raw '{"name":"x"}' \
+  --compressed -sSLk -m 2.5"#,
+        )
+        .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.url, "https://api.test/v1/items");
+        assert_eq!(
+            request.headers,
+            vec![
+                KeyValue::new("accept", "application/json"),
+                KeyValue::new("content-type", "application/json"),
+                KeyValue::new("Cookie", "sid=abc"),
+            ]
+        );
+        assert_eq!(
+            request.body,
+            HttpBody::Json {
+                content: r#"{"name":"x"}"#.to_string()
+            }
+        );
+        assert_eq!(request.compressed, Some(true));
+        assert_eq!(request.follow_redirects, Some(true));
+        assert_eq!(request.verify_tls, Some(false));
+        assert_eq!(request.timeout_ms, Some(2500));
+    }
+
+    #[test]
+    fn test_parse_form_auth_and_get() {
+        let request = parse_curl(
+            "curl -XPUT -u admin:p:w -d a=1 -d 'b=x%20y' -x 127.0.0.1:8080 --proxy-user u:p http://h.test",
+        )
+        .unwrap();
+        assert_eq!(request.method, "PUT");
+        assert_eq!(
+            request.auth,
+            Some(HttpAuth::Basic {
+                username: "admin".to_string(),
+                password: "p:w".to_string()
+            })
+        );
+        assert_eq!(
+            request.body,
+            HttpBody::Form {
+                fields: vec![KeyValue::new("a", "1"), KeyValue::new("b", "x y")]
+            }
+        );
+        assert_eq!(request.proxy.as_deref(), Some("http://u:p@127.0.0.1:8080"));
+        assert_eq!(request.follow_redirects, Some(false));
+
+        let request =
+            parse_curl("curl -G http://h.test?x=1 --data-urlencode 'q=a b' -d raw").unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.url, "http://h.test?x=1&q=a+b&raw");
+        assert_eq!(request.body, HttpBody::None);
+
+        let request = parse_curl(
+            "curl http://h.test -F 'file=@/tmp/a.png;type=image/png' -F 'note=hi' --form-string 'raw=@x'",
+        )
+        .unwrap();
+        assert_eq!(request.method, "POST");
+        let HttpBody::Multipart { parts } = request.body else {
+            panic!("expected multipart body");
+        };
+        assert_eq!(parts[0].file_path.as_deref(), Some("/tmp/a.png"));
+        assert_eq!(parts[0].content_type.as_deref(), Some("image/png"));
+        assert_eq!(parts[1].value.as_deref(), Some("hi"));
+        assert_eq!(parts[2].value.as_deref(), Some("@x"));
+
+        assert!(parse_curl("curl -H 'a: b'").is_err());
+        assert!(parse_curl("curl http://h.test -d @body.json").is_err());
+    }
+
+    #[test]
+    fn test_curl_round_trip() {
+        let request = HttpRequest {
+            method: "PATCH".to_string(),
+            url: "https://h.test/p".to_string(),
+            headers: vec![KeyValue::new("X-Note", "it's")],
+            query: vec![KeyValue::new("q", "a b")],
+            body: HttpBody::Form {
+                fields: vec![KeyValue::new("k", "v&w")],
+            },
+            auth: Some(HttpAuth::Basic {
+                username: "u".to_string(),
+                password: "p".to_string(),
+            }),
+            follow_redirects: Some(true),
+            max_redirects: Some(3),
+            proxy: Some("http://127.0.0.1:8080".to_string()),
+            timeout_ms: Some(1500),
+            verify_tls: Some(false),
+            compressed: Some(true),
+        };
+        let command = generate_http_snippet(request.clone(), SnippetLanguage::Curl).unwrap();
+        let parsed = parse_curl(&command).unwrap();
+        assert_eq!(
+            parsed,
+            HttpRequest {
+                url: "https://h.test/p?q=a+b".to_string(),
+                query: Vec::new(),
+                ..request
+            }
+        );
+    }
+
+    #[test]
+    fn test_generate_snippets() {
+        let request = HttpRequest {
+            method: "POST".to_string(),
+            url: "http://h.test/api".to_string(),
+            body: HttpBody::Json {
+                content: "{\"a\":\"\\\"\"}".to_string(),
+            },
+            auth: Some(HttpAuth::Bearer {
+                token: "t0k".to_string(),
+            }),
+            follow_redirects: Some(false),
+            ..Default::default()
+        };
+        let snippet = |language| generate_http_snippet(request.clone(), language).unwrap();
+
+        assert_eq!(
+            snippet(SnippetLanguage::Curl),
+            "curl \\\n  http://h.test/api \\\n  -H 'Content-Type: application/json' \\\n  -H 'Authorization: Bearer t0k' \\\n  --data-raw '{\"a\":\"\\\"\"}'"
+        );
+        assert!(snippet(SnippetLanguage::Httpie)
+            .contains("--raw '{\"a\":\"\\\"\"}' \\\n  POST \\\n  http://h.test/api"));
+        let rust = snippet(SnippetLanguage::RustReqwest);
+        assert!(rust.contains(".redirect(reqwest::redirect::Policy::none())"));
+        assert!(rust.contains(".request(reqwest::Method::POST, \"http://h.test/api\")"));
+        assert!(rust.contains(".body(\"{\\\"a\\\":\\\"\\\\\\\"\\\"}\")"));
+        let java = snippet(SnippetLanguage::JavaOkhttp);
+        assert!(java.contains("RequestBody.create(\"{\\\"a\\\":\\\"\\\\\\\"\\\"}\", MediaType.parse(\"application/json\"))"));
+        assert!(java.contains(".addHeader(\"Authorization\", \"Bearer t0k\")"));
+        let python = snippet(SnippetLanguage::PythonRequests);
+        assert!(python.contains("    allow_redirects=False,\n"));
+        assert!(python.contains("    \"Authorization\": \"Bearer t0k\",\n"));
+        let js = snippet(SnippetLanguage::JsFetch);
+        assert!(js.contains("  redirect: \"manual\",\n"));
+        assert!(js.contains("  method: \"POST\",\n"));
+    }
+}
@@ -0,0 +1,1876 @@
+use crate::utils::http_client::Io;
+use crate::utils::id_generator::to_hex;
+use crate::utils::network_util::{
+    connect_with_proxy, CheckPortResult, DEFAULT_CONNECT_TIMEOUT_MS, MAX_CONNECT_TIMEOUT_MS,
+};
+use crate::utils::tls_inspector::client_config;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, Connection};
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::TlsConnector;
+
+/// 握手时上报的客户端名称
+const CLIENT_NAME: &str = "lin-toolbox";
+/// 单条协议消息的最大长度
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+// MySQL 客户端能力标志
+const CLIENT_LONG_PASSWORD: u32 = 0x1;
+const CLIENT_LONG_FLAG: u32 = 0x4;
+const CLIENT_CONNECT_WITH_DB: u32 = 0x8;
+const CLIENT_PROTOCOL_41: u32 = 0x200;
+const CLIENT_SSL: u32 = 0x800;
+const CLIENT_TRANSACTIONS: u32 = 0x2000;
+const CLIENT_SECURE_CONNECTION: u32 = 0x8000;
+const CLIENT_PLUGIN_AUTH: u32 = 0x80000;
+const CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA: u32 = 0x200000;
+/// MySQL 的 utf8mb4_general_ci 字符集编号
+const MYSQL_UTF8MB4: u8 = 45;
+
+/// PostgreSQL SSLRequest 请求码
+const PG_SSL_REQUEST: u32 = 80877103;
+/// PostgreSQL 协议版本 3.0
+const PG_PROTOCOL_3: u32 = 196608;
+
+/// MongoDB OP_MSG 操作码
+const OP_MSG: i32 = 2013;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DbKind {
+    Mysql,
+    Postgres,
+    Redis,
+    Mongodb,
+    Sqlite,
+}
+
+impl DbKind {
+    fn default_port(self) -> u16 {
+        match self {
+            DbKind::Mysql => 3306,
+            DbKind::Postgres => 5432,
+            DbKind::Redis => 6379,
+            DbKind::Mongodb => 27017,
+            DbKind::Sqlite => 0,
+        }
+    }
+}
+
+/// TLS 模式，Redis 与 MongoDB 无法在明文连接上协商，只在 require 时使用 TLS
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TlsMode {
+    Disable,
+    /// 服务端支持时使用 TLS
+    #[default]
+    Prefer,
+    Require,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct DbCheckRequest {
+    pub kind: DbKind,
+    /// 主机，默认 127.0.0.1
+    pub host: Option<String>,
+    /// 端口，默认使用各数据库的默认端口
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// MySQL、PostgreSQL 为数据库名，Redis 为库编号，MongoDB 为认证库（默认 admin），SQLite 为文件路径
+    pub database: Option<String>,
+    #[serde(default)]
+    pub tls: TlsMode,
+    /// 是否校验服务端证书，默认校验
+    pub verify_tls: Option<bool>,
+    /// 超时时间（毫秒），包含连接与认证，默认 5000
+    pub timeout_ms: Option<u64>,
+    /// 代理地址，为空时使用全局代理，`direct` 表示直接连接
+    pub proxy: Option<String>,
+}
+
+/// 检测失败的原因
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DbErrorKind {
+    /// TCP 连接失败，详见连接结果
+    Connect,
+    Timeout,
+    /// 用户名或密码错误、没有权限或服务端要求认证
+    AuthFailed,
+    UnknownDatabase,
+    /// 服务端要求使用 TLS 连接
+    TlsRequired,
+    /// TLS 握手失败或服务端不支持 TLS
+    Tls,
+    /// 不支持的认证方式
+    Unsupported,
+    /// 不是预期的数据库协议
+    Protocol,
+    /// 服务端返回的其他错误
+    Server,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DbCheckResult {
+    kind: DbKind,
+    success: bool,
+    /// TCP 连接结果，SQLite 为空
+    connect: Option<CheckPortResult>,
+    server_version: Option<String>,
+    /// 是否使用了 TLS
+    tls: bool,
+    /// 认证方式，如 caching_sha2_password、SCRAM-SHA-256
+    auth_method: Option<String>,
+    /// 从开始连接到完成认证的耗时（毫秒）
+    latency_ms: f64,
+    error_kind: Option<DbErrorKind>,
+    /// 服务端返回的错误码，如 MySQL 的 1045、PostgreSQL 的 28P01
+    error_code: Option<String>,
+    message: Option<String>,
+}
+
+#[derive(Debug)]
+struct DbError {
+    kind: DbErrorKind,
+    code: Option<String>,
+    message: String,
+}
+
+impl DbError {
+    fn new(kind: DbErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            code: None,
+            message: message.into(),
+        }
+    }
+
+    fn with_code(mut self, code: impl ToString) -> Self {
+        self.code = Some(code.to_string());
+        self
+    }
+
+    fn io(e: io::Error) -> Self {
+        let message = if e.kind() == io::ErrorKind::UnexpectedEof {
+            "服务端关闭了连接".to_string()
+        } else {
+            format!("连接中断: {}", e)
+        };
+        Self::new(DbErrorKind::Protocol, message)
+    }
+
+    fn protocol(message: &str) -> Self {
+        Self::new(DbErrorKind::Protocol, message)
+    }
+}
+
+/// 握手成功后得到的服务端信息
+#[derive(Default)]
+struct Handshake {
+    server_version: Option<String>,
+    auth_method: Option<String>,
+    tls: bool,
+}
+
+/// 连接数据库并完成认证，检测失败时在结果中说明原因
+#[tauri::command]
+pub async fn check_database(request: DbCheckRequest) -> Result<DbCheckResult, String> {
+    let timeout_ms = request.timeout_ms.unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS);
+    if timeout_ms == 0 || timeout_ms > MAX_CONNECT_TIMEOUT_MS {
+        return Err(format!(
+            "超时时间应在 1-{} 毫秒之间",
+            MAX_CONNECT_TIMEOUT_MS
+        ));
+    }
+    Ok(check(&request, Duration::from_millis(timeout_ms)).await)
+}
+
+pub async fn check(request: &DbCheckRequest, timeout: Duration) -> DbCheckResult {
+    let start = Instant::now();
+    let mut result = DbCheckResult {
+        kind: request.kind,
+        success: false,
+        connect: None,
+        server_version: None,
+        tls: false,
+        auth_method: None,
+        latency_ms: 0.0,
+        error_kind: None,
+        error_code: None,
+        message: None,
+    };
+    let outcome = if request.kind == DbKind::Sqlite {
+        tokio::time::timeout(timeout, check_sqlite(request)).await
+    } else {
+        let host = non_empty(&request.host).unwrap_or("127.0.0.1");
+        let port = request.port.unwrap_or(request.kind.default_port());
+        let (connect, stream) =
+            connect_with_proxy(host, port, timeout, request.proxy.as_deref()).await;
+        let message = connect.message.clone();
+        result.connect = Some(connect);
+        match stream {
+            Some(stream) => {
+                let remaining = timeout.saturating_sub(start.elapsed());
+                tokio::time::timeout(remaining, handshake(stream, request, host)).await
+            }
+            None => Ok(Err(DbError::new(
+                DbErrorKind::Connect,
+                message.unwrap_or_else(|| "连接失败".to_string()),
+            ))),
+        }
+    };
+    result.latency_ms = start.elapsed().as_secs_f64() * 1000.0;
+    match outcome.unwrap_or_else(|_| Err(DbError::new(DbErrorKind::Timeout, "等待服务端响应超时")))
+    {
+        Ok(handshake) => {
+            result.success = true;
+            result.server_version = handshake.server_version;
+            result.auth_method = handshake.auth_method;
+            result.tls = handshake.tls;
+        }
+        Err(e) => {
+            result.error_kind = Some(e.kind);
+            result.error_code = e.code;
+            result.message = Some(e.message);
+        }
+    }
+    result
+}
+
+async fn handshake(
+    stream: TcpStream,
+    request: &DbCheckRequest,
+    host: &str,
+) -> Result<Handshake, DbError> {
+    match request.kind {
+        DbKind::Mysql => check_mysql(stream, request, host).await,
+        DbKind::Postgres => check_postgres(stream, request, host).await,
+        DbKind::Redis => check_redis(stream, request, host).await,
+        DbKind::Mongodb => check_mongodb(stream, request, host).await,
+        DbKind::Sqlite => check_sqlite(request).await,
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|s| !s.is_empty())
+}
+
+fn lossy(data: &[u8]) -> String {
+    String::from_utf8_lossy(data).into_owned()
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(x, y)| x ^ y).collect()
+}
+
+async fn start_tls(
+    stream: TcpStream,
+    host: &str,
+    request: &DbCheckRequest,
+) -> Result<Box<dyn Io>, DbError> {
+    let config = client_config(request.verify_tls.unwrap_or(true))
+        .map_err(|e| DbError::new(DbErrorKind::Tls, e))?;
+    let name = host.trim_matches(['[', ']']).to_string();
+    let server_name = ServerName::try_from(name)
+        .map_err(|e| DbError::new(DbErrorKind::Tls, format!("无效的主机名 {}: {}", host, e)))?;
+    let tls = TlsConnector::from(Arc::new(config))
+        .connect(server_name, stream)
+        .await
+        .map_err(|e| DbError::new(DbErrorKind::Tls, format!("TLS 握手失败: {}", e)))?;
+    Ok(Box::new(tls))
+}
+
+/// 按协议格式读取字节
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let data = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(data)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u16_le(&mut self) -> Option<u16> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn i32_le(&mut self) -> Option<i32> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn i32_be(&mut self) -> Option<i32> {
+        self.array().map(i32::from_be_bytes)
+    }
+
+    /// 以 0 结尾的字符串，不含结尾的 0
+    fn cstr(&mut self) -> Option<&'a [u8]> {
+        let rest = self.data.get(self.pos..)?;
+        let end = rest.iter().position(|b| *b == 0)?;
+        self.pos += end + 1;
+        Some(&rest[..end])
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let rest = self.data.get(self.pos..).unwrap_or_default();
+        self.pos = self.data.len();
+        rest
+    }
+
+    /// 剩余的字节，不移动位置
+    fn rest_peek(&self) -> &'a [u8] {
+        self.data.get(self.pos..).unwrap_or_default()
+    }
+}
+
+/// SCRAM-SHA-256 客户端（RFC 5802、RFC 7677），不使用通道绑定
+struct Scram {
+    password: String,
+    nonce: String,
+    client_first_bare: String,
+    server_signature: Option<Vec<u8>>,
+}
+
+impl Scram {
+    fn new(username: &str, password: &str) -> Self {
+        let nonce = base64::engine::general_purpose::STANDARD.encode(rand::random::<[u8; 18]>());
+        Self::with_nonce(username, password, &nonce)
+    }
+
+    fn with_nonce(username: &str, password: &str, nonce: &str) -> Self {
+        let username = username.replace('=', "=3D").replace(',', "=2C");
+        Self {
+            password: password.to_string(),
+            nonce: nonce.to_string(),
+            client_first_bare: format!("n={},r={}", username, nonce),
+            server_signature: None,
+        }
+    }
+
+    fn client_first(&self) -> String {
+        format!("n,,{}", self.client_first_bare)
+    }
+
+    fn client_final(&mut self, server_first: &str) -> Result<String, DbError> {
+        let attr = |key: char| {
+            server_first
+                .split(',')
+                .find_map(|part| part.strip_prefix(key)?.strip_prefix('='))
+        };
+        if let Some(error) = attr('e') {
+            return Err(DbError::new(
+                DbErrorKind::AuthFailed,
+                format!("认证失败: {}", error),
+            ));
+        }
+        let invalid = || DbError::protocol("无效的 SCRAM 响应");
+        let nonce = attr('r')
+            .filter(|r| r.starts_with(&self.nonce))
+            .ok_or_else(invalid)?;
+        let salt = base64::engine::general_purpose::STANDARD
+            .decode(attr('s').ok_or_else(invalid)?)
+            .map_err(|_| invalid())?;
+        let iterations = attr('i')
+            .and_then(|i| i.parse::<u32>().ok())
+            .filter(|i| *i > 0)
+            .ok_or_else(invalid)?;
+
+        let salted = pbkdf2_sha256(self.password.as_bytes(), &salt, iterations);
+        let client_key = hmac_sha256(&salted, b"Client Key");
+        let stored_key = Sha256::digest(&client_key);
+        let without_proof = format!("c=biws,r={}", nonce);
+        let auth_message = format!(
+            "{},{},{}",
+            self.client_first_bare, server_first, without_proof
+        );
+        let proof = xor(
+            &client_key,
+            &hmac_sha256(&stored_key, auth_message.as_bytes()),
+        );
+        let server_key = hmac_sha256(&salted, b"Server Key");
+        self.server_signature = Some(hmac_sha256(&server_key, auth_message.as_bytes()));
+        Ok(format!(
+            "{},p={}",
+            without_proof,
+            base64::engine::general_purpose::STANDARD.encode(proof)
+        ))
+    }
+
+    fn verify_final(&self, server_final: &str) -> Result<(), DbError> {
+        if let Some(error) = server_final.strip_prefix("e=") {
+            return Err(DbError::new(
+                DbErrorKind::AuthFailed,
+                format!("认证失败: {}", error),
+            ));
+        }
+        let signature = server_final
+            .strip_prefix("v=")
+            .and_then(|v| base64::engine::general_purpose::STANDARD.decode(v).ok());
+        match (signature, &self.server_signature) {
+            (Some(signature), Some(expected)) if &signature == expected => Ok(()),
+            _ => Err(DbError::protocol("服务端签名校验失败")),
+        }
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC 接受任意长度的密钥");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// PBKDF2-HMAC-SHA256，输出长度等于摘要长度，只需计算一个块
+fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32) -> Vec<u8> {
+    let mut block = salt.to_vec();
+    block.extend_from_slice(&1u32.to_be_bytes());
+    let mut u = hmac_sha256(password, &block);
+    let mut result = u.clone();
+    for _ in 1..iterations {
+        u = hmac_sha256(password, &u);
+        for (r, b) in result.iter_mut().zip(&u) {
+            *r ^= b;
+        }
+    }
+    result
+}
+
+struct MysqlGreeting {
+    version: String,
+    capabilities: u32,
+    scramble: Vec<u8>,
+    plugin: String,
+}
+
+async fn check_mysql(
+    mut stream: TcpStream,
+    request: &DbCheckRequest,
+    host: &str,
+) -> Result<Handshake, DbError> {
+    let mut seq = 0u8;
+    let greeting = read_mysql_packet(&mut stream, &mut seq).await?;
+    if greeting.first() == Some(&0xFF) {
+        return Err(mysql_error(&greeting));
+    }
+    let greeting = parse_mysql_greeting(&greeting)?;
+    if greeting.capabilities & CLIENT_PROTOCOL_41 == 0 {
+        return Err(DbError::new(
+            DbErrorKind::Unsupported,
+            "不支持 MySQL 4.1 之前的协议",
+        ));
+    }
+    let database = non_empty(&request.database);
+    let username = non_empty(&request.username).unwrap_or("root");
+    let password = request.password.as_deref().unwrap_or("");
+
+    let mut capabilities = CLIENT_LONG_PASSWORD
+        | CLIENT_LONG_FLAG
+        | CLIENT_PROTOCOL_41
+        | CLIENT_TRANSACTIONS
+        | CLIENT_SECURE_CONNECTION
+        | CLIENT_PLUGIN_AUTH
+        | CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA;
+    if database.is_some() {
+        capabilities |= CLIENT_CONNECT_WITH_DB;
+    }
+    capabilities &= greeting.capabilities;
+    let use_tls = match (request.tls, greeting.capabilities & CLIENT_SSL != 0) {
+        (TlsMode::Disable, _) => false,
+        (TlsMode::Prefer, supported) => supported,
+        (TlsMode::Require, true) => true,
+        (TlsMode::Require, false) => {
+            return Err(DbError::new(DbErrorKind::Tls, "服务端未开启 TLS"))
+        }
+    };
+    let mut stream: Box<dyn Io> = if use_tls {
+        capabilities |= CLIENT_SSL;
+        write_mysql_packet(&mut stream, &mut seq, &mysql_client_prefix(capabilities)).await?;
+        start_tls(stream, host, request).await?
+    } else {
+        Box::new(stream)
+    };
+
+    let mut plugin = greeting.plugin.clone();
+    let auth = mysql_auth(&plugin, password, &greeting.scramble, use_tls)?;
+    let mut response = mysql_client_prefix(capabilities);
+    response.extend_from_slice(username.as_bytes());
+    response.push(0);
+    if capabilities & CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA != 0 {
+        response.extend_from_slice(&mysql_lenenc_int(auth.len() as u64));
+    } else {
+        let len = u8::try_from(auth.len()).map_err(|_| DbError::protocol("认证数据过长"))?;
+        response.push(len);
+    }
+    response.extend_from_slice(&auth);
+    if let (Some(database), true) = (database, capabilities & CLIENT_CONNECT_WITH_DB != 0) {
+        response.extend_from_slice(database.as_bytes());
+        response.push(0);
+    }
+    if capabilities & CLIENT_PLUGIN_AUTH != 0 {
+        response.extend_from_slice(plugin.as_bytes());
+        response.push(0);
+    }
+    write_mysql_packet(&mut stream, &mut seq, &response).await?;
+
+    loop {
+        let packet = read_mysql_packet(&mut stream, &mut seq).await?;
+        match packet.first() {
+            Some(0x00) => break,
+            Some(0xFF) => return Err(mysql_error(&packet)),
+            // 服务端要求切换认证方式
+            Some(0xFE) => {
+                let mut cursor = Cursor::new(&packet[1..]);
+                plugin = lossy(cursor.cstr().unwrap_or_default());
+                let data = cursor.rest();
+                let scramble = data.strip_suffix(&[0]).unwrap_or(data);
+                let auth = mysql_auth(&plugin, password, scramble, use_tls)?;
+                write_mysql_packet(&mut stream, &mut seq, &auth).await?;
+            }
+            // caching_sha2_password：3 为快速认证成功，4 为需要完整认证
+            Some(0x01) if plugin == "caching_sha2_password" => match packet.get(1) {
+                Some(3) => {}
+                Some(4) if use_tls => {
+                    let mut data = password.as_bytes().to_vec();
+                    data.push(0);
+                    write_mysql_packet(&mut stream, &mut seq, &data).await?;
+                }
+                Some(4) => {
+                    return Err(DbError::new(
+                        DbErrorKind::TlsRequired,
+                        "caching_sha2_password 需要完整认证，请开启 TLS 连接",
+                    ))
+                }
+                _ => return Err(DbError::protocol("无法识别的认证响应")),
+            },
+            _ => return Err(DbError::protocol("无法识别的认证响应")),
+        }
+    }
+
+    // COM_QUIT
+    let mut seq = 0;
+    let _ = write_mysql_packet(&mut stream, &mut seq, &[0x01]).await;
+    Ok(Handshake {
+        server_version: Some(greeting.version),
+        auth_method: Some(plugin),
+        tls: use_tls,
+    })
+}
+
+async fn read_mysql_packet<S: AsyncRead + Unpin + ?Sized>(
+    stream: &mut S,
+    seq: &mut u8,
+) -> Result<Vec<u8>, DbError> {
+    let mut header = [0u8; 4];
+    stream.read_exact(&mut header).await.map_err(DbError::io)?;
+    let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+    *seq = header[3].wrapping_add(1);
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload).await.map_err(DbError::io)?;
+    Ok(payload)
+}
+
+async fn write_mysql_packet<S: AsyncWrite + Unpin + ?Sized>(
+    stream: &mut S,
+    seq: &mut u8,
+    payload: &[u8],
+) -> Result<(), DbError> {
+    let mut packet = (payload.len() as u32).to_le_bytes()[..3].to_vec();
+    packet.push(*seq);
+    packet.extend_from_slice(payload);
+    *seq = seq.wrapping_add(1);
+    stream.write_all(&packet).await.map_err(DbError::io)
+}
+
+/// 初始握手包：协议版本 10、服务端版本、连接 ID、挑战码与能力标志
+fn parse_mysql_greeting(data: &[u8]) -> Result<MysqlGreeting, DbError> {
+    let parse = || {
+        let mut cursor = Cursor::new(data);
+        if cursor.u8()? != 10 {
+            return None;
+        }
+        let version = lossy(cursor.cstr()?);
+        cursor.take(4)?;
+        let mut scramble = cursor.take(8)?.to_vec();
+        cursor.take(1)?;
+        let mut capabilities = u32::from(cursor.u16_le()?);
+        let mut plugin = "mysql_native_password".to_string();
+        if !cursor.is_empty() {
+            cursor.take(3)?;
+            capabilities |= u32::from(cursor.u16_le()?) << 16;
+            let auth_len = usize::from(cursor.u8()?);
+            cursor.take(10)?;
+            if capabilities & CLIENT_SECURE_CONNECTION != 0 {
+                let part = cursor.take(auth_len.saturating_sub(8).max(13))?;
+                scramble.extend_from_slice(part.strip_suffix(&[0]).unwrap_or(part));
+            }
+            // 部分旧版本的插件名没有结尾的 0
+            if capabilities & CLIENT_PLUGIN_AUTH != 0 {
+                let name = match cursor.cstr() {
+                    Some(name) => name,
+                    None => cursor.rest(),
+                };
+                plugin = lossy(name);
+            }
+        }
+        Some(MysqlGreeting {
+            version,
+            capabilities,
+            scramble,
+            plugin,
+        })
+    };
+    parse().ok_or_else(|| DbError::protocol("不是 MySQL 服务或握手包无效"))
+}
+
+/// 握手响应与 SSLRequest 共同的开头：能力标志、最大包长度、字符集与 23 字节保留位
+fn mysql_client_prefix(capabilities: u32) -> Vec<u8> {
+    let mut data = capabilities.to_le_bytes().to_vec();
+    data.extend_from_slice(&(MAX_MESSAGE_SIZE as u32).to_le_bytes());
+    data.push(MYSQL_UTF8MB4);
+    data.extend_from_slice(&[0; 23]);
+    data
+}
+
+/// MySQL 的长度编码整数
+fn mysql_lenenc_int(value: u64) -> Vec<u8> {
+    match value {
+        0..=250 => vec![value as u8],
+        251..=0xFFFF => [&[0xFC][..], &value.to_le_bytes()[..2]].concat(),
+        0x1_0000..=0xFF_FFFF => [&[0xFD][..], &value.to_le_bytes()[..3]].concat(),
+        _ => [&[0xFE][..], &value.to_le_bytes()[..]].concat(),
+    }
+}
+
+fn mysql_auth(
+    plugin: &str,
+    password: &str,
+    scramble: &[u8],
+    tls: bool,
+) -> Result<Vec<u8>, DbError> {
+    if password.is_empty() {
+        return Ok(Vec::new());
+    }
+    match plugin {
+        // SHA1(password) XOR SHA1(scramble + SHA1(SHA1(password)))
+        "mysql_native_password" => {
+            let hash = Sha1::digest(password);
+            let mix = Sha1::new()
+                .chain_update(scramble)
+                .chain_update(Sha1::digest(hash))
+                .finalize();
+            Ok(xor(&hash, &mix))
+        }
+        // SHA256(password) XOR SHA256(SHA256(SHA256(password)) + scramble)
+        "caching_sha2_password" => {
+            let hash = Sha256::digest(password);
+            let mix = Sha256::new()
+                .chain_update(Sha256::digest(hash))
+                .chain_update(scramble)
+                .finalize();
+            Ok(xor(&hash, &mix))
+        }
+        "mysql_clear_password" if tls => {
+            let mut data = password.as_bytes().to_vec();
+            data.push(0);
+            Ok(data)
+        }
+        "mysql_clear_password" => Err(DbError::new(
+            DbErrorKind::TlsRequired,
+            "mysql_clear_password 需要 TLS 连接",
+        )),
+        plugin => Err(DbError::new(
+            DbErrorKind::Unsupported,
+            format!("不支持的认证方式: {}", plugin),
+        )),
+    }
+}
+
+/// 错误包：0xFF、错误码、`#` 加 5 位 SQLSTATE、错误信息
+fn mysql_error(packet: &[u8]) -> DbError {
+    let mut cursor = Cursor::new(packet.get(1..).unwrap_or_default());
+    let code = cursor.u16_le().unwrap_or(0);
+    let mut message = cursor.rest();
+    if message.first() == Some(&b'#') && message.len() >= 6 {
+        message = &message[6..];
+    }
+    let kind = match code {
+        1044 | 1045 | 1130 | 1698 => DbErrorKind::AuthFailed,
+        1049 => DbErrorKind::UnknownDatabase,
+        3159 => DbErrorKind::TlsRequired,
+        1251 => DbErrorKind::Unsupported,
+        _ => DbErrorKind::Server,
+    };
+    DbError::new(kind, lossy(message)).with_code(code)
+}
+
+async fn check_postgres(
+    mut stream: TcpStream,
+    request: &DbCheckRequest,
+    host: &str,
+) -> Result<Handshake, DbError> {
+    let use_tls = if request.tls == TlsMode::Disable {
+        false
+    } else {
+        let mut ssl_request = 8u32.to_be_bytes().to_vec();
+        ssl_request.extend_from_slice(&PG_SSL_REQUEST.to_be_bytes());
+        stream.write_all(&ssl_request).await.map_err(DbError::io)?;
+        match stream.read_u8().await.map_err(DbError::io)? {
+            b'S' => true,
+            b'N' if request.tls == TlsMode::Require => {
+                return Err(DbError::new(DbErrorKind::Tls, "服务端未开启 SSL"))
+            }
+            b'N' => false,
+            _ => return Err(DbError::protocol("不是 PostgreSQL 服务")),
+        }
+    };
+    let mut stream: Box<dyn Io> = if use_tls {
+        start_tls(stream, host, request).await?
+    } else {
+        Box::new(stream)
+    };
+
+    let user = non_empty(&request.username).unwrap_or("postgres");
+    let database = non_empty(&request.database).unwrap_or(user);
+    let password = request.password.as_deref().filter(|p| !p.is_empty());
+    let mut body = PG_PROTOCOL_3.to_be_bytes().to_vec();
+    for (name, value) in [
+        ("user", user),
+        ("database", database),
+        ("application_name", CLIENT_NAME),
+        ("client_encoding", "UTF8"),
+    ] {
+        body.extend_from_slice(name.as_bytes());
+        body.push(0);
+        body.extend_from_slice(value.as_bytes());
+        body.push(0);
+    }
+    body.push(0);
+    let mut startup = ((body.len() + 4) as u32).to_be_bytes().to_vec();
+    startup.extend_from_slice(&body);
+    stream.write_all(&startup).await.map_err(DbError::io)?;
+
+    let mut handshake = Handshake {
+        tls: use_tls,
+        ..Default::default()
+    };
+    let require_password =
+        || password.ok_or_else(|| DbError::new(DbErrorKind::AuthFailed, "服务端要求密码"));
+    let mut scram: Option<Scram> = None;
+    loop {
+        let (tag, body) = read_pg_message(&mut stream).await?;
+        let mut cursor = Cursor::new(&body);
+        match tag {
+            b'R' => match cursor.i32_be() {
+                Some(0) => {
+                    handshake
+                        .auth_method
+                        .get_or_insert_with(|| "trust".to_string());
+                }
+                Some(3) => {
+                    let mut data = require_password()?.as_bytes().to_vec();
+                    data.push(0);
+                    write_pg_message(&mut stream, b'p', &data).await?;
+                    handshake.auth_method = Some("password".to_string());
+                }
+                // "md5" + md5(md5(password + user) + salt)
+                Some(5) => {
+                    let salt = cursor
+                        .take(4)
+                        .ok_or_else(|| DbError::protocol("无效的认证请求"))?;
+                    let inner = to_hex(&Md5::digest(format!("{}{}", require_password()?, user)));
+                    let outer = Md5::new().chain_update(inner).chain_update(salt).finalize();
+                    let mut data = format!("md5{}", to_hex(&outer)).into_bytes();
+                    data.push(0);
+                    write_pg_message(&mut stream, b'p', &data).await?;
+                    handshake.auth_method = Some("md5".to_string());
+                }
+                Some(10) => {
+                    let mechanisms: Vec<String> = cursor
+                        .rest()
+                        .split(|b| *b == 0)
+                        .filter(|m| !m.is_empty())
+                        .map(lossy)
+                        .collect();
+                    if !mechanisms.iter().any(|m| m == "SCRAM-SHA-256") {
+                        return Err(DbError::new(
+                            DbErrorKind::Unsupported,
+                            format!("不支持的认证方式: {}", mechanisms.join(", ")),
+                        ));
+                    }
+                    let client = Scram::new("", require_password()?);
+                    let first = client.client_first();
+                    let mut data = b"SCRAM-SHA-256\0".to_vec();
+                    data.extend_from_slice(&(first.len() as i32).to_be_bytes());
+                    data.extend_from_slice(first.as_bytes());
+                    write_pg_message(&mut stream, b'p', &data).await?;
+                    handshake.auth_method = Some("SCRAM-SHA-256".to_string());
+                    scram = Some(client);
+                }
+                Some(11) => {
+                    let client = scram
+                        .as_mut()
+                        .ok_or_else(|| DbError::protocol("无效的认证请求"))?;
+                    let data = client.client_final(&lossy(cursor.rest()))?;
+                    write_pg_message(&mut stream, b'p', data.as_bytes()).await?;
+                }
+                Some(12) => {
+                    let client = scram
+                        .as_ref()
+                        .ok_or_else(|| DbError::protocol("无效的认证请求"))?;
+                    client.verify_final(&lossy(cursor.rest()))?;
+                }
+                code => {
+                    return Err(DbError::new(
+                        DbErrorKind::Unsupported,
+                        format!("不支持的认证方式: {}", code.unwrap_or(-1)),
+                    ))
+                }
+            },
+            b'S' if cursor.cstr() == Some(b"server_version") => {
+                handshake.server_version = cursor.cstr().map(lossy);
+            }
+            b'E' => return Err(pg_error(&body)),
+            b'Z' => break,
+            // BackendKeyData、NoticeResponse 等
+            _ => {}
+        }
+    }
+
+    // Terminate
+    let _ = write_pg_message(&mut stream, b'X', &[]).await;
+    Ok(handshake)
+}
+
+async fn read_pg_message(stream: &mut Box<dyn Io>) -> Result<(u8, Vec<u8>), DbError> {
+    let tag = stream.read_u8().await.map_err(DbError::io)?;
+    let len = stream.read_i32().await.map_err(DbError::io)?;
+    if !(4..=MAX_MESSAGE_SIZE as i32).contains(&len) {
+        return Err(DbError::protocol("不是 PostgreSQL 服务或消息长度无效"));
+    }
+    let mut body = vec![0u8; len as usize - 4];
+    stream.read_exact(&mut body).await.map_err(DbError::io)?;
+    Ok((tag, body))
+}
+
+async fn write_pg_message(stream: &mut Box<dyn Io>, tag: u8, body: &[u8]) -> Result<(), DbError> {
+    let mut message = vec![tag];
+    message.extend_from_slice(&((body.len() + 4) as i32).to_be_bytes());
+    message.extend_from_slice(body);
+    stream.write_all(&message).await.map_err(DbError::io)
+}
+
+/// ErrorResponse 由字段类型与以 0 结尾的值组成，C 为 SQLSTATE，M 为错误信息
+fn pg_error(body: &[u8]) -> DbError {
+    let mut cursor = Cursor::new(body);
+    let (mut code, mut message) = (String::new(), String::new());
+    while let Some(field) = cursor.u8().filter(|f| *f != 0) {
+        let value = lossy(cursor.cstr().unwrap_or_default());
+        match field {
+            b'C' => code = value,
+            b'M' => message = value,
+            _ => {}
+        }
+    }
+    let kind = match code.as_str() {
+        "28P01" => DbErrorKind::AuthFailed,
+        // pg_hba.conf 只允许 hostssl 时提示 "no encryption" 或 "SSL off"
+        "28000" if message.contains("no encryption") || message.contains("SSL off") => {
+            DbErrorKind::TlsRequired
+        }
+        "28000" => DbErrorKind::AuthFailed,
+        "3D000" => DbErrorKind::UnknownDatabase,
+        "08P01" => DbErrorKind::Protocol,
+        _ => DbErrorKind::Server,
+    };
+    DbError::new(kind, message).with_code(code)
+}
+
+async fn check_redis(
+    stream: TcpStream,
+    request: &DbCheckRequest,
+    host: &str,
+) -> Result<Handshake, DbError> {
+    let use_tls = request.tls == TlsMode::Require;
+    let stream: Box<dyn Io> = if use_tls {
+        start_tls(stream, host, request).await?
+    } else {
+        Box::new(stream)
+    };
+    let mut stream = BufReader::new(stream);
+    let mut handshake = Handshake {
+        tls: use_tls,
+        ..Default::default()
+    };
+
+    if let Some(password) = request.password.as_deref().filter(|p| !p.is_empty()) {
+        let mut args = vec!["AUTH"];
+        args.extend(non_empty(&request.username));
+        args.push(password);
+        redis_command(&mut stream, &args)
+            .await?
+            .map_err(|e| redis_error(e, DbErrorKind::AuthFailed))?;
+        handshake.auth_method = Some("AUTH".to_string());
+    }
+    if let Some(database) = non_empty(&request.database) {
+        if database.parse::<u32>().is_err() {
+            return Err(DbError::new(
+                DbErrorKind::UnknownDatabase,
+                format!("无效的库编号: {}", database),
+            ));
+        }
+        redis_command(&mut stream, &["SELECT", database])
+            .await?
+            .map_err(|e| redis_error(e, DbErrorKind::UnknownDatabase))?;
+    }
+    let info = redis_command(&mut stream, &["INFO", "server"])
+        .await?
+        .map_err(|e| redis_error(e, DbErrorKind::Server))?;
+    handshake.server_version = info
+        .lines()
+        .find_map(|line| line.strip_prefix("redis_version:"))
+        .map(|v| v.trim().to_string());
+
+    let _ = redis_command(&mut stream, &["QUIT"]).await;
+    Ok(handshake)
+}
+
+/// 发送命令并读取回复，服务端返回的错误放在内层的 Err 中
+async fn redis_command(
+    stream: &mut BufReader<Box<dyn Io>>,
+    args: &[&str],
+) -> Result<Result<String, String>, DbError> {
+    let mut command = format!("*{}\r\n", args.len());
+    for arg in args {
+        command.push_str(&format!("${}\r\n{}\r\n", arg.len(), arg));
+    }
+    stream
+        .write_all(command.as_bytes())
+        .await
+        .map_err(DbError::io)?;
+
+    let mut line = String::new();
+    if stream.read_line(&mut line).await.map_err(DbError::io)? == 0 {
+        return Err(DbError::io(io::ErrorKind::UnexpectedEof.into()));
+    }
+    let line = line.trim_end();
+    let invalid = || DbError::protocol("不是 Redis 服务或响应无效");
+    match line.chars().next() {
+        Some('+') | Some(':') => Ok(Ok(line[1..].to_string())),
+        Some('-') => Ok(Err(line[1..].to_string())),
+        Some('$') => {
+            let len: i64 = line[1..].parse().map_err(|_| invalid())?;
+            if len < 0 {
+                return Ok(Ok(String::new()));
+            }
+            if len as usize > MAX_MESSAGE_SIZE {
+                return Err(invalid());
+            }
+            let mut data = vec![0u8; len as usize + 2];
+            stream.read_exact(&mut data).await.map_err(DbError::io)?;
+            data.truncate(len as usize);
+            Ok(Ok(lossy(&data)))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// 错误回复的第一个单词为错误码，如 NOAUTH、WRONGPASS
+fn redis_error(message: String, kind: DbErrorKind) -> DbError {
+    let code = message.split_whitespace().next().unwrap_or("").to_string();
+    let kind = match code.as_str() {
+        "NOAUTH" | "WRONGPASS" => DbErrorKind::AuthFailed,
+        _ => kind,
+    };
+    DbError::new(kind, message).with_code(code)
+}
+
+/// 检测用到的 BSON 类型，其他类型只跳过不解析
+#[derive(Clone, Debug, PartialEq)]
+enum Bson {
+    Double(f64),
+    String(String),
+    Document(Document),
+    Array(Vec<Bson>),
+    Binary(Vec<u8>),
+    Bool(bool),
+    Null,
+    Int32(i32),
+    Int64(i64),
+    Other,
+}
+
+type Document = Vec<(String, Bson)>;
+
+fn bson_doc(fields: Vec<(&str, Bson)>) -> Document {
+    fields
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+}
+
+fn bson_field<'a>(doc: &'a Document, name: &str) -> Option<&'a Bson> {
+    doc.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+}
+
+fn encode_bson(doc: &Document) -> Vec<u8> {
+    let mut body = Vec::new();
+    for (name, value) in doc {
+        let (tag, data) = match value {
+            Bson::Double(v) => (0x01, v.to_le_bytes().to_vec()),
+            Bson::String(s) => {
+                let mut data = ((s.len() + 1) as i32).to_le_bytes().to_vec();
+                data.extend_from_slice(s.as_bytes());
+                data.push(0);
+                (0x02, data)
+            }
+            Bson::Document(doc) => (0x03, encode_bson(doc)),
+            Bson::Array(items) => {
+                let doc = items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (i.to_string(), v.clone()))
+                    .collect();
+                (0x04, encode_bson(&doc))
+            }
+            Bson::Binary(bytes) => {
+                let mut data = (bytes.len() as i32).to_le_bytes().to_vec();
+                data.push(0);
+                data.extend_from_slice(bytes);
+                (0x05, data)
+            }
+            Bson::Bool(b) => (0x08, vec![u8::from(*b)]),
+            Bson::Null | Bson::Other => (0x0A, Vec::new()),
+            Bson::Int32(v) => (0x10, v.to_le_bytes().to_vec()),
+            Bson::Int64(v) => (0x12, v.to_le_bytes().to_vec()),
+        };
+        body.push(tag);
+        body.extend_from_slice(name.as_bytes());
+        body.push(0);
+        body.extend_from_slice(&data);
+    }
+    let mut data = ((body.len() + 5) as i32).to_le_bytes().to_vec();
+    data.extend_from_slice(&body);
+    data.push(0);
+    data
+}
+
+fn decode_bson(data: &[u8]) -> Option<Document> {
+    let len = usize::try_from(Cursor::new(data).i32_le()?).ok()?;
+    let mut cursor = Cursor::new(data.get(4..len.checked_sub(1)?)?);
+    let mut doc = Vec::new();
+    while !cursor.is_empty() {
+        let tag = cursor.u8()?;
+        let name = lossy(cursor.cstr()?);
+        let value = match tag {
+            0x01 => Bson::Double(f64::from_le_bytes(cursor.array()?)),
+            0x02 => {
+                let len = usize::try_from(cursor.i32_le()?).ok()?;
+                Bson::String(lossy(cursor.take(len)?.strip_suffix(&[0])?))
+            }
+            0x03 | 0x04 => {
+                let len = usize::try_from(Cursor::new(cursor.rest_peek()).i32_le()?).ok()?;
+                let inner = decode_bson(cursor.take(len)?)?;
+                if tag == 0x03 {
+                    Bson::Document(inner)
+                } else {
+                    Bson::Array(inner.into_iter().map(|(_, v)| v).collect())
+                }
+            }
+            0x05 => {
+                let len = usize::try_from(cursor.i32_le()?).ok()?;
+                cursor.u8()?;
+                Bson::Binary(cursor.take(len)?.to_vec())
+            }
+            0x08 => Bson::Bool(cursor.u8()? != 0),
+            0x0A => Bson::Null,
+            0x10 => Bson::Int32(cursor.i32_le()?),
+            0x12 => Bson::Int64(i64::from_le_bytes(cursor.array()?)),
+            // undefined、MinKey、MaxKey
+            0x06 | 0x7F | 0xFF => Bson::Other,
+            // ObjectId、日期、时间戳、Decimal128
+            0x07 => cursor.take(12).map(|_| Bson::Other)?,
+            0x09 | 0x11 => cursor.take(8).map(|_| Bson::Other)?,
+            0x13 => cursor.take(16).map(|_| Bson::Other)?,
+            // 正则表达式
+            0x0B => {
+                cursor.cstr()?;
+                cursor.cstr()?;
+                Bson::Other
+            }
+            // JavaScript 代码、Symbol
+            0x0D | 0x0E => {
+                let len = usize::try_from(cursor.i32_le()?).ok()?;
+                cursor.take(len).map(|_| Bson::Other)?
+            }
+            // DBPointer
+            0x0C => {
+                let len = usize::try_from(cursor.i32_le()?).ok()?;
+                cursor.take(len + 12).map(|_| Bson::Other)?
+            }
+            // 带作用域的 JavaScript 代码，长度包含自身
+            0x0F => {
+                let len = usize::try_from(cursor.i32_le()?).ok()?;
+                cursor.take(len.checked_sub(4)?).map(|_| Bson::Other)?
+            }
+            _ => return None,
+        };
+        doc.push((name, value));
+    }
+    Some(doc)
+}
+
+/// OP_MSG：16 字节消息头、标志位与单个 kind 0 的文档
+fn op_msg(request_id: i32, response_to: i32, doc: &Document) -> Vec<u8> {
+    let body = encode_bson(doc);
+    let mut message = ((21 + body.len()) as i32).to_le_bytes().to_vec();
+    message.extend_from_slice(&request_id.to_le_bytes());
+    message.extend_from_slice(&response_to.to_le_bytes());
+    message.extend_from_slice(&OP_MSG.to_le_bytes());
+    message.extend_from_slice(&0u32.to_le_bytes());
+    message.push(0);
+    message.extend_from_slice(&body);
+    message
+}
+
+/// 读取一条 OP_MSG，返回请求 ID 与文档
+async fn read_op_msg<S: AsyncRead + Unpin + ?Sized>(
+    stream: &mut S,
+) -> Result<(i32, Document), DbError> {
+    let invalid = || DbError::protocol("不是 MongoDB 服务或响应无效");
+    let mut header = [0u8; 16];
+    stream.read_exact(&mut header).await.map_err(DbError::io)?;
+    let mut cursor = Cursor::new(&header);
+    let len = cursor.i32_le().unwrap_or_default() as usize;
+    let request_id = cursor.i32_le().unwrap_or_default();
+    cursor.take(4);
+    if cursor.i32_le() != Some(OP_MSG) || !(21..=MAX_MESSAGE_SIZE).contains(&len) {
+        return Err(invalid());
+    }
+    let mut body = vec![0u8; len - 16];
+    stream.read_exact(&mut body).await.map_err(DbError::io)?;
+    if body[4] != 0 {
+        return Err(invalid());
+    }
+    let doc = decode_bson(&body[5..]).ok_or_else(invalid)?;
+    Ok((request_id, doc))
+}
+
+async fn mongo_command(
+    stream: &mut Box<dyn Io>,
+    request_id: &mut i32,
+    command: Vec<(&str, Bson)>,
+) -> Result<Document, DbError> {
+    *request_id += 1;
+    let message = op_msg(*request_id, 0, &bson_doc(command));
+    stream.write_all(&message).await.map_err(DbError::io)?;
+    Ok(read_op_msg(stream).await?.1)
+}
+
+/// 命令结果的 ok 不为 1 时返回错误，code 18 为认证失败
+fn mongo_ok(reply: &Document) -> Result<(), DbError> {
+    let ok = match bson_field(reply, "ok") {
+        Some(Bson::Double(v)) => *v == 1.0,
+        Some(Bson::Int32(v)) => *v == 1,
+        Some(Bson::Int64(v)) => *v == 1,
+        Some(Bson::Bool(v)) => *v,
+        _ => false,
+    };
+    if ok {
+        return Ok(());
+    }
+    let code = match bson_field(reply, "code") {
+        Some(Bson::Int32(v)) => i64::from(*v),
+        Some(Bson::Int64(v)) => *v,
+        Some(Bson::Double(v)) => *v as i64,
+        _ => 0,
+    };
+    let message = match bson_field(reply, "errmsg") {
+        Some(Bson::String(message)) => message.clone(),
+        _ => "命令执行失败".to_string(),
+    };
+    let kind = match code {
+        11 | 13 | 18 => DbErrorKind::AuthFailed,
+        59 => DbErrorKind::Unsupported,
+        _ => DbErrorKind::Server,
+    };
+    Err(DbError::new(kind, message).with_code(code))
+}
+
+fn mongo_payload(reply: &Document) -> Result<String, DbError> {
+    match bson_field(reply, "payload") {
+        Some(Bson::Binary(payload)) => Ok(lossy(payload)),
+        _ => Err(DbError::protocol("无效的认证响应")),
+    }
+}
+
+async fn check_mongodb(
+    stream: TcpStream,
+    request: &DbCheckRequest,
+    host: &str,
+) -> Result<Handshake, DbError> {
+    let use_tls = request.tls == TlsMode::Require;
+    let mut stream: Box<dyn Io> = if use_tls {
+        start_tls(stream, host, request).await?
+    } else {
+        Box::new(stream)
+    };
+    let mut handshake = Handshake {
+        tls: use_tls,
+        ..Default::default()
+    };
+    let mut request_id = 0;
+    let auth_db = non_empty(&request.database).unwrap_or("admin");
+    let username = non_empty(&request.username);
+
+    // 4.4 之前的版本没有 hello 命令，改用 isMaster
+    let mut reply = Document::new();
+    for command in ["hello", "isMaster"] {
+        let mut hello = vec![(command, Bson::Int32(1))];
+        if let Some(username) = username {
+            let user = format!("{}.{}", auth_db, username);
+            hello.push(("saslSupportedMechs", Bson::String(user)));
+        }
+        hello.push(("$db", Bson::String("admin".to_string())));
+        reply = mongo_command(&mut stream, &mut request_id, hello).await?;
+        match mongo_ok(&reply) {
+            Err(e) if e.code.as_deref() == Some("59") => continue,
+            result => {
+                result?;
+                break;
+            }
+        }
+    }
+    mongo_ok(&reply)?;
+    let mechanisms: Vec<&str> = match bson_field(&reply, "saslSupportedMechs") {
+        Some(Bson::Array(items)) => items
+            .iter()
+            .filter_map(|m| match m {
+                Bson::String(m) => Some(m.as_str()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let build = vec![
+        ("buildInfo", Bson::Int32(1)),
+        ("$db", Bson::String("admin".to_string())),
+    ];
+    let build = mongo_command(&mut stream, &mut request_id, build).await?;
+    mongo_ok(&build)?;
+    if let Some(Bson::String(version)) = bson_field(&build, "version") {
+        handshake.server_version = Some(version.clone());
+    }
+
+    let Some(username) = username else {
+        return Ok(handshake);
+    };
+    // 用户不存在时服务端不返回 saslSupportedMechs，由认证结果报错
+    if !mechanisms.is_empty() && !mechanisms.contains(&"SCRAM-SHA-256") {
+        return Err(DbError::new(
+            DbErrorKind::Unsupported,
+            format!("不支持的认证方式: {}", mechanisms.join(", ")),
+        ));
+    }
+    let mut scram = Scram::new(username, request.password.as_deref().unwrap_or(""));
+    let start = vec![
+        ("saslStart", Bson::Int32(1)),
+        ("mechanism", Bson::String("SCRAM-SHA-256".to_string())),
+        ("payload", Bson::Binary(scram.client_first().into_bytes())),
+        (
+            "options",
+            Bson::Document(bson_doc(vec![("skipEmptyExchange", Bson::Bool(true))])),
+        ),
+        ("$db", Bson::String(auth_db.to_string())),
+    ];
+    let mut reply = mongo_command(&mut stream, &mut request_id, start).await?;
+    mongo_ok(&reply)?;
+    let conversation = bson_field(&reply, "conversationId")
+        .cloned()
+        .ok_or_else(|| DbError::protocol("无效的认证响应"))?;
+    let mut payload = scram.client_final(&mongo_payload(&reply)?)?.into_bytes();
+    let mut verified = false;
+    while !matches!(bson_field(&reply, "done"), Some(Bson::Bool(true))) {
+        let next = vec![
+            ("saslContinue", Bson::Int32(1)),
+            ("conversationId", conversation.clone()),
+            ("payload", Bson::Binary(std::mem::take(&mut payload))),
+            ("$db", Bson::String(auth_db.to_string())),
+        ];
+        reply = mongo_command(&mut stream, &mut request_id, next).await?;
+        mongo_ok(&reply)?;
+        if !verified {
+            scram.verify_final(&mongo_payload(&reply)?)?;
+            verified = true;
+        }
+    }
+    if !verified {
+        return Err(DbError::protocol("服务端未完成认证"));
+    }
+    handshake.auth_method = Some("SCRAM-SHA-256".to_string());
+    Ok(handshake)
+}
+
+/// 以只读方式打开文件并读取表结构，文件损坏、加密或不是数据库时报错
+async fn check_sqlite(request: &DbCheckRequest) -> Result<Handshake, DbError> {
+    let path = non_empty(&request.database)
+        .ok_or_else(|| DbError::new(DbErrorKind::UnknownDatabase, "请输入数据库文件路径"))?;
+    if !Path::new(path).is_file() {
+        return Err(DbError::new(
+            DbErrorKind::UnknownDatabase,
+            format!("文件不存在: {}", path),
+        ));
+    }
+    let mut conn = SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .create_if_missing(false)
+        .connect()
+        .await
+        .map_err(sqlite_error)?;
+    let version: String = sqlx::query_scalar("select sqlite_version()")
+        .fetch_one(&mut conn)
+        .await
+        .map_err(sqlite_error)?;
+    sqlx::query_scalar::<_, i64>("select count(*) from sqlite_master")
+        .fetch_one(&mut conn)
+        .await
+        .map_err(sqlite_error)?;
+    let _ = conn.close().await;
+    Ok(Handshake {
+        server_version: Some(version),
+        ..Default::default()
+    })
+}
+
+/// 扩展错误码的低 8 位为主错误码，14 为无法打开，26 为不是数据库
+fn sqlite_error(e: sqlx::Error) -> DbError {
+    let sqlx::Error::Database(error) = &e else {
+        return DbError::new(DbErrorKind::Server, e.to_string());
+    };
+    let code = error.code().map(|c| c.into_owned());
+    let primary = code
+        .as_deref()
+        .and_then(|c| c.parse::<i32>().ok())
+        .map(|c| c & 0xFF);
+    let (kind, message) = match primary {
+        Some(14) => (
+            DbErrorKind::UnknownDatabase,
+            format!("无法打开文件: {}", error.message()),
+        ),
+        Some(26) => (
+            DbErrorKind::Protocol,
+            format!("不是 SQLite 数据库或已加密: {}", error.message()),
+        ),
+        _ => (DbErrorKind::Server, error.message().to_string()),
+    };
+    DbError {
+        kind,
+        code,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    const PASSWORD: &str = "secret";
+    const MYSQL_SCRAMBLE: &[u8; 20] = b"abcdefghij0123456789";
+
+    fn request(kind: DbKind, port: u16) -> DbCheckRequest {
+        DbCheckRequest {
+            kind,
+            host: Some("127.0.0.1".to_string()),
+            port: Some(port),
+            username: Some("user".to_string()),
+            password: Some(PASSWORD.to_string()),
+            database: None,
+            tls: TlsMode::Prefer,
+            verify_tls: None,
+            timeout_ms: None,
+            proxy: None,
+        }
+    }
+
+    async fn run(request: DbCheckRequest) -> DbCheckResult {
+        check_database(request).await.unwrap()
+    }
+
+    /// 接受连接并交给 handler 处理
+    async fn spawn_stand_in<F, Fut>(handler: F) -> u16
+    where
+        F: Fn(TcpStream) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<(), DbError>> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handler(stream));
+            }
+        });
+        port
+    }
+
+    /// 服务端视角：从客户端首条消息中取出随机数，生成 server-first 与期望的 client-final
+    fn scram_server(client_first: &str, username: &str) -> (Scram, String, String) {
+        let nonce = client_first.rsplit("r=").next().unwrap();
+        let mut expected = Scram::with_nonce(username, PASSWORD, nonce);
+        let server_first = format!("r={}server,s=c2FsdA==,i=4096", nonce);
+        let client_final = expected.client_final(&server_first).unwrap();
+        (expected, server_first, client_final)
+    }
+
+    fn server_final(scram: &Scram) -> String {
+        let signature = scram.server_signature.as_ref().unwrap();
+        format!(
+            "v={}",
+            base64::engine::general_purpose::STANDARD.encode(signature)
+        )
+    }
+
+    async fn mysql_stand_in(mut stream: TcpStream) -> Result<(), DbError> {
+        let mut seq = 0;
+        let capabilities = CLIENT_LONG_PASSWORD
+            | CLIENT_PROTOCOL_41
+            | CLIENT_CONNECT_WITH_DB
+            | CLIENT_SECURE_CONNECTION
+            | CLIENT_PLUGIN_AUTH
+            | CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA;
+        let mut greeting = vec![10];
+        greeting.extend_from_slice(b"8.0.36-stand-in\0");
+        greeting.extend_from_slice(&7u32.to_le_bytes());
+        greeting.extend_from_slice(&MYSQL_SCRAMBLE[..8]);
+        greeting.push(0);
+        greeting.extend_from_slice(&(capabilities as u16).to_le_bytes());
+        greeting.push(MYSQL_UTF8MB4);
+        greeting.extend_from_slice(&2u16.to_le_bytes());
+        greeting.extend_from_slice(&((capabilities >> 16) as u16).to_le_bytes());
+        greeting.push(21);
+        greeting.extend_from_slice(&[0; 10]);
+        greeting.extend_from_slice(&MYSQL_SCRAMBLE[8..]);
+        greeting.push(0);
+        greeting.extend_from_slice(b"mysql_native_password\0");
+        write_mysql_packet(&mut stream, &mut seq, &greeting).await?;
+
+        let response = read_mysql_packet(&mut stream, &mut seq).await?;
+        let mut cursor = Cursor::new(&response);
+        let client_capabilities = u32::from_le_bytes(cursor.array().unwrap());
+        cursor.take(28);
+        let user = lossy(cursor.cstr().unwrap());
+        let auth_len = cursor.u8().unwrap() as usize;
+        let auth = cursor.take(auth_len).unwrap().to_vec();
+        let database = (client_capabilities & CLIENT_CONNECT_WITH_DB != 0)
+            .then(|| lossy(cursor.cstr().unwrap()));
+
+        let error = |code: u16, message: &str| {
+            let mut packet = vec![0xFF];
+            packet.extend_from_slice(&code.to_le_bytes());
+            packet.extend_from_slice(b"#28000");
+            packet.extend_from_slice(message.as_bytes());
+            packet
+        };
+        let reply = if user == "sha2" {
+            // 切换到 caching_sha2_password 后要求完整认证
+            let mut switch = vec![0xFE];
+            switch.extend_from_slice(b"caching_sha2_password\0");
+            switch.extend_from_slice(MYSQL_SCRAMBLE);
+            switch.push(0);
+            write_mysql_packet(&mut stream, &mut seq, &switch).await?;
+            read_mysql_packet(&mut stream, &mut seq).await?;
+            vec![0x01, 0x04]
+        } else if auth != mysql_auth("mysql_native_password", PASSWORD, MYSQL_SCRAMBLE, false)? {
+            error(1045, "Access denied for user")
+        } else if database.as_deref() == Some("missing") {
+            error(1049, "Unknown database 'missing'")
+        } else {
+            vec![0, 0, 0, 2, 0, 0, 0]
+        };
+        write_mysql_packet(&mut stream, &mut seq, &reply).await?;
+        let _ = read_mysql_packet(&mut stream, &mut seq).await;
+        Ok(())
+    }
+
+    /// 启动消息与 SSLRequest 没有类型字节
+    async fn read_startup(stream: &mut Box<dyn Io>) -> Result<Vec<u8>, DbError> {
+        let len = stream.read_i32().await.map_err(DbError::io)? as usize;
+        let mut body = vec![0u8; len - 4];
+        stream.read_exact(&mut body).await.map_err(DbError::io)?;
+        Ok(body)
+    }
+
+    async fn postgres_stand_in(stream: TcpStream) -> Result<(), DbError> {
+        let mut stream: Box<dyn Io> = Box::new(stream);
+        let mut startup = read_startup(&mut stream).await?;
+        if startup[..4] == PG_SSL_REQUEST.to_be_bytes() {
+            stream.write_all(b"N").await.map_err(DbError::io)?;
+            startup = read_startup(&mut stream).await?;
+        }
+        let params: Vec<String> = startup[4..].split(|b| *b == 0).map(lossy).collect();
+        let param = |name: &str| {
+            let i = params.iter().position(|p| p == name)?;
+            params.get(i + 1).cloned()
+        };
+        let error =
+            |code: &str, message: &str| format!("SFATAL\0C{}\0M{}\0\0", code, message).into_bytes();
+        if param("database").as_deref() == Some("missing") {
+            let body = error("3D000", "database \"missing\" does not exist");
+            return write_pg_message(&mut stream, b'E', &body).await;
+        }
+
+        let mut sasl = 10i32.to_be_bytes().to_vec();
+        sasl.extend_from_slice(b"SCRAM-SHA-256\0\0");
+        write_pg_message(&mut stream, b'R', &sasl).await?;
+        let (_, initial) = read_pg_message(&mut stream).await?;
+        let (scram, server_first, expected) = scram_server(&lossy(&initial), "");
+        let mut body = 11i32.to_be_bytes().to_vec();
+        body.extend_from_slice(server_first.as_bytes());
+        write_pg_message(&mut stream, b'R', &body).await?;
+        let (_, client_final) = read_pg_message(&mut stream).await?;
+        if lossy(&client_final) != expected {
+            let body = error("28P01", "password authentication failed for user \"user\"");
+            return write_pg_message(&mut stream, b'E', &body).await;
+        }
+        let mut body = 12i32.to_be_bytes().to_vec();
+        body.extend_from_slice(server_final(&scram).as_bytes());
+        write_pg_message(&mut stream, b'R', &body).await?;
+        write_pg_message(&mut stream, b'R', &0i32.to_be_bytes()).await?;
+        write_pg_message(&mut stream, b'S', b"server_version\x0016.2\0").await?;
+        write_pg_message(&mut stream, b'Z', b"I").await?;
+        let _ = read_pg_message(&mut stream).await;
+        Ok(())
+    }
+
+    async fn redis_stand_in(stream: TcpStream) -> Result<(), DbError> {
+        let mut stream = BufReader::new(stream);
+        let mut authed = false;
+        loop {
+            let mut line = String::new();
+            if stream.read_line(&mut line).await.map_err(DbError::io)? == 0 {
+                return Ok(());
+            }
+            let count: usize = line.trim()[1..].parse().unwrap();
+            let mut args = Vec::new();
+            for _ in 0..count * 2 {
+                line.clear();
+                stream.read_line(&mut line).await.map_err(DbError::io)?;
+                args.push(line.trim_end().to_string());
+            }
+            let args: Vec<&str> = args.iter().skip(1).step_by(2).map(String::as_str).collect();
+            let reply = match (args[0], authed) {
+                ("AUTH", _) if args.last() == Some(&PASSWORD) => {
+                    authed = true;
+                    "+OK\r\n".to_string()
+                }
+                ("AUTH", _) => "-WRONGPASS invalid username-password pair\r\n".to_string(),
+                ("QUIT", _) => "+OK\r\n".to_string(),
+                (_, false) => "-NOAUTH Authentication required.\r\n".to_string(),
+                ("SELECT", _) if args[1].parse::<u32>().unwrap() < 16 => "+OK\r\n".to_string(),
+                ("SELECT", _) => "-ERR DB index is out of range\r\n".to_string(),
+                _ => {
+                    let info = "# Server\r\nredis_version:7.2.4\r\n";
+                    format!("${}\r\n{}\r\n", info.len(), info)
+                }
+            };
+            stream
+                .write_all(reply.as_bytes())
+                .await
+                .map_err(DbError::io)?;
+        }
+    }
+
+    async fn mongodb_stand_in(mut stream: TcpStream) -> Result<(), DbError> {
+        let mut scram = None;
+        loop {
+            let (request_id, command) = read_op_msg(&mut stream).await?;
+            let ok = ("ok", Bson::Double(1.0));
+            let reply = match command[0].0.as_str() {
+                "hello" => bson_doc(vec![
+                    ("isWritablePrimary", Bson::Bool(true)),
+                    (
+                        "saslSupportedMechs",
+                        Bson::Array(vec![Bson::String("SCRAM-SHA-256".to_string())]),
+                    ),
+                    ok,
+                ]),
+                "buildInfo" => bson_doc(vec![("version", Bson::String("7.0.5".to_string())), ok]),
+                "saslStart" => {
+                    let client_first = mongo_payload(&command)?;
+                    let (expected, server_first, client_final) =
+                        scram_server(&client_first, "user");
+                    scram = Some((expected, client_final));
+                    bson_doc(vec![
+                        ("conversationId", Bson::Int32(1)),
+                        ("done", Bson::Bool(false)),
+                        ("payload", Bson::Binary(server_first.into_bytes())),
+                        ok,
+                    ])
+                }
+                // 客户端发送空消息时结束认证
+                _ if mongo_payload(&command)?.is_empty() => bson_doc(vec![
+                    ("conversationId", Bson::Int32(1)),
+                    ("done", Bson::Bool(true)),
+                    ("payload", Bson::Binary(Vec::new())),
+                    ok,
+                ]),
+                _ => match &scram {
+                    Some((expected, client_final)) if mongo_payload(&command)? == *client_final => {
+                        bson_doc(vec![
+                            ("conversationId", Bson::Int32(1)),
+                            ("done", Bson::Bool(false)),
+                            ("payload", Bson::Binary(server_final(expected).into_bytes())),
+                            ok,
+                        ])
+                    }
+                    _ => bson_doc(vec![
+                        ("ok", Bson::Double(0.0)),
+                        ("errmsg", Bson::String("Authentication failed.".to_string())),
+                        ("code", Bson::Int32(18)),
+                    ]),
+                },
+            };
+            let message = op_msg(request_id + 100, request_id, &reply);
+            stream.write_all(&message).await.map_err(DbError::io)?;
+        }
+    }
+
+    #[test]
+    fn test_mysql_lenenc_int() {
+        assert_eq!(mysql_lenenc_int(20), [20]);
+        assert_eq!(mysql_lenenc_int(251), [0xFC, 251, 0]);
+        assert_eq!(mysql_lenenc_int(0x1_0000), [0xFD, 0, 0, 1]);
+        assert_eq!(mysql_lenenc_int(1 << 24), [0xFE, 0, 0, 0, 1, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_scram_and_bson() {
+        // RFC 7677 的测试向量
+        let mut scram = Scram::with_nonce("user", "pencil", "rOprNGfwEbeRWgbNEkqO");
+        assert_eq!(scram.client_first(), "n,,n=user,r=rOprNGfwEbeRWgbNEkqO");
+        let client_final = scram
+            .client_final("r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096")
+            .unwrap();
+        assert_eq!(
+            client_final,
+            "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ="
+        );
+        assert!(scram
+            .verify_final("v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=")
+            .is_ok());
+        assert_eq!(
+            scram.verify_final("v=AAAA").unwrap_err().kind,
+            DbErrorKind::Protocol
+        );
+        assert!(scram.client_final("r=other,s=c2FsdA==,i=1").is_err());
+
+        let doc = bson_doc(vec![
+            ("hello", Bson::Int32(1)),
+            ("ok", Bson::Double(1.0)),
+            ("name", Bson::String("中文".to_string())),
+            ("payload", Bson::Binary(vec![0, 1, 2])),
+            ("list", Bson::Array(vec![Bson::Bool(true), Bson::Null])),
+            (
+                "nested",
+                Bson::Document(bson_doc(vec![("n", Bson::Int64(-5))])),
+            ),
+        ]);
+        assert_eq!(decode_bson(&encode_bson(&doc)), Some(doc));
+        // ObjectId 与日期只跳过
+        let mut data = vec![0, 0, 0, 0, 0x07, b'_', b'i', b'd', 0];
+        data.extend_from_slice(&[1; 12]);
+        data.extend_from_slice(&[0x09, b't', 0]);
+        data.extend_from_slice(&[2; 8]);
+        data.push(0);
+        let len = data.len() as i32;
+        data[..4].copy_from_slice(&len.to_le_bytes());
+        assert_eq!(
+            decode_bson(&data),
+            Some(bson_doc(vec![("_id", Bson::Other), ("t", Bson::Other)]))
+        );
+        assert_eq!(decode_bson(&data[..data.len() - 3]), None);
+    }
+
+    #[tokio::test]
+    async fn test_mysql_stand_in() {
+        let port = spawn_stand_in(mysql_stand_in).await;
+
+        let result = run(request(DbKind::Mysql, port)).await;
+        assert!(result.success, "{:?}", result);
+        assert_eq!(result.server_version.as_deref(), Some("8.0.36-stand-in"));
+        assert_eq!(result.auth_method.as_deref(), Some("mysql_native_password"));
+        assert!(!result.tls);
+        assert!(result.connect.unwrap().is_open());
+
+        let mut wrong = request(DbKind::Mysql, port);
+        wrong.password = Some("wrong".to_string());
+        let result = run(wrong).await;
+        assert_eq!(result.error_kind, Some(DbErrorKind::AuthFailed));
+        assert_eq!(result.error_code.as_deref(), Some("1045"));
+
+        let mut missing = request(DbKind::Mysql, port);
+        missing.database = Some("missing".to_string());
+        let result = run(missing).await;
+        assert_eq!(result.error_kind, Some(DbErrorKind::UnknownDatabase));
+        assert_eq!(
+            result.message.as_deref(),
+            Some("Unknown database 'missing'")
+        );
+
+        let mut sha2 = request(DbKind::Mysql, port);
+        sha2.username = Some("sha2".to_string());
+        assert_eq!(run(sha2).await.error_kind, Some(DbErrorKind::TlsRequired));
+
+        let mut tls = request(DbKind::Mysql, port);
+        tls.tls = TlsMode::Require;
+        assert_eq!(run(tls).await.error_kind, Some(DbErrorKind::Tls));
+    }
+
+    #[tokio::test]
+    async fn test_postgres_stand_in() {
+        let port = spawn_stand_in(postgres_stand_in).await;
+
+        let result = run(request(DbKind::Postgres, port)).await;
+        assert!(result.success, "{:?}", result);
+        assert_eq!(result.server_version.as_deref(), Some("16.2"));
+        assert_eq!(result.auth_method.as_deref(), Some("SCRAM-SHA-256"));
+
+        let mut wrong = request(DbKind::Postgres, port);
+        wrong.password = Some("wrong".to_string());
+        let result = run(wrong).await;
+        assert_eq!(result.error_kind, Some(DbErrorKind::AuthFailed));
+        assert_eq!(result.error_code.as_deref(), Some("28P01"));
+
+        let mut missing = request(DbKind::Postgres, port);
+        missing.database = Some("missing".to_string());
+        let result = run(missing).await;
+        assert_eq!(result.error_kind, Some(DbErrorKind::UnknownDatabase));
+        assert_eq!(result.error_code.as_deref(), Some("3D000"));
+
+        let mut no_password = request(DbKind::Postgres, port);
+        no_password.password = None;
+        assert_eq!(
+            run(no_password).await.error_kind,
+            Some(DbErrorKind::AuthFailed)
+        );
+
+        let mut tls = request(DbKind::Postgres, port);
+        tls.tls = TlsMode::Require;
+        assert_eq!(run(tls).await.error_kind, Some(DbErrorKind::Tls));
+    }
+
+    #[tokio::test]
+    async fn test_redis_and_mongodb_stand_in() {
+        let port = spawn_stand_in(redis_stand_in).await;
+        let mut ok = request(DbKind::Redis, port);
+        ok.database = Some("2".to_string());
+        let result = run(ok).await;
+        assert!(result.success, "{:?}", result);
+        assert_eq!(result.server_version.as_deref(), Some("7.2.4"));
+        assert_eq!(result.auth_method.as_deref(), Some("AUTH"));
+
+        let mut no_password = request(DbKind::Redis, port);
+        no_password.password = None;
+        let result = run(no_password).await;
+        assert_eq!(result.error_kind, Some(DbErrorKind::AuthFailed));
+        assert_eq!(result.error_code.as_deref(), Some("NOAUTH"));
+
+        let mut wrong = request(DbKind::Redis, port);
+        wrong.password = Some("wrong".to_string());
+        assert_eq!(run(wrong).await.error_code.as_deref(), Some("WRONGPASS"));
+
+        let mut missing = request(DbKind::Redis, port);
+        missing.database = Some("99".to_string());
+        assert_eq!(
+            run(missing).await.error_kind,
+            Some(DbErrorKind::UnknownDatabase)
+        );
+
+        let port = spawn_stand_in(mongodb_stand_in).await;
+        let result = run(request(DbKind::Mongodb, port)).await;
+        assert!(result.success, "{:?}", result);
+        assert_eq!(result.server_version.as_deref(), Some("7.0.5"));
+        assert_eq!(result.auth_method.as_deref(), Some("SCRAM-SHA-256"));
+
+        let mut wrong = request(DbKind::Mongodb, port);
+        wrong.password = Some("wrong".to_string());
+        let result = run(wrong).await;
+        assert_eq!(result.error_kind, Some(DbErrorKind::AuthFailed));
+        assert_eq!(result.error_code.as_deref(), Some("18"));
+
+        // 不认证时只读取版本
+        let mut anonymous = request(DbKind::Mongodb, port);
+        anonymous.username = None;
+        let result = run(anonymous).await;
+        assert!(result.success);
+        assert!(result.auth_method.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_file() {
+        let dir = std::env::temp_dir().join(format!("lin-toolbox-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db = dir.join("test.db");
+        let mut conn = SqliteConnectOptions::new()
+            .filename(&db)
+            .create_if_missing(true)
+            .connect()
+            .await
+            .unwrap();
+        sqlx::query("create table t (id integer)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        conn.close().await.unwrap();
+
+        let sqlite = |path: &Path| DbCheckRequest {
+            database: Some(path.to_string_lossy().into_owned()),
+            ..request(DbKind::Sqlite, 0)
+        };
+        let result = run(sqlite(&db)).await;
+        assert!(result.success, "{:?}", result);
+        assert!(result.server_version.unwrap().starts_with('3'));
+        assert!(result.connect.is_none());
+
+        let text = dir.join("text.db");
+        std::fs::write(&text, "x".repeat(4096)).unwrap();
+        let result = run(sqlite(&text)).await;
+        assert_eq!(result.error_kind, Some(DbErrorKind::Protocol));
+        assert_eq!(result.error_code.as_deref(), Some("26"));
+
+        let result = run(sqlite(&dir.join("missing.db"))).await;
+        assert_eq!(result.error_kind, Some(DbErrorKind::UnknownDatabase));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
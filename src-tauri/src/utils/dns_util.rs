@@ -0,0 +1,844 @@
+use crate::utils::{http_client, network_util};
+use hyper::header::{self, HeaderMap, HeaderValue};
+use hyper::Method;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{lookup_host, UdpSocket};
+use url::Url;
+
+/// 默认查询超时时间（毫秒）
+const DEFAULT_DNS_TIMEOUT_MS: u64 = 5_000;
+/// DNS 默认端口
+const DNS_PORT: u16 = 53;
+/// UDP 响应最大长度（启用 EDNS 前的 512 字节之外预留空间）
+const MAX_UDP_RESPONSE: usize = 4096;
+/// 域名压缩指针最多跳转次数，防止恶意报文造成死循环
+const MAX_POINTER_JUMPS: usize = 32;
+
+/// 支持查询的记录类型
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DnsRecordType {
+    A,
+    Aaaa,
+    Cname,
+    Mx,
+    Txt,
+    Ns,
+    Srv,
+    Ptr,
+    Soa,
+}
+
+impl DnsRecordType {
+    pub fn code(&self) -> u16 {
+        match self {
+            DnsRecordType::A => 1,
+            DnsRecordType::Ns => 2,
+            DnsRecordType::Cname => 5,
+            DnsRecordType::Soa => 6,
+            DnsRecordType::Ptr => 12,
+            DnsRecordType::Mx => 15,
+            DnsRecordType::Txt => 16,
+            DnsRecordType::Aaaa => 28,
+            DnsRecordType::Srv => 33,
+        }
+    }
+}
+
+/// 传输协议
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DnsProtocol {
+    #[default]
+    Udp,
+    Tcp,
+    /// DNS over HTTPS (RFC 8484)
+    Doh,
+}
+
+/// DNS 查询请求
+#[derive(Deserialize)]
+pub struct DnsQueryRequest {
+    /// 查询的域名，PTR 查询时也可以直接填写 IP 地址
+    pub name: String,
+    pub record_type: DnsRecordType,
+    /// DNS 服务器，为空时使用系统配置的服务器；UDP/TCP 填写 `ip[:port]`，DoH 填写完整 URL
+    pub server: Option<String>,
+    pub protocol: Option<DnsProtocol>,
+    /// 超时时间（毫秒），默认 5000
+    pub timeout_ms: Option<u64>,
+    /// 代理地址，为空时使用全局代理，`direct` 表示直接连接；UDP 查询不经过代理
+    pub proxy: Option<String>,
+}
+
+/// 解析出的资源记录
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct DnsRecord {
+    pub name: String,
+    pub record_type: String,
+    pub ttl: u32,
+    pub value: String,
+}
+
+/// 解析出的 DNS 报文
+#[derive(Debug)]
+pub struct DnsMessage {
+    pub id: u16,
+    pub authoritative: bool,
+    pub truncated: bool,
+    pub recursion_available: bool,
+    pub response_code: u8,
+    pub answers: Vec<DnsRecord>,
+    pub authorities: Vec<DnsRecord>,
+    pub additionals: Vec<DnsRecord>,
+}
+
+/// DNS 查询结果
+#[derive(Serialize, Debug)]
+pub struct DnsQueryResult {
+    name: String,
+    record_type: DnsRecordType,
+    /// 实际应答的服务器
+    server: String,
+    protocol: DnsProtocol,
+    /// 响应耗时（毫秒）
+    elapsed_ms: f64,
+    response_code: String,
+    authoritative: bool,
+    /// UDP 响应被截断后是否改用 TCP 重新查询
+    tcp_fallback: bool,
+    answers: Vec<DnsRecord>,
+    authorities: Vec<DnsRecord>,
+}
+
+/// 查询 DNS 记录
+#[tauri::command]
+pub async fn dns_lookup(request: DnsQueryRequest) -> Result<DnsQueryResult, String> {
+    let timeout =
+        Duration::from_millis(request.timeout_ms.unwrap_or(DEFAULT_DNS_TIMEOUT_MS).max(1));
+    let protocol = request.protocol.unwrap_or_default();
+    let proxy = request.proxy.as_deref();
+    let name = match (request.record_type, request.name.trim().parse::<IpAddr>()) {
+        (DnsRecordType::Ptr, Ok(ip)) => reverse_name(ip),
+        _ => request.name.trim().to_string(),
+    };
+    let query = build_query(rand::rng().random(), &name, request.record_type.code())?;
+
+    let servers = match (protocol, request.server.as_deref().map(str::trim)) {
+        (DnsProtocol::Doh, Some(url)) if !url.is_empty() => vec![url.to_string()],
+        (DnsProtocol::Doh, _) => return Err("DoH 查询需要填写服务器 URL".to_string()),
+        (_, Some(server)) if !server.is_empty() => vec![resolve_server(server).await?.to_string()],
+        _ => system_nameservers()?
+            .into_iter()
+            .map(|ip| SocketAddr::new(ip, DNS_PORT).to_string())
+            .collect(),
+    };
+
+    // 依次尝试各个服务器，返回第一个成功的结果
+    let mut last_error = "没有可用的 DNS 服务器".to_string();
+    for server in servers {
+        let start = Instant::now();
+        let outcome = match protocol {
+            DnsProtocol::Doh => query_doh(&server, &query, timeout, proxy)
+                .await
+                .map(|m| (m, false)),
+            _ => {
+                let addr: SocketAddr = server.parse().map_err(|e| format!("{}", e))?;
+                query_socket(addr, protocol, &query, timeout, proxy).await
+            }
+        };
+        match outcome {
+            Ok((message, tcp_fallback)) => {
+                return Ok(DnsQueryResult {
+                    name,
+                    record_type: request.record_type,
+                    server,
+                    protocol,
+                    elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+                    response_code: response_code_name(message.response_code),
+                    authoritative: message.authoritative,
+                    tcp_fallback,
+                    answers: message.answers,
+                    authorities: message.authorities,
+                })
+            }
+            Err(e) => last_error = format!("{}: {}", server, e),
+        }
+    }
+    Err(last_error)
+}
+
+/// 通过 UDP 或 TCP 发送查询，UDP 响应被截断时自动改用 TCP，返回 (报文, 是否改用了 TCP)
+///
+/// TCP 查询按 `proxy` 经过代理，见 [`crate::utils::proxy::resolve`]
+pub async fn query_socket(
+    server: SocketAddr,
+    protocol: DnsProtocol,
+    query: &[u8],
+    timeout: Duration,
+    proxy: Option<&str>,
+) -> Result<(DnsMessage, bool), String> {
+    let id = u16::from_be_bytes([query[0], query[1]]);
+    if protocol == DnsProtocol::Tcp {
+        return query_tcp(server, query, timeout, proxy)
+            .await
+            .map(|m| (m, false));
+    }
+
+    let message = tokio::time::timeout(timeout, async {
+        let bind_addr: SocketAddr = match server {
+            SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+            SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+        };
+        let socket = UdpSocket::bind(bind_addr)
+            .await
+            .map_err(|e| e.to_string())?;
+        socket.connect(server).await.map_err(|e| e.to_string())?;
+        socket.send(query).await.map_err(|e| e.to_string())?;
+
+        let mut buf = vec![0u8; MAX_UDP_RESPONSE];
+        loop {
+            let n = socket.recv(&mut buf).await.map_err(|e| e.to_string())?;
+            // 忽略ID不匹配或无法解析的报文，继续等待
+            if let Ok(message) = parse_message(&buf[..n]) {
+                if message.id == id {
+                    return Ok::<_, String>(message);
+                }
+            }
+        }
+    })
+    .await
+    .map_err(|_| "查询超时".to_string())??;
+
+    if message.truncated {
+        return query_tcp(server, query, timeout, proxy)
+            .await
+            .map(|m| (m, true));
+    }
+    Ok((message, false))
+}
+
+async fn query_tcp(
+    server: SocketAddr,
+    query: &[u8],
+    timeout: Duration,
+    proxy: Option<&str>,
+) -> Result<DnsMessage, String> {
+    tokio::time::timeout(timeout, async {
+        let mut stream =
+            network_util::open_stream(&server.ip().to_string(), server.port(), timeout, proxy)
+                .await?;
+        let mut packet = (query.len() as u16).to_be_bytes().to_vec();
+        packet.extend_from_slice(query);
+        stream.write_all(&packet).await.map_err(|e| e.to_string())?;
+
+        let len = stream.read_u16().await.map_err(|e| e.to_string())? as usize;
+        let mut buf = vec![0u8; len];
+        stream
+            .read_exact(&mut buf)
+            .await
+            .map_err(|e| e.to_string())?;
+        parse_message(&buf)
+    })
+    .await
+    .map_err(|_| "查询超时".to_string())?
+}
+
+async fn query_doh(
+    url: &str,
+    query: &[u8],
+    timeout: Duration,
+    proxy: Option<&str>,
+) -> Result<DnsMessage, String> {
+    // RFC 8484 建议 DoH 查询使用 0 作为报文ID，便于缓存
+    let mut query = query.to_vec();
+    query[0] = 0;
+    query[1] = 0;
+
+    let url = Url::parse(url).map_err(|e| format!("无效的 DoH 地址: {}", e))?;
+    let mut headers = HeaderMap::new();
+    let dns_message = HeaderValue::from_static("application/dns-message");
+    headers.insert(header::CONTENT_TYPE, dns_message.clone());
+    headers.insert(header::ACCEPT, dns_message);
+    let (status, body) = tokio::time::timeout(
+        timeout,
+        http_client::send_raw(&url, &Method::POST, &headers, query.into(), proxy),
+    )
+    .await
+    .map_err(|_| "查询超时".to_string())??;
+    if !status.is_success() {
+        return Err(format!("HTTP {}", status));
+    }
+    parse_message(&body)
+}
+
+/// 解析服务器地址，支持 `ip`、`ip:port`、`[ipv6]:port` 以及 `host[:port]`
+async fn resolve_server(server: &str) -> Result<SocketAddr, String> {
+    if let Ok(addr) = server.parse::<SocketAddr>() {
+        return Ok(addr);
+    }
+    if let Ok(ip) = server.trim_matches(['[', ']']).parse::<IpAddr>() {
+        return Ok(SocketAddr::new(ip, DNS_PORT));
+    }
+    let (host, port) = match server.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse().map_err(|_| format!("无效的端口: {}", port))?,
+        ),
+        None => (server, DNS_PORT),
+    };
+    lookup_host((host, port))
+        .await
+        .map_err(|e| format!("无法解析 DNS 服务器 {}: {}", server, e))?
+        .next()
+        .ok_or_else(|| format!("无法解析 DNS 服务器: {}", server))
+}
+
+/// 读取系统配置的 DNS 服务器
+pub fn system_nameservers() -> Result<Vec<IpAddr>, String> {
+    #[cfg(unix)]
+    {
+        let content = std::fs::read_to_string("/etc/resolv.conf")
+            .map_err(|e| format!("读取 /etc/resolv.conf 失败: {}", e))?;
+        Ok(parse_resolv_conf(&content))
+    }
+    #[cfg(windows)]
+    {
+        let adapters = ipconfig::get_adapters().map_err(|e| format!("读取网卡配置失败: {}", e))?;
+        let mut servers = Vec::new();
+        for adapter in adapters
+            .iter()
+            .filter(|a| a.oper_status() == ipconfig::OperStatus::IfOperStatusUp)
+        {
+            for server in adapter.dns_servers() {
+                if !servers.contains(server) {
+                    servers.push(*server);
+                }
+            }
+        }
+        Ok(servers)
+    }
+}
+
+/// 解析 resolv.conf 中的 nameserver 行
+pub fn parse_resolv_conf(content: &str) -> Vec<IpAddr> {
+    content
+        .lines()
+        .map(|line| line.split(['#', ';']).next().unwrap_or("").trim())
+        .filter_map(|line| line.strip_prefix("nameserver"))
+        .filter_map(|rest| {
+            // 去掉 IPv6 链路本地地址的网卡后缀，如 fe80::1%eth0
+            let addr = rest.trim().split('%').next()?;
+            addr.parse().ok()
+        })
+        .collect()
+}
+
+/// 将 IP 地址转换为 PTR 查询使用的反向域名
+pub fn reverse_name(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(ip) => {
+            let o = ip.octets();
+            format!("{}.{}.{}.{}.in-addr.arpa", o[3], o[2], o[1], o[0])
+        }
+        IpAddr::V6(ip) => {
+            let mut labels: Vec<String> = ip
+                .octets()
+                .iter()
+                .flat_map(|b| [b >> 4, b & 0x0F])
+                .map(|n| format!("{:x}", n))
+                .collect();
+            labels.reverse();
+            format!("{}.ip6.arpa", labels.join("."))
+        }
+    }
+}
+
+/// 构造标准查询报文（RD=1）
+pub fn build_query(id: u16, name: &str, record_type: u16) -> Result<Vec<u8>, String> {
+    let mut packet = Vec::with_capacity(512);
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&0x0100u16.to_be_bytes()); // RD
+    packet.extend_from_slice(&1u16.to_be_bytes()); // QDCOUNT
+    packet.extend_from_slice(&[0; 6]); // ANCOUNT / NSCOUNT / ARCOUNT
+    encode_name(name, &mut packet)?;
+    packet.extend_from_slice(&record_type.to_be_bytes());
+    packet.extend_from_slice(&1u16.to_be_bytes()); // IN
+    Ok(packet)
+}
+
+/// 将域名编码为 DNS 标签序列
+pub fn encode_name(name: &str, packet: &mut Vec<u8>) -> Result<(), String> {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    if name.len() > 253 {
+        return Err("域名长度不能超过 253".to_string());
+    }
+    if !name.is_empty() {
+        for label in name.split('.') {
+            if label.is_empty() || label.len() > 63 {
+                return Err(format!("无效的域名标签: '{}'", label));
+            }
+            packet.push(label.len() as u8);
+            packet.extend_from_slice(label.as_bytes());
+        }
+    }
+    packet.push(0);
+    Ok(())
+}
+
+/// 解析 DNS 报文
+pub fn parse_message(buf: &[u8]) -> Result<DnsMessage, String> {
+    let mut reader = Reader { buf, pos: 0 };
+    let id = reader.u16()?;
+    let flags = reader.u16()?;
+    let qdcount = reader.u16()?;
+    let ancount = reader.u16()?;
+    let nscount = reader.u16()?;
+    let arcount = reader.u16()?;
+
+    for _ in 0..qdcount {
+        reader.name()?;
+        reader.skip(4)?;
+    }
+    let mut read_records = |count: u16| -> Result<Vec<DnsRecord>, String> {
+        (0..count).map(|_| reader.record()).collect()
+    };
+    let answers = read_records(ancount)?;
+    let authorities = read_records(nscount)?;
+    let additionals = read_records(arcount)?;
+
+    Ok(DnsMessage {
+        id,
+        authoritative: flags & 0x0400 != 0,
+        truncated: flags & 0x0200 != 0,
+        recursion_available: flags & 0x0080 != 0,
+        response_code: (flags & 0x000F) as u8,
+        answers,
+        authorities,
+        additionals,
+    })
+}
+
+fn response_code_name(code: u8) -> String {
+    match code {
+        0 => "NOERROR".to_string(),
+        1 => "FORMERR".to_string(),
+        2 => "SERVFAIL".to_string(),
+        3 => "NXDOMAIN".to_string(),
+        4 => "NOTIMP".to_string(),
+        5 => "REFUSED".to_string(),
+        code => format!("RCODE{}", code),
+    }
+}
+
+fn type_name(code: u16) -> String {
+    match code {
+        1 => "A".to_string(),
+        2 => "NS".to_string(),
+        5 => "CNAME".to_string(),
+        6 => "SOA".to_string(),
+        12 => "PTR".to_string(),
+        15 => "MX".to_string(),
+        16 => "TXT".to_string(),
+        28 => "AAAA".to_string(),
+        33 => "SRV".to_string(),
+        41 => "OPT".to_string(),
+        code => format!("TYPE{}", code),
+    }
+}
+
+/// 报文读取器
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8], String> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.buf.len());
+        let end = end.ok_or_else(|| "报文长度不足".to_string())?;
+        let slice = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn skip(&mut self, n: usize) -> Result<(), String> {
+        self.take(n).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// 读取域名，支持压缩指针
+    fn name(&mut self) -> Result<String, String> {
+        let mut labels = Vec::new();
+        let mut pos = self.pos;
+        let mut jumps = 0;
+        let mut end_pos = None;
+        loop {
+            let len = *self.buf.get(pos).ok_or("域名越界")? as usize;
+            match len & 0xC0 {
+                0x00 if len == 0 => {
+                    pos += 1;
+                    break;
+                }
+                0x00 => {
+                    let label = self.buf.get(pos + 1..pos + 1 + len).ok_or("域名越界")?;
+                    labels.push(String::from_utf8_lossy(label).into_owned());
+                    pos += 1 + len;
+                }
+                0xC0 => {
+                    let low = *self.buf.get(pos + 1).ok_or("域名越界")? as usize;
+                    end_pos.get_or_insert(pos + 2);
+                    jumps += 1;
+                    if jumps > MAX_POINTER_JUMPS {
+                        return Err("域名压缩指针过多".to_string());
+                    }
+                    pos = ((len & 0x3F) << 8) | low;
+                }
+                _ => return Err("不支持的域名标签类型".to_string()),
+            }
+        }
+        self.pos = end_pos.unwrap_or(pos);
+        Ok(if labels.is_empty() {
+            ".".to_string()
+        } else {
+            labels.join(".")
+        })
+    }
+
+    fn record(&mut self) -> Result<DnsRecord, String> {
+        let name = self.name()?;
+        let record_type = self.u16()?;
+        let _class = self.u16()?;
+        let ttl = self.u32()?;
+        let rdlength = self.u16()? as usize;
+        let rdata_end = self.pos + rdlength;
+        if rdata_end > self.buf.len() {
+            return Err("报文长度不足".to_string());
+        }
+
+        let value = match record_type {
+            1 if rdlength == 4 => {
+                let b = self.take(4)?;
+                Ipv4Addr::new(b[0], b[1], b[2], b[3]).to_string()
+            }
+            28 if rdlength == 16 => {
+                let b: [u8; 16] = self.take(16)?.try_into().unwrap();
+                Ipv6Addr::from(b).to_string()
+            }
+            2 | 5 | 12 => self.name()?,
+            15 => {
+                let preference = self.u16()?;
+                format!("{} {}", preference, self.name()?)
+            }
+            16 => {
+                let mut parts = Vec::new();
+                while self.pos < rdata_end {
+                    let len = self.u8()? as usize;
+                    parts.push(format!("\"{}\"", String::from_utf8_lossy(self.take(len)?)));
+                }
+                parts.join(" ")
+            }
+            33 => {
+                let priority = self.u16()?;
+                let weight = self.u16()?;
+                let port = self.u16()?;
+                format!("{} {} {} {}", priority, weight, port, self.name()?)
+            }
+            6 => {
+                let mname = self.name()?;
+                let rname = self.name()?;
+                let serial = self.u32()?;
+                let refresh = self.u32()?;
+                let retry = self.u32()?;
+                let expire = self.u32()?;
+                let minimum = self.u32()?;
+                format!(
+                    "{} {} {} {} {} {} {}",
+                    mname, rname, serial, refresh, retry, expire, minimum
+                )
+            }
+            _ => self
+                .take(rdlength)?
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        };
+        self.pos = rdata_end;
+
+        Ok(DnsRecord {
+            name,
+            record_type: type_name(record_type),
+            ttl,
+            value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::net::TcpListener;
+
+    /// 为测试构造应答报文：复制问题部分并追加记录
+    fn build_response(
+        query: &[u8],
+        flags: u16,
+        answers: &[(u16, u32, Vec<u8>)],
+        authorities: &[(u16, u32, Vec<u8>)],
+    ) -> Vec<u8> {
+        let mut packet = query[..2].to_vec();
+        packet.extend_from_slice(&(0x8180 | flags).to_be_bytes());
+        packet.extend_from_slice(&1u16.to_be_bytes());
+        packet.extend_from_slice(&(answers.len() as u16).to_be_bytes());
+        packet.extend_from_slice(&(authorities.len() as u16).to_be_bytes());
+        packet.extend_from_slice(&0u16.to_be_bytes());
+        packet.extend_from_slice(&query[12..]);
+        for (record_type, ttl, rdata) in answers.iter().chain(authorities) {
+            packet.extend_from_slice(&[0xC0, 0x0C]); // 指向问题中的域名
+            packet.extend_from_slice(&record_type.to_be_bytes());
+            packet.extend_from_slice(&1u16.to_be_bytes());
+            packet.extend_from_slice(&ttl.to_be_bytes());
+            packet.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+            packet.extend_from_slice(rdata);
+        }
+        packet
+    }
+
+    fn name_bytes(name: &str) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_name(name, &mut buf).unwrap();
+        buf
+    }
+
+    fn stub_answers() -> Vec<(u16, u32, Vec<u8>)> {
+        let mut mx = 10u16.to_be_bytes().to_vec();
+        mx.extend(name_bytes("mail.example.test"));
+        vec![(1, 300, vec![10, 0, 0, 1]), (15, 60, mx)]
+    }
+
+    /// 本地 UDP 桩服务器，`truncate` 为真时只返回带 TC 标志的空应答
+    async fn spawn_udp_stub(socket: UdpSocket, truncate: bool) {
+        tokio::spawn(async move {
+            let mut buf = [0u8; 512];
+            while let Ok((n, peer)) = socket.recv_from(&mut buf).await {
+                let response = if truncate {
+                    build_response(&buf[..n], 0x0200, &[], &[])
+                } else {
+                    build_response(&buf[..n], 0, &stub_answers(), &[])
+                };
+                socket.send_to(&response, peer).await.unwrap();
+            }
+        });
+    }
+
+    async fn spawn_tcp_stub(listener: TcpListener) {
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let len = stream.read_u16().await.unwrap() as usize;
+                let mut query = vec![0u8; len];
+                stream.read_exact(&mut query).await.unwrap();
+                let response = build_response(&query, 0, &stub_answers(), &[]);
+                stream.write_u16(response.len() as u16).await.unwrap();
+                stream.write_all(&response).await.unwrap();
+            }
+        });
+    }
+
+    fn request(server: String, protocol: DnsProtocol) -> DnsQueryRequest {
+        DnsQueryRequest {
+            name: "www.example.test".to_string(),
+            record_type: DnsRecordType::A,
+            server: Some(server),
+            protocol: Some(protocol),
+            timeout_ms: Some(2_000),
+            proxy: None,
+        }
+    }
+
+    #[test]
+    fn test_build_query() {
+        let query = build_query(0x1234, "example.com.", 28).unwrap();
+        assert_eq!(&query[..2], &[0x12, 0x34]);
+        assert_eq!(&query[12..], b"\x07example\x03com\x00\x00\x1c\x00\x01");
+        assert!(build_query(1, "a..b", 1).is_err());
+        assert!(build_query(1, &"a".repeat(64), 1).is_err());
+    }
+
+    #[test]
+    fn test_parse_soa_and_pointer_loop() {
+        let query = build_query(7, "missing.example.test", 1).unwrap();
+        let mut soa = name_bytes("ns1.example.test");
+        soa.extend(name_bytes("hostmaster.example.test"));
+        for v in [2024010101u32, 7200, 3600, 1209600, 300] {
+            soa.extend(v.to_be_bytes());
+        }
+        let response = build_response(&query, 0x0003, &[], &[(6, 900, soa)]);
+        let message = parse_message(&response).unwrap();
+        assert_eq!(message.response_code, 3);
+        assert_eq!(message.authorities[0].record_type, "SOA");
+        assert_eq!(
+            message.authorities[0].value,
+            "ns1.example.test hostmaster.example.test 2024010101 7200 3600 1209600 300"
+        );
+
+        // 指向自身的压缩指针
+        let mut looped = response[..12].to_vec();
+        looped[5] = 1;
+        looped.extend_from_slice(&[0xC0, 0x0C, 0, 1, 0, 1]);
+        assert!(parse_message(&looped).is_err());
+    }
+
+    #[test]
+    fn test_reverse_name() {
+        assert_eq!(
+            reverse_name("192.0.2.10".parse().unwrap()),
+            "10.2.0.192.in-addr.arpa"
+        );
+        assert!(reverse_name("2001:db8::1".parse().unwrap())
+            .starts_with("1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2"));
+    }
+
+    #[test]
+    fn test_parse_resolv_conf() {
+        let content = "# comment\nnameserver 10.0.0.2\nsearch corp.example\nnameserver fe80::1%eth0 ; local\nnameserver bogus\n";
+        let servers = parse_resolv_conf(content);
+        assert_eq!(
+            servers,
+            vec![
+                "10.0.0.2".parse::<IpAddr>().unwrap(),
+                "fe80::1".parse().unwrap()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lookup_udp_stub() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        spawn_udp_stub(socket, false).await;
+
+        let result = dns_lookup(request(addr.to_string(), DnsProtocol::Udp))
+            .await
+            .unwrap();
+        assert_eq!(result.response_code, "NOERROR");
+        assert!(!result.tcp_fallback);
+        assert_eq!(result.answers.len(), 2);
+        assert_eq!(
+            result.answers[0],
+            DnsRecord {
+                name: "www.example.test".to_string(),
+                record_type: "A".to_string(),
+                ttl: 300,
+                value: "10.0.0.1".to_string(),
+            }
+        );
+        assert_eq!(result.answers[1].value, "10 mail.example.test");
+        assert_eq!(result.answers[1].ttl, 60);
+    }
+
+    #[tokio::test]
+    async fn test_lookup_tcp_stub_and_truncation_fallback() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        spawn_tcp_stub(listener).await;
+
+        let result = dns_lookup(request(addr.to_string(), DnsProtocol::Tcp))
+            .await
+            .unwrap();
+        assert_eq!(result.answers.len(), 2);
+
+        // 同一端口上的 UDP 桩服务器只返回截断应答，应自动改用 TCP
+        let socket = UdpSocket::bind(addr).await.unwrap();
+        spawn_udp_stub(socket, true).await;
+        let result = dns_lookup(request(addr.to_string(), DnsProtocol::Udp))
+            .await
+            .unwrap();
+        assert!(result.tcp_fallback);
+        assert_eq!(result.answers.len(), 2);
+    }
+
+    /// 以明文 HTTP 模拟 DoH 服务器，同时充当 HTTP 代理，记录每个请求的请求行
+    #[tokio::test]
+    async fn test_lookup_doh_stub() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).await.unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).await.unwrap();
+                    if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = v.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut query = vec![0u8; content_length];
+                reader.read_exact(&mut query).await.unwrap();
+                assert_eq!(&query[..2], &[0, 0]);
+                let body = build_response(&query, 0, &stub_answers(), &[]);
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/dns-message\r\ncontent-length: {}\r\n\r\n",
+                    body.len()
+                );
+                let mut stream = reader.into_inner();
+                stream.write_all(head.as_bytes()).await.unwrap();
+                stream.write_all(&body).await.unwrap();
+                tx.send(request_line.trim_end().to_string()).unwrap();
+            }
+        });
+
+        let mut req = request(format!("http://{}/dns-query", addr), DnsProtocol::Doh);
+        req.proxy = Some("direct".to_string());
+        let result = dns_lookup(req).await.unwrap();
+        assert_eq!(result.answers[0].value, "10.0.0.1");
+        assert_eq!(rx.recv().await.unwrap(), "POST /dns-query HTTP/1.1");
+
+        // 经 HTTP 代理时请求行为完整 URL，域名交给代理处理
+        let mut req = request(
+            "http://doh.example.test/dns-query".to_string(),
+            DnsProtocol::Doh,
+        );
+        req.proxy = Some(format!("http://{}", addr));
+        let result = dns_lookup(req).await.unwrap();
+        assert_eq!(result.answers[0].value, "10.0.0.1");
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            "POST http://doh.example.test/dns-query HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lookup_timeout() {
+        // 绑定但从不应答的 UDP 服务器
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut req = request(socket.local_addr().unwrap().to_string(), DnsProtocol::Udp);
+        req.timeout_ms = Some(200);
+        let err = dns_lookup(req).await.unwrap_err();
+        assert!(err.ends_with("查询超时"));
+    }
+}
@@ -0,0 +1,549 @@
+use chrono::{Local, TimeZone};
+use once_cell::sync::Lazy;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::{Uuid, Variant};
+
+/// 单次最多生成的ID数量
+const MAX_BATCH_SIZE: u32 = 10_000;
+
+/// Crockford Base32 字符表（ULID 使用）
+pub(crate) const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Base62 字符表（KSUID 使用）
+pub(crate) const BASE62_ALPHABET: &[u8; 62] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// KSUID 纪元：2014-05-13 16:53:20 UTC（秒）
+const KSUID_EPOCH: i64 = 1_400_000_000;
+/// KSUID 字符串长度
+const KSUID_STRING_LEN: usize = 27;
+
+/// NanoID 默认字符表
+const NANO_ID_DEFAULT_ALPHABET: &str =
+    "_-0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// NanoID 默认长度
+const NANO_ID_DEFAULT_SIZE: usize = 21;
+/// NanoID 最大长度
+const NANO_ID_MAX_SIZE: usize = 1024;
+
+/// ObjectId 进程唯一的 5 字节随机值
+static OBJECT_ID_PROCESS_UNIQUE: Lazy<[u8; 5]> = Lazy::new(|| rand::rng().random());
+/// ObjectId 自增计数器（只使用低 24 位），以随机值开始
+static OBJECT_ID_COUNTER: Lazy<AtomicU32> = Lazy::new(|| AtomicU32::new(rand::rng().random()));
+/// UUID v1/v6 使用的节点ID：随机生成并设置组播位，避免暴露真实 MAC 地址
+static UUID_NODE_ID: Lazy<[u8; 6]> = Lazy::new(|| {
+    let mut node_id: [u8; 6] = rand::rng().random();
+    node_id[0] |= 0x01;
+    node_id
+});
+
+/// 支持的ID格式
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdKind {
+    UuidV1,
+    UuidV4,
+    UuidV6,
+    UuidV7,
+    Ulid,
+    Ksuid,
+    NanoId,
+    ObjectId,
+}
+
+/// NanoID 生成参数
+#[derive(Deserialize, Default)]
+pub struct NanoIdOptions {
+    /// 自定义字符表，为空时使用默认字符表
+    pub alphabet: Option<String>,
+    /// ID长度，为空时默认为 21
+    pub size: Option<usize>,
+}
+
+/// 解析出的字段
+#[derive(Serialize, Clone, Debug)]
+pub struct IdField {
+    name: String,
+    value: String,
+}
+
+impl IdField {
+    fn new(name: &str, value: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// ID解析结果
+#[derive(Serialize, Debug)]
+pub struct DecodedId {
+    kind: IdKind,
+    /// 规范化后的ID字符串
+    canonical: String,
+    /// 内嵌时间戳（UNIX 毫秒），不含时间信息的格式为空
+    timestamp: Option<i64>,
+    /// 内嵌时间的本地时间表示，格式 "YYYY-MM-DD HH:mm:ss.SSS"
+    datetime: Option<String>,
+    fields: Vec<IdField>,
+}
+
+impl DecodedId {
+    fn new(kind: IdKind, canonical: String, timestamp: Option<i64>, fields: Vec<IdField>) -> Self {
+        Self {
+            kind,
+            canonical,
+            timestamp,
+            datetime: timestamp.and_then(format_millis),
+            fields,
+        }
+    }
+}
+
+/// 批量生成指定格式的ID
+#[tauri::command]
+pub fn generate_ids(
+    kind: IdKind,
+    count: u32,
+    nano_id: Option<NanoIdOptions>,
+) -> Result<Vec<String>, String> {
+    if count == 0 || count > MAX_BATCH_SIZE {
+        return Err(format!("生成数量应在 1-{} 之间", MAX_BATCH_SIZE));
+    }
+    let nano_id = nano_id.unwrap_or_default();
+    (0..count).map(|_| generate_id(kind, &nano_id)).collect()
+}
+
+/// 解析ID，提取内嵌的时间戳与各字段
+#[tauri::command]
+pub fn decode_id(kind: IdKind, id: String) -> Result<DecodedId, String> {
+    let id = id.trim();
+    match kind {
+        IdKind::UuidV1 | IdKind::UuidV4 | IdKind::UuidV6 | IdKind::UuidV7 => decode_uuid(id),
+        IdKind::Ulid => decode_ulid(id),
+        IdKind::Ksuid => decode_ksuid(id),
+        IdKind::NanoId => Ok(decode_nano_id(id)),
+        IdKind::ObjectId => decode_object_id(id),
+    }
+}
+
+/// 生成单个ID
+pub fn generate_id(kind: IdKind, nano_id: &NanoIdOptions) -> Result<String, String> {
+    let id = match kind {
+        IdKind::UuidV1 => Uuid::now_v1(&UUID_NODE_ID).to_string(),
+        IdKind::UuidV4 => Uuid::new_v4().to_string(),
+        IdKind::UuidV6 => Uuid::now_v6(&UUID_NODE_ID).to_string(),
+        IdKind::UuidV7 => Uuid::now_v7().to_string(),
+        IdKind::Ulid => new_ulid(),
+        IdKind::Ksuid => new_ksuid(),
+        IdKind::NanoId => new_nano_id(
+            nano_id
+                .alphabet
+                .as_deref()
+                .unwrap_or(NANO_ID_DEFAULT_ALPHABET),
+            nano_id.size.unwrap_or(NANO_ID_DEFAULT_SIZE),
+        )?,
+        IdKind::ObjectId => new_object_id(),
+    };
+    Ok(id)
+}
+
+/// 生成 ULID：48 位毫秒时间戳 + 80 位随机数，Crockford Base32 编码
+pub fn new_ulid() -> String {
+    let random: u128 = rand::rng().random::<u128>() & ((1 << 80) - 1);
+    encode_ulid(((now_millis() as u128) << 80) | random)
+}
+
+/// 生成 KSUID：4 字节秒级时间戳（相对 KSUID 纪元）+ 16 字节随机负载，Base62 编码
+pub fn new_ksuid() -> String {
+    let mut bytes = [0u8; 20];
+    let timestamp = (now_millis() / 1000 - KSUID_EPOCH) as u32;
+    bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+    rand::rng().fill(&mut bytes[4..]);
+    encode_ksuid(&bytes)
+}
+
+/// 使用指定字符表与长度生成 NanoID
+pub fn new_nano_id(alphabet: &str, size: usize) -> Result<String, String> {
+    let chars: Vec<char> = alphabet.chars().collect();
+    if chars.is_empty() || chars.len() > 256 {
+        return Err("字符表长度应在 1-256 之间".to_string());
+    }
+    let mut sorted = chars.clone();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if sorted.len() != chars.len() {
+        return Err("字符表中存在重复字符".to_string());
+    }
+    if size == 0 || size > NANO_ID_MAX_SIZE {
+        return Err(format!("长度应在 1-{} 之间", NANO_ID_MAX_SIZE));
+    }
+
+    let mut rng = rand::rng();
+    Ok((0..size)
+        .map(|_| chars[rng.random_range(0..chars.len())])
+        .collect())
+}
+
+/// 生成 MongoDB ObjectId：4 字节秒级时间戳 + 5 字节进程随机值 + 3 字节计数器
+pub fn new_object_id() -> String {
+    let mut bytes = [0u8; 12];
+    let timestamp = (now_millis() / 1000) as u32;
+    let counter = OBJECT_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+    bytes[4..9].copy_from_slice(&*OBJECT_ID_PROCESS_UNIQUE);
+    bytes[9..].copy_from_slice(&counter.to_be_bytes()[1..]);
+    to_hex(&bytes)
+}
+
+fn decode_uuid(id: &str) -> Result<DecodedId, String> {
+    let uuid = Uuid::parse_str(id).map_err(|e| format!("无效的 UUID: {}", e))?;
+    let version = uuid.get_version_num();
+    let kind = match version {
+        1 => IdKind::UuidV1,
+        4 => IdKind::UuidV4,
+        6 => IdKind::UuidV6,
+        7 => IdKind::UuidV7,
+        _ => return Err(format!("不支持的 UUID 版本: {}", version)),
+    };
+
+    let mut fields = vec![
+        IdField::new("version", version),
+        IdField::new("variant", variant_name(uuid.get_variant())),
+    ];
+    let timestamp = uuid.get_timestamp().map(|ts| {
+        let (seconds, nanos) = ts.to_unix();
+        if matches!(kind, IdKind::UuidV1 | IdKind::UuidV6) {
+            let (ticks, clock_seq) = ts.to_gregorian();
+            fields.push(IdField::new("gregorian_ticks", ticks));
+            fields.push(IdField::new("clock_sequence", clock_seq));
+        }
+        seconds as i64 * 1000 + (nanos / 1_000_000) as i64
+    });
+    if let Some(node_id) = uuid.get_node_id() {
+        let node = node_id
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(":");
+        fields.push(IdField::new("node", node));
+        fields.push(IdField::new("node_multicast", node_id[0] & 0x01 == 1));
+    }
+    if kind == IdKind::UuidV7 {
+        let bytes = uuid.as_bytes();
+        let rand_a = u16::from_be_bytes([bytes[6], bytes[7]]) & 0x0FFF;
+        fields.push(IdField::new("rand_a", format!("{:03x}", rand_a)));
+    }
+
+    Ok(DecodedId::new(kind, uuid.to_string(), timestamp, fields))
+}
+
+fn decode_ulid(id: &str) -> Result<DecodedId, String> {
+    if id.len() != 26 {
+        return Err("ULID 长度应为 26 位".to_string());
+    }
+    let mut value: u128 = 0;
+    for (i, c) in id.chars().enumerate() {
+        let digit = crockford_digit(c).ok_or_else(|| format!("ULID 包含非法字符: {}", c))?;
+        if i == 0 && digit > 7 {
+            return Err("ULID 超出 128 位范围".to_string());
+        }
+        value = (value << 5) | digit as u128;
+    }
+
+    let timestamp = (value >> 80) as i64;
+    let randomness = value & ((1 << 80) - 1);
+    let fields = vec![IdField::new("randomness", format!("{:020x}", randomness))];
+    Ok(DecodedId::new(
+        IdKind::Ulid,
+        encode_ulid(value),
+        Some(timestamp),
+        fields,
+    ))
+}
+
+fn decode_ksuid(id: &str) -> Result<DecodedId, String> {
+    if id.len() != KSUID_STRING_LEN {
+        return Err(format!("KSUID 长度应为 {} 位", KSUID_STRING_LEN));
+    }
+    // 20 字节大端整数，以 u32 分段做 62 进制乘加
+    let mut words = [0u32; 5];
+    for c in id.bytes() {
+        let digit = BASE62_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or_else(|| format!("KSUID 包含非法字符: {}", c as char))?;
+        let mut carry = digit as u64;
+        for word in words.iter_mut().rev() {
+            let v = *word as u64 * 62 + carry;
+            *word = v as u32;
+            carry = v >> 32;
+        }
+        if carry != 0 {
+            return Err("KSUID 超出 160 位范围".to_string());
+        }
+    }
+    let mut bytes = [0u8; 20];
+    for (chunk, word) in bytes.chunks_mut(4).zip(words.iter()) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+
+    let offset = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64;
+    let fields = vec![
+        IdField::new("timestamp_offset", offset),
+        IdField::new("payload", to_hex(&bytes[4..])),
+    ];
+    Ok(DecodedId::new(
+        IdKind::Ksuid,
+        id.to_string(),
+        Some((offset + KSUID_EPOCH) * 1000),
+        fields,
+    ))
+}
+
+fn decode_nano_id(id: &str) -> DecodedId {
+    let mut chars: Vec<char> = id.chars().collect();
+    let size = chars.len();
+    chars.sort_unstable();
+    chars.dedup();
+    let default_alphabet = id.chars().all(|c| NANO_ID_DEFAULT_ALPHABET.contains(c));
+    // NanoID 不包含时间等结构化信息，仅给出长度与按默认字符表估算的熵
+    let mut fields = vec![
+        IdField::new("size", size),
+        IdField::new("distinct_chars", chars.len()),
+        IdField::new("default_alphabet", default_alphabet),
+    ];
+    if default_alphabet {
+        fields.push(IdField::new("entropy_bits", size * 6));
+    }
+    DecodedId::new(IdKind::NanoId, id.to_string(), None, fields)
+}
+
+fn decode_object_id(id: &str) -> Result<DecodedId, String> {
+    let bytes = from_hex(id)
+        .filter(|b| b.len() == 12)
+        .ok_or("ObjectId 应为 24 位十六进制字符串")?;
+
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as i64;
+    let counter = u32::from_be_bytes([0, bytes[9], bytes[10], bytes[11]]);
+    let fields = vec![
+        IdField::new("process_unique", to_hex(&bytes[4..9])),
+        IdField::new("counter", counter),
+    ];
+    Ok(DecodedId::new(
+        IdKind::ObjectId,
+        to_hex(&bytes),
+        Some(seconds * 1000),
+        fields,
+    ))
+}
+
+fn encode_ulid(value: u128) -> String {
+    (0..26)
+        .rev()
+        .map(|i| CROCKFORD_ALPHABET[((value >> (i * 5)) & 0x1F) as usize] as char)
+        .collect()
+}
+
+/// Crockford Base32 字符解码，忽略大小写，并兼容 I/L → 1、O → 0
+pub(crate) fn crockford_digit(c: char) -> Option<u8> {
+    let c = match c.to_ascii_uppercase() {
+        'I' | 'L' => '1',
+        'O' => '0',
+        c => c,
+    };
+    CROCKFORD_ALPHABET
+        .iter()
+        .position(|&a| a as char == c)
+        .map(|p| p as u8)
+}
+
+fn encode_ksuid(bytes: &[u8; 20]) -> String {
+    let mut number = bytes.to_vec();
+    let mut digits = Vec::with_capacity(KSUID_STRING_LEN);
+    while number.iter().any(|&b| b != 0) {
+        // 大端字节数组整体除以 62
+        let mut remainder = 0u32;
+        for byte in number.iter_mut() {
+            let acc = (remainder << 8) | *byte as u32;
+            *byte = (acc / 62) as u8;
+            remainder = acc % 62;
+        }
+        digits.push(BASE62_ALPHABET[remainder as usize]);
+    }
+    digits.resize(KSUID_STRING_LEN, b'0');
+    digits.iter().rev().map(|&b| b as char).collect()
+}
+
+fn variant_name(variant: Variant) -> &'static str {
+    match variant {
+        Variant::NCS => "NCS",
+        Variant::RFC4122 => "RFC4122",
+        Variant::Microsoft => "Microsoft",
+        Variant::Future => "Future",
+        _ => "Unknown",
+    }
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn format_millis(millis: i64) -> Option<String> {
+    Local
+        .timestamp_millis_opt(millis)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn field<'a>(decoded: &'a DecodedId, name: &str) -> &'a str {
+        &decoded
+            .fields
+            .iter()
+            .find(|f| f.name == name)
+            .unwrap()
+            .value
+    }
+
+    #[test]
+    fn test_generate_all_kinds_roundtrip() {
+        let kinds = [
+            IdKind::UuidV1,
+            IdKind::UuidV4,
+            IdKind::UuidV6,
+            IdKind::UuidV7,
+            IdKind::Ulid,
+            IdKind::Ksuid,
+            IdKind::NanoId,
+            IdKind::ObjectId,
+        ];
+        let before = now_millis() / 1000 * 1000;
+        for kind in kinds {
+            let ids = generate_ids(kind, 100, None).unwrap();
+            let unique: HashSet<_> = ids.iter().collect();
+            assert_eq!(unique.len(), 100, "{:?} 生成了重复ID", kind);
+
+            let decoded = decode_id(kind, ids[0].clone()).unwrap();
+            assert_eq!(decoded.kind, kind);
+            assert_eq!(decoded.canonical, ids[0]);
+            if let Some(timestamp) = decoded.timestamp {
+                assert!(
+                    timestamp >= before && timestamp <= now_millis(),
+                    "{:?} 时间戳异常",
+                    kind
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_ids_invalid_count() {
+        assert!(generate_ids(IdKind::UuidV4, 0, None).is_err());
+        assert!(generate_ids(IdKind::UuidV4, MAX_BATCH_SIZE + 1, None).is_err());
+    }
+
+    /// RFC 9562 附录中的示例
+    #[test]
+    fn test_decode_uuid_rfc_examples() {
+        let v1 = decode_id(
+            IdKind::UuidV1,
+            "C232AB00-9414-11EC-B3C8-9F6BDECED846".to_string(),
+        )
+        .unwrap();
+        assert_eq!(v1.timestamp, Some(1645557742000));
+        assert_eq!(field(&v1, "node"), "9f:6b:de:ce:d8:46");
+        assert_eq!(field(&v1, "clock_sequence"), "13256");
+
+        let v6 = decode_id(
+            IdKind::UuidV6,
+            "1EC9414C-232A-6B00-B3C8-9F6BDECED846".to_string(),
+        )
+        .unwrap();
+        assert_eq!(v6.timestamp, Some(1645557742000));
+
+        let v7 = decode_id(
+            IdKind::UuidV7,
+            "017F22E2-79B0-7CC3-98C4-DC0C0C07398F".to_string(),
+        )
+        .unwrap();
+        assert_eq!(v7.kind, IdKind::UuidV7);
+        assert_eq!(v7.timestamp, Some(1645557742000));
+        assert_eq!(field(&v7, "variant"), "RFC4122");
+    }
+
+    #[test]
+    fn test_decode_ulid() {
+        let decoded = decode_id(IdKind::Ulid, "01arz3ndektsv4rrffq69g5fav".to_string()).unwrap();
+        assert_eq!(decoded.canonical, "01ARZ3NDEKTSV4RRFFQ69G5FAV");
+        assert_eq!(decoded.timestamp, Some(1469922850259));
+        assert!(decode_id(IdKind::Ulid, "81ARZ3NDEKTSV4RRFFQ69G5FAV".to_string()).is_err());
+        assert!(decode_id(IdKind::Ulid, "01ARZ3NDEKTSV4RRFFQ69G5FA".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_decode_ksuid() {
+        let decoded = decode_id(IdKind::Ksuid, "0ujtsYcgvSTl8PAuAdqWYSMnLOv".to_string()).unwrap();
+        assert_eq!(decoded.timestamp, Some(1507608047000));
+        assert_eq!(
+            field(&decoded, "payload"),
+            "b5a1cd34b5f99d1154fb6853345c9735"
+        );
+        assert!(decode_id(IdKind::Ksuid, "aWgEPTl1tmebfsQzFP4bxwgy80W".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_ksuid_encode_bounds() {
+        assert_eq!(encode_ksuid(&[0; 20]), "000000000000000000000000000");
+        assert_eq!(encode_ksuid(&[0xFF; 20]), "aWgEPTl1tmebfsQzFP4bxwgy80V");
+    }
+
+    #[test]
+    fn test_decode_object_id() {
+        let decoded = decode_id(IdKind::ObjectId, "507f1f77bcf86cd799439011".to_string()).unwrap();
+        assert_eq!(decoded.timestamp, Some(1350508407000));
+        assert_eq!(field(&decoded, "process_unique"), "bcf86cd799");
+        assert_eq!(field(&decoded, "counter"), "4427793");
+        assert!(decode_id(IdKind::ObjectId, "507f1f77bcf86cd79943901".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_nano_id_custom_alphabet() {
+        let options = NanoIdOptions {
+            alphabet: Some("abc".to_string()),
+            size: Some(40),
+        };
+        let ids = generate_ids(IdKind::NanoId, 10, Some(options)).unwrap();
+        for id in ids {
+            assert_eq!(id.len(), 40);
+            assert!(id.chars().all(|c| "abc".contains(c)));
+        }
+        assert!(new_nano_id("aab", 10).is_err());
+        assert!(new_nano_id("", 10).is_err());
+        assert!(new_nano_id("abc", 0).is_err());
+    }
+}
@@ -0,0 +1,561 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::process::Command;
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketProtocol {
+    Tcp,
+    Udp,
+}
+
+/// 本机套接字
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct LocalSocket {
+    protocol: SocketProtocol,
+    ipv6: bool,
+    local_address: String,
+    local_port: u16,
+    remote_address: Option<String>,
+    remote_port: Option<u16>,
+    /// TCP 状态，如 LISTEN、ESTABLISHED；未连接的 UDP 套接字为 UNCONN
+    state: String,
+    /// 无权限查看其他用户的进程时为空
+    pid: Option<u32>,
+    process_name: Option<String>,
+    command_line: Option<String>,
+}
+
+impl LocalSocket {
+    fn is_listening(&self) -> bool {
+        match self.protocol {
+            SocketProtocol::Tcp => self.state == "LISTEN",
+            SocketProtocol::Udp => self.remote_port.is_none(),
+        }
+    }
+}
+
+/// 列出本机 TCP、UDP 套接字及所属进程
+/// - listening_only: 只返回监听中的 TCP 端口与未连接的 UDP 端口
+#[tauri::command]
+pub async fn list_local_sockets(listening_only: Option<bool>) -> Result<Vec<LocalSocket>, String> {
+    let mut sockets = tokio::task::spawn_blocking(collect)
+        .await
+        .map_err(|e| e.to_string())??;
+    if listening_only.unwrap_or(false) {
+        sockets.retain(LocalSocket::is_listening);
+    }
+    sockets.sort_by(|a, b| {
+        (a.local_port, a.protocol as u8, a.ipv6).cmp(&(b.local_port, b.protocol as u8, b.ipv6))
+    });
+    Ok(sockets)
+}
+
+/// 结束进程
+/// - force: 为真时强制结束（SIGKILL、taskkill /F），否则发送 SIGTERM
+#[tauri::command]
+pub async fn kill_process(pid: u32, force: Option<bool>) -> Result<String, String> {
+    if pid <= 1 || pid == std::process::id() {
+        return Err(format!("不允许结束进程 {}", pid));
+    }
+    let force = force.unwrap_or(false);
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = command("taskkill");
+        command.args(["/PID", &pid.to_string()]);
+        if force {
+            command.arg("/F");
+        }
+        command
+    };
+    #[cfg(not(windows))]
+    let mut command = {
+        let mut command = command("kill");
+        command.args([if force { "-KILL" } else { "-TERM" }, &pid.to_string()]);
+        command
+    };
+    let output = tokio::task::spawn_blocking(move || command.output())
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| format!("结束进程失败: {}", e))?;
+    if output.status.success() {
+        Ok(format!("已结束进程 {}", pid))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let message = if stderr.trim().is_empty() {
+            stdout
+        } else {
+            stderr
+        };
+        Err(format!("结束进程 {} 失败: {}", pid, message.trim()))
+    }
+}
+
+fn command(program: &str) -> Command {
+    #[allow(unused_mut)]
+    let mut command = Command::new(program);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        // CREATE_NO_WINDOW，避免弹出控制台窗口
+        command.creation_flags(0x0800_0000);
+    }
+    command
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+pub(crate) fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = command(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("执行 {} 失败: {}", program, e))?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(target_os = "linux")]
+fn collect() -> Result<Vec<LocalSocket>, String> {
+    let mut sockets = Vec::new();
+    let mut inodes = Vec::new();
+    for (file, protocol, ipv6) in [
+        ("/proc/net/tcp", SocketProtocol::Tcp, false),
+        ("/proc/net/tcp6", SocketProtocol::Tcp, true),
+        ("/proc/net/udp", SocketProtocol::Udp, false),
+        ("/proc/net/udp6", SocketProtocol::Udp, true),
+    ] {
+        // 未启用 IPv6 时 tcp6、udp6 不存在
+        let Ok(content) = std::fs::read_to_string(file) else {
+            continue;
+        };
+        for (socket, inode) in parse_proc_net(&content, protocol, ipv6) {
+            sockets.push(socket);
+            inodes.push(inode);
+        }
+    }
+
+    let owners = socket_owners();
+    let mut processes: HashMap<u32, (Option<String>, Option<String>)> = HashMap::new();
+    for (socket, inode) in sockets.iter_mut().zip(inodes) {
+        let Some(pid) = owners.get(&inode).copied() else {
+            continue;
+        };
+        let (name, command_line) = processes.entry(pid).or_insert_with(|| {
+            let name = std::fs::read_to_string(format!("/proc/{}/comm", pid))
+                .ok()
+                .map(|s| s.trim_end().to_string());
+            let command_line = std::fs::read(format!("/proc/{}/cmdline", pid))
+                .ok()
+                .map(|raw| {
+                    raw.split(|b| *b == 0)
+                        .filter(|arg| !arg.is_empty())
+                        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .filter(|s| !s.is_empty());
+            (name, command_line)
+        });
+        socket.pid = Some(pid);
+        socket.process_name = name.clone();
+        socket.command_line = command_line.clone();
+    }
+    Ok(sockets)
+}
+
+/// 遍历 /proc/<pid>/fd 建立套接字 inode 到进程的映射，无权限的进程会被跳过
+#[cfg(target_os = "linux")]
+fn socket_owners() -> HashMap<u64, u32> {
+    let mut owners = HashMap::new();
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return owners;
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|s| s.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            let target = target.to_string_lossy();
+            if let Some(inode) = target
+                .strip_prefix("socket:[")
+                .and_then(|s| s.strip_suffix(']'))
+                .and_then(|s| s.parse().ok())
+            {
+                owners.entry(inode).or_insert(pid);
+            }
+        }
+    }
+    owners
+}
+
+#[cfg(target_os = "macos")]
+fn collect() -> Result<Vec<LocalSocket>, String> {
+    let mut sockets = parse_lsof(&run("lsof", &["-nP", "-iTCP", "-iUDP", "-FpcftPTn"])?);
+    let command_lines: HashMap<u32, String> = run("ps", &["-axo", "pid=,command="])?
+        .lines()
+        .filter_map(|line| {
+            let (pid, command) = line.trim().split_once(char::is_whitespace)?;
+            Some((pid.parse().ok()?, command.trim().to_string()))
+        })
+        .collect();
+    for socket in &mut sockets {
+        socket.command_line = socket.pid.and_then(|pid| command_lines.get(&pid).cloned());
+    }
+    Ok(sockets)
+}
+
+#[cfg(target_os = "windows")]
+fn collect() -> Result<Vec<LocalSocket>, String> {
+    let mut sockets = parse_netstat(&run("netstat", &["-ano"])?);
+    let names: HashMap<u32, String> = run("tasklist", &["/FO", "CSV", "/NH"])?
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split("\",\"");
+            let name = fields.next()?.trim_start_matches('"').to_string();
+            let pid = fields.next()?.parse().ok()?;
+            Some((pid, name))
+        })
+        .collect();
+    // 命令行只是附加信息，PowerShell 不可用时保留为空
+    let command_lines = run(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "[Console]::OutputEncoding = [Text.Encoding]::UTF8; \
+             Get-CimInstance Win32_Process | ForEach-Object { \"$($_.ProcessId)`t$($_.CommandLine)\" }",
+        ],
+    )
+    .map(|output| parse_process_command_lines(&output))
+    .unwrap_or_default();
+    for socket in &mut sockets {
+        socket.process_name = socket.pid.and_then(|pid| names.get(&pid).cloned());
+        socket.command_line = socket.pid.and_then(|pid| command_lines.get(&pid).cloned());
+    }
+    Ok(sockets)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn collect() -> Result<Vec<LocalSocket>, String> {
+    Err("当前系统暂不支持查看本机端口".to_string())
+}
+
+/// 解析 /proc/net/{tcp,tcp6,udp,udp6}，返回套接字与其 inode
+pub fn parse_proc_net(
+    content: &str,
+    protocol: SocketProtocol,
+    ipv6: bool,
+) -> Vec<(LocalSocket, u64)> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+            let (local_address, local_port) = parse_proc_address(fields[1])?;
+            let (remote_address, remote_port) = parse_proc_address(fields[2])?;
+            let state = u8::from_str_radix(fields[3], 16).ok()?;
+            let inode = fields[9].parse().ok()?;
+            let state = match (protocol, state) {
+                (SocketProtocol::Udp, 0x07) => "UNCONN",
+                (_, 0x01) => "ESTABLISHED",
+                (_, 0x02) => "SYN_SENT",
+                (_, 0x03) => "SYN_RECV",
+                (_, 0x04) => "FIN_WAIT1",
+                (_, 0x05) => "FIN_WAIT2",
+                (_, 0x06) => "TIME_WAIT",
+                (_, 0x07) => "CLOSE",
+                (_, 0x08) => "CLOSE_WAIT",
+                (_, 0x09) => "LAST_ACK",
+                (_, 0x0A) => "LISTEN",
+                (_, 0x0B) => "CLOSING",
+                (_, 0x0C) => "NEW_SYN_RECV",
+                _ => "UNKNOWN",
+            };
+            let connected = remote_port != 0;
+            Some((
+                LocalSocket {
+                    protocol,
+                    ipv6,
+                    local_address,
+                    local_port,
+                    remote_address: connected.then_some(remote_address),
+                    remote_port: connected.then_some(remote_port),
+                    state: state.to_string(),
+                    pid: None,
+                    process_name: None,
+                    command_line: None,
+                },
+                inode,
+            ))
+        })
+        .collect()
+}
+
+/// 解析 `0100007F:1F90` 形式的地址，IP 按 32 位字以主机字节序（小端）存放
+fn parse_proc_address(value: &str) -> Option<(String, u16)> {
+    let (ip, port) = value.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut bytes = Vec::with_capacity(16);
+    for word in 0..ip.len() / 8 {
+        let word = u32::from_str_radix(&ip[word * 8..word * 8 + 8], 16).ok()?;
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+    let ip = match bytes.len() {
+        4 => Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]).to_string(),
+        16 => {
+            let octets: [u8; 16] = bytes.try_into().ok()?;
+            let ip = Ipv6Addr::from(octets);
+            // IPv4 映射地址显示为 IPv4 形式
+            match ip.to_ipv4_mapped() {
+                Some(v4) => v4.to_string(),
+                None => ip.to_string(),
+            }
+        }
+        _ => return None,
+    };
+    Some((ip, port))
+}
+
+/// 拆分 `127.0.0.1:80`、`[::1]:80`、`*:80` 形式的地址
+fn split_host_port(value: &str) -> Option<(String, Option<u16>)> {
+    let (host, port) = value.rsplit_once(':')?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let host = if host == "*" { "0.0.0.0" } else { host };
+    Some((host.to_string(), port.parse().ok()))
+}
+
+/// 解析 `lsof -FpcftPTn` 的输出
+pub fn parse_lsof(output: &str) -> Vec<LocalSocket> {
+    let mut sockets: Vec<LocalSocket> = Vec::new();
+    let mut pid = None;
+    let mut process_name = None;
+    let mut current: Option<LocalSocket> = None;
+    for line in output.lines() {
+        let (field, value) = line.split_at(line.len().min(1));
+        match field {
+            "p" => {
+                sockets.extend(current.take());
+                pid = value.parse().ok();
+                process_name = None;
+            }
+            "c" => process_name = Some(value.to_string()),
+            "f" => {
+                sockets.extend(current.take());
+                current = Some(LocalSocket {
+                    protocol: SocketProtocol::Tcp,
+                    ipv6: false,
+                    local_address: String::new(),
+                    local_port: 0,
+                    remote_address: None,
+                    remote_port: None,
+                    state: String::new(),
+                    pid,
+                    process_name: process_name.clone(),
+                    command_line: None,
+                });
+            }
+            "t" => {
+                if let Some(socket) = current.as_mut() {
+                    socket.ipv6 = value == "IPv6";
+                }
+            }
+            "P" => {
+                if let Some(socket) = current.as_mut() {
+                    socket.protocol = if value.eq_ignore_ascii_case("UDP") {
+                        SocketProtocol::Udp
+                    } else {
+                        SocketProtocol::Tcp
+                    };
+                }
+            }
+            "T" => {
+                if let (Some(socket), Some(state)) = (current.as_mut(), value.strip_prefix("ST=")) {
+                    socket.state = state.to_string();
+                }
+            }
+            "n" => {
+                if let Some(socket) = current.as_mut() {
+                    let (local, remote) = match value.split_once("->") {
+                        Some((local, remote)) => (local, Some(remote)),
+                        None => (value, None),
+                    };
+                    if let Some((address, port)) = split_host_port(local) {
+                        socket.local_address = address;
+                        socket.local_port = port.unwrap_or(0);
+                    }
+                    if let Some((address, port)) = remote.and_then(split_host_port) {
+                        socket.remote_address = Some(address);
+                        socket.remote_port = port;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    sockets.extend(current.take());
+    sockets.retain(|s| !s.local_address.is_empty());
+    for socket in &mut sockets {
+        if socket.state.is_empty() {
+            socket.state = if socket.remote_port.is_some() {
+                "ESTABLISHED".to_string()
+            } else {
+                "UNCONN".to_string()
+            };
+        }
+    }
+    sockets
+}
+
+/// 解析 Windows `netstat -ano` 的输出
+pub fn parse_netstat(output: &str) -> Vec<LocalSocket> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let protocol = match fields.first()?.to_ascii_uppercase().as_str() {
+                "TCP" => SocketProtocol::Tcp,
+                "UDP" => SocketProtocol::Udp,
+                _ => return None,
+            };
+            let (state, pid) = match (protocol, fields.len()) {
+                (SocketProtocol::Tcp, 5) => (fields[3], fields[4]),
+                (SocketProtocol::Udp, 4) => ("", fields[3]),
+                _ => return None,
+            };
+            let (local_address, local_port) = split_host_port(fields[1])?;
+            let (remote_address, remote_port) = split_host_port(fields[2])
+                .filter(|(_, port)| port.is_some_and(|p| p != 0))
+                .map_or((None, None), |(address, port)| (Some(address), port));
+            let state = match state {
+                "" if remote_port.is_some() => "ESTABLISHED",
+                "" => "UNCONN",
+                "LISTENING" => "LISTEN",
+                other => other,
+            };
+            Some(LocalSocket {
+                protocol,
+                ipv6: fields[1].starts_with('['),
+                local_address,
+                local_port: local_port?,
+                remote_address,
+                remote_port,
+                state: state.to_string(),
+                pid: pid.parse().ok().filter(|pid| *pid != 0),
+                process_name: None,
+                command_line: None,
+            })
+        })
+        .collect()
+}
+
+/// 解析每行 `PID<Tab>命令行` 格式的进程列表，跳过命令行为空的进程
+pub fn parse_process_command_lines(output: &str) -> HashMap<u32, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (pid, command) = line.split_once('\t')?;
+            let command = command.trim();
+            if command.is_empty() {
+                return None;
+            }
+            Some((pid.trim().parse().ok()?, command.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_net() {
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n\
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 12345 1 0000000000000000 100 0 0 10 0\n\
+   1: 0100007F:1F90 0100007F:D431 01 00000000:00000000 00:00000000 00000000  1000        0 12346 1 0000000000000000 20 4 30 10 -1\n";
+        let sockets = parse_proc_net(tcp, SocketProtocol::Tcp, false);
+        assert_eq!(sockets.len(), 2);
+        let (listen, inode) = &sockets[0];
+        assert_eq!(*inode, 12345);
+        assert_eq!(listen.local_address, "127.0.0.1");
+        assert_eq!(listen.local_port, 8080);
+        assert_eq!(listen.state, "LISTEN");
+        assert_eq!(listen.remote_port, None);
+        assert!(listen.is_listening());
+        assert_eq!(sockets[1].0.remote_port, Some(54321));
+        assert_eq!(sockets[1].0.state, "ESTABLISHED");
+
+        let udp6 = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops\n\
+  10: 00000000000000000000000001000000:0035 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 999 2 0000000000000000 0\n\
+  11: 0000000000000000FFFF00000100007F:0044 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000   101        0 998 2 0000000000000000 0\n";
+        let sockets = parse_proc_net(udp6, SocketProtocol::Udp, true);
+        assert_eq!(sockets[0].0.local_address, "::1");
+        assert_eq!(sockets[0].0.local_port, 53);
+        assert_eq!(sockets[0].0.state, "UNCONN");
+        assert_eq!(sockets[1].0.local_address, "127.0.0.1");
+    }
+
+    #[test]
+    fn test_parse_lsof_and_netstat() {
+        let lsof = "p501\ncnode\nf23\ntIPv4\nPTCP\nn*:3000\nTST=LISTEN\nf24\ntIPv6\nPTCP\nn[::1]:3000->[::1]:50000\nTST=ESTABLISHED\np77\ncmDNSResponder\nf5\ntIPv4\nPUDP\nn*:5353\n";
+        let sockets = parse_lsof(lsof);
+        assert_eq!(sockets.len(), 3);
+        assert_eq!(sockets[0].pid, Some(501));
+        assert_eq!(sockets[0].process_name.as_deref(), Some("node"));
+        assert_eq!(sockets[0].local_address, "0.0.0.0");
+        assert_eq!(sockets[0].state, "LISTEN");
+        assert!(sockets[1].ipv6);
+        assert_eq!(sockets[1].remote_address.as_deref(), Some("::1"));
+        assert_eq!(sockets[1].remote_port, Some(50000));
+        assert_eq!(sockets[2].protocol, SocketProtocol::Udp);
+        assert_eq!(sockets[2].state, "UNCONN");
+
+        let netstat = "\r\nActive Connections\r\n\r\n  Proto  Local Address          Foreign Address        State           PID\r\n  TCP    0.0.0.0:135            0.0.0.0:0              LISTENING       1024\r\n  TCP    [::1]:5432             [::1]:61000            ESTABLISHED     4242\r\n  UDP    0.0.0.0:123            *:*                                    2048\r\n";
+        let sockets = parse_netstat(netstat);
+        assert_eq!(sockets.len(), 3);
+        assert_eq!(sockets[0].state, "LISTEN");
+        assert_eq!(sockets[0].remote_port, None);
+        assert_eq!(sockets[0].pid, Some(1024));
+        assert!(sockets[1].ipv6);
+        assert_eq!(sockets[1].local_address, "::1");
+        assert_eq!(sockets[1].remote_port, Some(61000));
+        assert_eq!(sockets[2].state, "UNCONN");
+        assert_eq!(sockets[2].pid, Some(2048));
+
+        let command_lines = parse_process_command_lines(
+            "0\t\r\n4\t\r\n1024\tC:\\Windows\\system32\\svchost.exe -k RPCSS -p\r\n",
+        );
+        assert_eq!(command_lines.len(), 1);
+        assert_eq!(
+            command_lines.get(&1024).map(String::as_str),
+            Some("C:\\Windows\\system32\\svchost.exe -k RPCSS -p")
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_list_finds_own_listener() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let sockets = list_local_sockets(Some(true)).await.unwrap();
+        let socket = sockets
+            .iter()
+            .find(|s| s.protocol == SocketProtocol::Tcp && s.local_port == port)
+            .expect("listener not found");
+        assert_eq!(socket.pid, Some(std::process::id()));
+        assert!(socket.command_line.is_some());
+    }
+}
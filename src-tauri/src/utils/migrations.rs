@@ -0,0 +1,112 @@
+use tauri_plugin_sql::{Migration, MigrationKind};
+
+/// 前端通过 `sqlite:test.db` 访问的数据库
+pub const DB_URL: &str = "sqlite:test.db";
+
+/// 数据库表结构迁移，按版本号顺序执行
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration {
+            version: 1,
+            description: "create_http_collection_and_request",
+            sql: r#"
+CREATE TABLE IF NOT EXISTS http_collection (
+    id bigint primary key, -- 集合ID
+    name varchar(128) not null, -- 集合名称
+    description varchar(1024), -- 集合描述
+    create_time datetime, -- 创建时间
+    update_time datetime -- 修改时间
+);
+CREATE TABLE IF NOT EXISTS http_request (
+    id bigint primary key, -- 请求ID
+    collection_id bigint, -- 所属集合ID，为空时不属于任何集合
+    name varchar(128) not null, -- 请求名称
+    method varchar(16), -- 请求方法
+    url varchar(2048), -- 请求地址
+    request_json text not null, -- 完整请求定义（JSON）
+    create_time datetime, -- 创建时间
+    update_time datetime -- 修改时间
+);
+CREATE INDEX IF NOT EXISTS idx_http_request_collection ON http_request (collection_id);
+"#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 2,
+            description: "create_monitor_and_history",
+            sql: r#"
+CREATE TABLE IF NOT EXISTS monitor (
+    id bigint primary key, -- 监控ID
+    name varchar(128) not null, -- 监控名称
+    kind varchar(16) not null, -- 监控类型：tcp、http
+    host varchar(255), -- tcp监控的主机
+    port integer, -- tcp监控的端口
+    url varchar(2048), -- http监控的地址
+    expected_status integer, -- http监控期望的状态码，为空时小于400即为正常
+    verify_tls boolean, -- http监控是否校验证书
+    interval_secs integer not null, -- 检测间隔（秒）
+    timeout_ms integer, -- 检测超时时间（毫秒）
+    last_state varchar(16), -- 最新状态：up、down
+    effective boolean, -- 0: 已停止 1：运行中
+    create_time datetime, -- 创建时间
+    update_time datetime -- 修改时间
+);
+CREATE TABLE IF NOT EXISTS monitor_history (
+    id bigint primary key, -- 记录ID
+    monitor_id bigint not null, -- 监控ID
+    state varchar(16) not null, -- 状态：up、down
+    latency_ms real, -- 耗时（毫秒）
+    message varchar(1024), -- 失败原因
+    check_time datetime -- 检测时间
+);
+CREATE INDEX IF NOT EXISTS idx_monitor_history_monitor ON monitor_history (monitor_id, check_time);
+"#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 3,
+            description: "create_websocket_session",
+            sql: r#"
+CREATE TABLE IF NOT EXISTS websocket_session (
+    id bigint primary key, -- 会话ID
+    name varchar(128) not null, -- 会话名称
+    url varchar(2048) not null, -- 连接地址
+    request_json text not null, -- 完整连接配置（JSON）
+    create_time datetime, -- 创建时间
+    update_time datetime -- 修改时间
+);
+"#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 4,
+            description: "create_wol_device",
+            sql: r#"
+CREATE TABLE IF NOT EXISTS wol_device (
+    id bigint primary key, -- 设备ID
+    name varchar(128) not null, -- 设备名称
+    mac varchar(32) not null, -- MAC地址
+    broadcast varchar(255), -- 广播地址，为空时使用 255.255.255.255
+    port integer, -- 端口，为空时使用 9
+    password varchar(32), -- SecureOn 密码
+    show_in_tray boolean, -- 是否显示在托盘菜单
+    create_time datetime, -- 创建时间
+    update_time datetime -- 修改时间
+);
+"#,
+            kind: MigrationKind::Up,
+        },
+        Migration {
+            version: 5,
+            description: "create_app_setting",
+            sql: r#"
+CREATE TABLE IF NOT EXISTS app_setting (
+    name varchar(64) primary key, -- 设置项名称
+    value text, -- 设置内容，JSON 格式
+    update_time datetime -- 修改时间
+);
+"#,
+            kind: MigrationKind::Up,
+        },
+    ]
+}
@@ -0,0 +1,350 @@
+use crate::utils::http_client::{self, HttpRequest};
+use crate::utils::network_util::{self, check_port, MAX_CONNECT_TIMEOUT_MS};
+use crate::utils::scheduled_tasks::SCHEDULER;
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use tokio_cron_scheduler::Job;
+
+/// 默认检测超时时间（毫秒）
+const DEFAULT_MONITOR_TIMEOUT_MS: u64 = 5_000;
+/// 最小检测间隔（秒）
+const MIN_INTERVAL_SECS: u64 = 5;
+
+/// 监控目标
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MonitorTarget {
+    /// TCP 端口能连通即为正常
+    Tcp { host: String, port: u16 },
+    /// HTTP 状态码与 `expected_status` 一致即为正常，未设置时状态码小于 400 即为正常
+    Http {
+        url: String,
+        expected_status: Option<u16>,
+        verify_tls: Option<bool>,
+    },
+}
+
+/// 监控对象
+#[derive(Deserialize, Clone, Debug)]
+pub struct Monitor {
+    pub id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub target: MonitorTarget,
+    /// 检测间隔（秒），最小 5 秒
+    pub interval_secs: u64,
+    /// 单次检测超时时间（毫秒），默认 5000，不超过检测间隔
+    pub timeout_ms: Option<u64>,
+    /// 上次记录的状态，用于应用重启后判断状态是否变化
+    pub last_state: Option<MonitorState>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorState {
+    Up,
+    Down,
+}
+
+/// 单次检测结果，通过 `monitor_checked` 事件发送给前端
+#[derive(Serialize, Clone, Debug)]
+pub struct MonitorCheck {
+    monitor_id: String,
+    state: MonitorState,
+    latency_ms: Option<f64>,
+    message: Option<String>,
+    /// 格式 "YYYY-MM-DD HH:mm:ss"
+    check_time: String,
+}
+
+/// 状态变化事件 `monitor_state_changed` 的内容
+#[derive(Serialize, Clone, Debug)]
+struct MonitorStateChanged<'a> {
+    monitor_id: &'a str,
+    name: &'a str,
+    previous: MonitorState,
+    state: MonitorState,
+    message: Option<&'a str>,
+    check_time: &'a str,
+}
+
+/// 运行中的监控，Key 是 Monitor.id，Value 是 Job UUID
+static MONITOR_POOL: Lazy<Mutex<HashMap<String, uuid::Uuid>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+/// 监控的最新状态，Key 是 Monitor.id
+static MONITOR_STATES: Lazy<Mutex<HashMap<String, MonitorState>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 启动监控，已在运行的同 ID 监控会先停止
+#[tauri::command]
+pub async fn start_monitor(app_handle: AppHandle, monitor: Monitor) -> Result<String, String> {
+    if monitor.interval_secs < MIN_INTERVAL_SECS {
+        return Err(format!("检测间隔不能小于 {} 秒", MIN_INTERVAL_SECS));
+    }
+    let scheduler = SCHEDULER
+        .get()
+        .ok_or_else(|| "Scheduler not initialized".to_string())?;
+    remove_job(&monitor.id).await?;
+
+    {
+        let mut states = MONITOR_STATES.lock().unwrap();
+        match monitor.last_state {
+            Some(state) => states.insert(monitor.id.clone(), state),
+            None => states.remove(&monitor.id),
+        };
+    }
+
+    let job_monitor = monitor.clone();
+    let job_app_handle = app_handle.clone();
+    let job = Job::new_repeated_async(
+        Duration::from_secs(monitor.interval_secs),
+        move |_uuid, _l| {
+            let app_handle = job_app_handle.clone();
+            let monitor = job_monitor.clone();
+            Box::pin(async move {
+                run_check(&app_handle, &monitor).await;
+            })
+        },
+    )
+    .map_err(|e| format!("创建监控任务失败: {}", e))?;
+    let job_id = scheduler.add(job).await.map_err(|e| e.to_string())?;
+    MONITOR_POOL
+        .lock()
+        .unwrap()
+        .insert(monitor.id.clone(), job_id);
+
+    // 定时任务在一个间隔后才首次执行，启动时先检测一次
+    network_util::spawn(async move {
+        run_check(&app_handle, &monitor).await;
+    });
+    Ok(format!("监控已启动，任务ID: {}", job_id))
+}
+
+/// 停止监控
+#[tauri::command]
+pub async fn stop_monitor(id: String) -> Result<String, String> {
+    if remove_job(&id).await? {
+        MONITOR_STATES.lock().unwrap().remove(&id);
+        Ok(format!("监控 {} 已停止", id))
+    } else {
+        Err(format!("未找到监控: {}", id))
+    }
+}
+
+/// 立即检测一次，不记录状态
+#[tauri::command]
+pub async fn check_monitor(monitor: Monitor) -> Result<MonitorCheck, String> {
+    Ok(check(&monitor).await)
+}
+
+/// 运行中的监控 ID 列表
+#[tauri::command]
+pub fn running_monitors() -> Vec<String> {
+    MONITOR_POOL.lock().unwrap().keys().cloned().collect()
+}
+
+async fn remove_job(id: &str) -> Result<bool, String> {
+    // 提前移除任务 ID，避免跨 .await 锁住 Mutex
+    let job_id = MONITOR_POOL.lock().unwrap().remove(id);
+    let Some(job_id) = job_id else {
+        return Ok(false);
+    };
+    let scheduler = SCHEDULER
+        .get()
+        .ok_or_else(|| "Scheduler not initialized".to_string())?;
+    scheduler.remove(&job_id).await.map_err(|e| e.to_string())?;
+    Ok(true)
+}
+
+/// 检测并通知前端，状态变化时发送桌面通知
+async fn run_check(app_handle: &AppHandle, monitor: &Monitor) {
+    let result = check(monitor).await;
+    // 检测期间监控可能已被停止
+    if !MONITOR_POOL.lock().unwrap().contains_key(&monitor.id) {
+        return;
+    }
+    let previous = MONITOR_STATES
+        .lock()
+        .unwrap()
+        .insert(monitor.id.clone(), result.state);
+
+    if let Err(e) = app_handle.emit_to("main", "monitor_checked", &result) {
+        eprintln!("发送监控检测结果失败: {}", e);
+    }
+    let Some(previous) = previous.filter(|p| *p != result.state) else {
+        return;
+    };
+
+    let body = match result.state {
+        MonitorState::Up => format!("{} 已恢复", monitor.name),
+        MonitorState::Down => format!(
+            "{} 不可用: {}",
+            monitor.name,
+            result.message.as_deref().unwrap_or("检测失败")
+        ),
+    };
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title("监控状态变化")
+        .body(body)
+        .show()
+    {
+        eprintln!("发送监控通知失败: {}", e);
+    }
+    let event = MonitorStateChanged {
+        monitor_id: &monitor.id,
+        name: &monitor.name,
+        previous,
+        state: result.state,
+        message: result.message.as_deref(),
+        check_time: &result.check_time,
+    };
+    if let Err(e) = app_handle.emit_to("main", "monitor_state_changed", event) {
+        eprintln!("发送监控状态变化事件失败: {}", e);
+    }
+}
+
+/// 检测一次监控目标
+pub async fn check(monitor: &Monitor) -> MonitorCheck {
+    let timeout_ms = monitor
+        .timeout_ms
+        .unwrap_or(DEFAULT_MONITOR_TIMEOUT_MS)
+        .clamp(1, MAX_CONNECT_TIMEOUT_MS)
+        .min(monitor.interval_secs.max(1) * 1000);
+    let (state, latency_ms, message) = match &monitor.target {
+        MonitorTarget::Tcp { host, port } => {
+            let result = check_port(host, *port, Duration::from_millis(timeout_ms)).await;
+            let state = if result.is_open() {
+                MonitorState::Up
+            } else {
+                MonitorState::Down
+            };
+            (state, result.latency_ms, result.message)
+        }
+        MonitorTarget::Http {
+            url,
+            expected_status,
+            verify_tls,
+        } => {
+            let request = HttpRequest {
+                method: "GET".to_string(),
+                url: url.clone(),
+                timeout_ms: Some(timeout_ms),
+                verify_tls: *verify_tls,
+                ..Default::default()
+            };
+            match http_client::send_http_request(request).await {
+                Ok(response) => {
+                    let up = match expected_status {
+                        Some(expected) => response.status == *expected,
+                        None => response.status < 400,
+                    };
+                    let state = if up {
+                        MonitorState::Up
+                    } else {
+                        MonitorState::Down
+                    };
+                    let message = (!up).then(|| format!("HTTP 状态码 {}", response.status));
+                    (state, Some(response.timing.total_ms), message)
+                }
+                Err(e) => (MonitorState::Down, None, Some(e)),
+            }
+        }
+    };
+    MonitorCheck {
+        monitor_id: monitor.id.clone(),
+        state,
+        latency_ms,
+        message,
+        check_time: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::network_util::stand_in::StandIn;
+    use crate::utils::network_util::with_connector;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn monitor(target: MonitorTarget) -> Monitor {
+        Monitor {
+            id: "1".to_string(),
+            name: "test".to_string(),
+            target,
+            interval_secs: 5,
+            timeout_ms: Some(2_000),
+            last_state: None,
+        }
+    }
+
+    #[test]
+    fn test_deserialize_monitor() {
+        let monitor: Monitor = serde_json::from_str(
+            r#"{"id":"7","name":"api","kind":"http","url":"http://h.test","expected_status":204,
+                "verify_tls":null,"interval_secs":30,"timeout_ms":null,"last_state":"down"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            monitor.target,
+            MonitorTarget::Http {
+                expected_status: Some(204),
+                ..
+            }
+        ));
+        assert_eq!(monitor.last_state, Some(MonitorState::Down));
+    }
+
+    #[tokio::test]
+    async fn test_check_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let target = MonitorTarget::Tcp {
+            host: "127.0.0.1".to_string(),
+            port,
+        };
+        let result = check(&monitor(target.clone())).await;
+        assert_eq!(result.state, MonitorState::Up);
+        assert!(result.latency_ms.is_some());
+
+        let connector = StandIn::new().await.build();
+        let result = with_connector(connector, check(&monitor(target))).await;
+        assert_eq!(result.state, MonitorState::Down);
+        assert!(result.message.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_check_http_expected_status() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+        let target = |expected_status| MonitorTarget::Http {
+            url: format!("http://127.0.0.1:{}/health", port),
+            expected_status,
+            verify_tls: None,
+        };
+        let result = check(&monitor(target(None))).await;
+        assert_eq!(result.state, MonitorState::Down);
+        assert_eq!(result.message.as_deref(), Some("HTTP 状态码 503"));
+        assert!(result.latency_ms.is_some());
+
+        let result = check(&monitor(target(Some(503)))).await;
+        assert_eq!(result.state, MonitorState::Up);
+    }
+}
@@ -0,0 +1,459 @@
+use crate::utils::dns_util::system_nameservers;
+use network_interface::{Addr, NetworkInterface, NetworkInterfaceConfig};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, UdpSocket};
+
+/// 用于确定出口地址的公共 DNS 地址，只做 UDP connect 选路，不会发送数据
+const PROBE_TARGET_V4: &str = "8.8.8.8:53";
+const PROBE_TARGET_V6: &str = "[2001:4860:4860::8888]:53";
+
+/// 网卡上的一个地址
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct InterfaceAddress {
+    address: String,
+    /// 前缀长度，由子网掩码计算，系统未返回掩码时为空
+    prefix: Option<u32>,
+    netmask: Option<String>,
+    broadcast: Option<String>,
+}
+
+/// 网卡信息
+#[derive(Serialize, Clone, Debug)]
+pub struct NetworkInterfaceInfo {
+    name: String,
+    index: u32,
+    mac: Option<String>,
+    /// 无法获取时为空（如 Windows）
+    mtu: Option<u32>,
+    /// 是否已启用且链路连通，无法获取时为空
+    up: Option<bool>,
+    loopback: bool,
+    ipv4: Vec<InterfaceAddress>,
+    ipv6: Vec<InterfaceAddress>,
+}
+
+/// 默认网关
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct DefaultGateway {
+    address: String,
+    interface: Option<String>,
+}
+
+/// 本机网络信息
+#[derive(Serialize, Clone, Debug)]
+pub struct NetworkInfo {
+    hostname: Option<String>,
+    interfaces: Vec<NetworkInterfaceInfo>,
+    gateways: Vec<DefaultGateway>,
+    dns_servers: Vec<String>,
+    /// 访问外网时使用的本机地址
+    primary_ipv4: Option<String>,
+    primary_ipv6: Option<String>,
+}
+
+/// 网卡的 MTU 与启用状态
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LinkState {
+    pub mtu: Option<u32>,
+    pub up: Option<bool>,
+}
+
+/// 获取本机网卡、默认网关、DNS 服务器与出口地址
+#[tauri::command]
+pub async fn get_network_info() -> Result<NetworkInfo, String> {
+    tokio::task::spawn_blocking(collect)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn collect() -> Result<NetworkInfo, String> {
+    let raw = NetworkInterface::show().map_err(|e| format!("读取网卡列表失败: {}", e))?;
+    let states = link_states();
+    let mut interfaces: Vec<NetworkInterfaceInfo> = Vec::new();
+    // 每个地址各占一项，按网卡名称合并
+    for item in raw {
+        let position = match interfaces.iter().position(|i| i.name == item.name) {
+            Some(position) => position,
+            None => {
+                let state = states.get(&item.name).copied().unwrap_or_default();
+                interfaces.push(NetworkInterfaceInfo {
+                    name: item.name.clone(),
+                    index: item.index,
+                    mac: None,
+                    mtu: state.mtu,
+                    up: state.up,
+                    loopback: item.internal,
+                    ipv4: Vec::new(),
+                    ipv6: Vec::new(),
+                });
+                interfaces.len() - 1
+            }
+        };
+        let interface = &mut interfaces[position];
+        if interface.mac.is_none() {
+            interface.mac = item.mac_addr.filter(|mac| !mac.is_empty());
+        }
+        for addr in item.addr {
+            let address = InterfaceAddress {
+                address: addr.ip().to_string(),
+                prefix: addr.netmask().map(|mask| prefix_len(&mask)),
+                netmask: addr.netmask().map(|mask| mask.to_string()),
+                broadcast: addr.broadcast().map(|b| b.to_string()),
+            };
+            let list = match addr {
+                Addr::V4(_) => &mut interface.ipv4,
+                Addr::V6(_) => &mut interface.ipv6,
+            };
+            if !list.contains(&address) {
+                list.push(address);
+            }
+        }
+    }
+    interfaces.sort_by_key(|i| i.index);
+
+    Ok(NetworkInfo {
+        hostname: hostname(),
+        interfaces,
+        gateways: default_gateways(),
+        // 读取失败时不影响其他信息
+        dns_servers: system_nameservers()
+            .unwrap_or_default()
+            .iter()
+            .map(IpAddr::to_string)
+            .collect(),
+        primary_ipv4: primary_address("0.0.0.0:0", PROBE_TARGET_V4),
+        primary_ipv6: primary_address("[::]:0", PROBE_TARGET_V6),
+    })
+}
+
+fn prefix_len(mask: &IpAddr) -> u32 {
+    match mask {
+        IpAddr::V4(mask) => mask.to_bits().count_ones(),
+        IpAddr::V6(mask) => mask.to_bits().count_ones(),
+    }
+}
+
+/// 通过 UDP connect 让系统选路，读取本端地址
+fn primary_address(bind: &str, target: &str) -> Option<String> {
+    let socket = UdpSocket::bind(bind).ok()?;
+    socket.connect(target).ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_unspecified()).then(|| ip.to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn hostname() -> Option<String> {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn hostname() -> Option<String> {
+    crate::utils::local_ports::run("hostname", &[])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn hostname() -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn link_states() -> HashMap<String, LinkState> {
+    const IFF_UP: u32 = 0x1;
+    let mut states = HashMap::new();
+    let Ok(entries) = std::fs::read_dir("/sys/class/net") else {
+        return states;
+    };
+    for entry in entries.flatten() {
+        let read = |file: &str| {
+            std::fs::read_to_string(entry.path().join(file))
+                .ok()
+                .map(|s| s.trim().to_string())
+        };
+        let admin_up = read("flags")
+            .and_then(|s| u32::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+            .map(|flags| flags & IFF_UP != 0);
+        // 回环等虚拟网卡不上报链路状态，operstate 为 unknown
+        let up = match (admin_up, read("operstate").as_deref()) {
+            (Some(admin_up), Some("up" | "unknown")) => Some(admin_up),
+            (Some(_), Some(_)) => Some(false),
+            _ => None,
+        };
+        states.insert(
+            entry.file_name().to_string_lossy().into_owned(),
+            LinkState {
+                mtu: read("mtu").and_then(|s| s.parse().ok()),
+                up,
+            },
+        );
+    }
+    states
+}
+
+#[cfg(target_os = "linux")]
+fn default_gateways() -> Vec<DefaultGateway> {
+    let mut gateways = std::fs::read_to_string("/proc/net/route")
+        .map(|content| parse_proc_route(&content))
+        .unwrap_or_default();
+    // 未启用 IPv6 时 ipv6_route 不存在
+    if let Ok(content) = std::fs::read_to_string("/proc/net/ipv6_route") {
+        gateways.extend(parse_proc_ipv6_route(&content));
+    }
+    gateways
+}
+
+#[cfg(target_os = "macos")]
+fn link_states() -> HashMap<String, LinkState> {
+    crate::utils::local_ports::run("ifconfig", &["-a"])
+        .map(|output| parse_ifconfig(&output))
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+fn default_gateways() -> Vec<DefaultGateway> {
+    crate::utils::local_ports::run("netstat", &["-rn"])
+        .map(|output| parse_netstat_routes(&output))
+        .unwrap_or_default()
+}
+
+/// Windows 下网卡名称为友好名称，与 ipconfig 的 friendly_name 一致
+#[cfg(target_os = "windows")]
+fn adapters() -> Vec<ipconfig::Adapter> {
+    ipconfig::get_adapters().unwrap_or_default()
+}
+
+#[cfg(target_os = "windows")]
+fn link_states() -> HashMap<String, LinkState> {
+    adapters()
+        .iter()
+        .map(|adapter| {
+            let up = adapter.oper_status() == ipconfig::OperStatus::IfOperStatusUp;
+            (
+                adapter.friendly_name().to_string(),
+                LinkState {
+                    mtu: None,
+                    up: Some(up),
+                },
+            )
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn default_gateways() -> Vec<DefaultGateway> {
+    adapters()
+        .iter()
+        .filter(|adapter| adapter.oper_status() == ipconfig::OperStatus::IfOperStatusUp)
+        .flat_map(|adapter| {
+            adapter.gateways().iter().map(|gateway| DefaultGateway {
+                address: gateway.to_string(),
+                interface: Some(adapter.friendly_name().to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn link_states() -> HashMap<String, LinkState> {
+    HashMap::new()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn default_gateways() -> Vec<DefaultGateway> {
+    Vec::new()
+}
+
+/// 解析 /proc/net/route 中的默认路由，网关按主机字节序（小端）存放
+pub fn parse_proc_route(content: &str) -> Vec<DefaultGateway> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 8 || fields[1] != "00000000" || fields[7] != "00000000" {
+                return None;
+            }
+            let gateway = u32::from_str_radix(fields[2], 16).ok()?;
+            if gateway == 0 {
+                return None;
+            }
+            Some(DefaultGateway {
+                address: Ipv4Addr::from(gateway.to_le_bytes()).to_string(),
+                interface: Some(fields[0].to_string()),
+            })
+        })
+        .collect()
+}
+
+/// 解析 /proc/net/ipv6_route 中的默认路由，地址按网络字节序存放
+pub fn parse_proc_ipv6_route(content: &str) -> Vec<DefaultGateway> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10
+                || u128::from_str_radix(fields[0], 16).ok()? != 0
+                || fields[1] != "00"
+            {
+                return None;
+            }
+            // 下一跳为空的是 lo 上的 unreachable 路由
+            let next_hop = u128::from_str_radix(fields[4], 16).ok()?;
+            if next_hop == 0 {
+                return None;
+            }
+            Some(DefaultGateway {
+                address: Ipv6Addr::from(next_hop).to_string(),
+                interface: Some(fields[9].to_string()),
+            })
+        })
+        .collect()
+}
+
+/// 解析 macOS `ifconfig -a` 输出中的 MTU 与状态
+pub fn parse_ifconfig(output: &str) -> HashMap<String, LinkState> {
+    let mut states = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            // en0: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500
+            let Some((name, rest)) = line.split_once(':') else {
+                current = None;
+                continue;
+            };
+            let flags = rest
+                .split_once('<')
+                .and_then(|(_, s)| s.split_once('>'))
+                .map(|(flags, _)| flags.split(',').collect::<Vec<_>>())
+                .unwrap_or_default();
+            let mtu = rest
+                .split_whitespace()
+                .skip_while(|s| *s != "mtu")
+                .nth(1)
+                .and_then(|s| s.parse().ok());
+            states.insert(
+                name.to_string(),
+                LinkState {
+                    mtu,
+                    up: Some(flags.contains(&"UP") && flags.contains(&"RUNNING")),
+                },
+            );
+            current = Some(name.to_string());
+        } else if let (Some(name), Some(status)) = (&current, line.trim().strip_prefix("status:")) {
+            // 有线网卡拔掉网线后仍带 RUNNING 标志，以 status 为准
+            if status.trim() != "active" {
+                if let Some(state) = states.get_mut(name) {
+                    state.up = Some(false);
+                }
+            }
+        }
+    }
+    states
+}
+
+/// 解析 macOS `netstat -rn` 输出中的默认路由
+pub fn parse_netstat_routes(output: &str) -> Vec<DefaultGateway> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 4 || fields[0] != "default" {
+                return None;
+            }
+            // 去掉 IPv6 链路本地地址的网卡后缀，如 fe80::1%en0；link#4 等非地址网关跳过
+            let address: IpAddr = fields[1].split('%').next()?.parse().ok()?;
+            Some(DefaultGateway {
+                address: address.to_string(),
+                interface: Some(fields[3].to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_routes() {
+        let route =
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+eth0\t00000000\t0101A8C0\t0003\t0\t0\t100\t00000000\t0\t0\t0\n\
+eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0\n";
+        assert_eq!(
+            parse_proc_route(route),
+            vec![DefaultGateway {
+                address: "192.168.1.1".to_string(),
+                interface: Some("eth0".to_string()),
+            }]
+        );
+
+        let ipv6_route = "fe800000000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000002 00000000 00000001     eth0\n\
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00000003     eth0\n\
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200       lo\n";
+        let gateways = parse_proc_ipv6_route(ipv6_route);
+        assert_eq!(gateways.len(), 1);
+        assert_eq!(gateways[0].address, "fe80::1");
+        assert_eq!(gateways[0].interface.as_deref(), Some("eth0"));
+    }
+
+    #[test]
+    fn test_parse_ifconfig_and_netstat() {
+        let ifconfig = "lo0: flags=8049<UP,LOOPBACK,RUNNING,MULTICAST> mtu 16384\n\
+\toptions=1203<RXCSUM,TXCSUM,TXSTATUS,SW_TIMESTAMP>\n\
+\tinet 127.0.0.1 netmask 0xff000000\n\
+en0: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500\n\
+\tether 3c:22:fb:00:00:01\n\
+\tstatus: active\n\
+en1: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500\n\
+\tstatus: inactive\n\
+gif0: flags=8010<POINTOPOINT,MULTICAST> mtu 1280\n";
+        let states = parse_ifconfig(ifconfig);
+        assert_eq!(
+            states["lo0"],
+            LinkState {
+                mtu: Some(16384),
+                up: Some(true)
+            }
+        );
+        assert_eq!(states["en0"].up, Some(true));
+        assert_eq!(states["en1"].up, Some(false));
+        assert_eq!(states["gif0"].mtu, Some(1280));
+        assert_eq!(states["gif0"].up, Some(false));
+
+        let netstat = "Routing tables\n\nInternet:\n\
+Destination        Gateway            Flags        Netif Expire\n\
+default            192.168.1.1        UGScg          en0\n\
+default            link#17            UCSIg    bridge100      !\n\
+127                127.0.0.1          UCS            lo0\n\n\
+Internet6:\n\
+Destination                             Gateway                                 Flags         Netif Expire\n\
+default                                 fe80::1%en0                             UGcg            en0\n";
+        let gateways = parse_netstat_routes(netstat);
+        assert_eq!(gateways.len(), 2);
+        assert_eq!(gateways[0].address, "192.168.1.1");
+        assert_eq!(gateways[1].address, "fe80::1");
+        assert_eq!(gateways[1].interface.as_deref(), Some("en0"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_get_network_info_finds_loopback() {
+        let info = get_network_info().await.unwrap();
+        let lo = info
+            .interfaces
+            .iter()
+            .find(|i| i.loopback)
+            .expect("loopback not found");
+        assert!(lo.ipv4.iter().any(|a| a.address == "127.0.0.1"));
+        assert_eq!(lo.ipv4[0].prefix, Some(8));
+        assert_eq!(lo.up, Some(true));
+        assert!(lo.mtu.is_some());
+    }
+}
@@ -0,0 +1,808 @@
+use crate::utils::id_generator::{from_hex, to_hex};
+use crate::utils::network_util::{open_stream, EventSink, ACCEPT_RETRY_DELAY};
+use crate::utils::udp_probe::recv_or_error;
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{lookup_host, TcpListener, TcpStream, UdpSocket};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::{JoinHandle, JoinSet};
+
+/// 默认连接超时时间（毫秒）
+const DEFAULT_NETCAT_TIMEOUT_MS: u64 = 5_000;
+/// 单次读取的最大字节数
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+/// 十六进制视图每行的字节数
+const HEX_DUMP_WIDTH: usize = 16;
+
+/// 会话模式
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NetcatMode {
+    /// 连接到远程 TCP 端口
+    TcpClient,
+    /// 向远程 UDP 端口收发数据
+    UdpClient,
+    /// 监听本地 TCP 端口，可接受多个客户端
+    TcpListen,
+    /// 监听本地 UDP 端口
+    UdpListen,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct NetcatRequest {
+    pub mode: NetcatMode,
+    /// 客户端模式为远程主机，监听模式为绑定地址（默认 0.0.0.0）
+    pub host: Option<String>,
+    pub port: u16,
+    /// 连接超时时间（毫秒），默认 5000
+    pub timeout_ms: Option<u64>,
+    /// 代理地址，仅 TCP 客户端模式有效，为空时使用全局代理，`direct` 表示直接连接
+    pub proxy: Option<String>,
+}
+
+/// 发送内容的编码
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadEncoding {
+    #[default]
+    Text,
+    /// 十六进制，允许空格分隔
+    Hex,
+}
+
+/// 文本内容末尾追加的换行符
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LineEnding {
+    #[default]
+    None,
+    Lf,
+    Crlf,
+    Cr,
+}
+
+impl LineEnding {
+    fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::None => "",
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct NetcatSessionInfo {
+    id: String,
+    mode: NetcatMode,
+    local_addr: String,
+    /// 客户端模式下的远程地址
+    remote_addr: Option<String>,
+    create_time: String,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NetcatEventKind {
+    /// 客户端连接到监听端口
+    Connected,
+    /// 收到数据
+    Received,
+    /// 已发送数据
+    Sent,
+    /// 对端断开连接
+    Disconnected,
+    Error,
+    /// 会话已关闭
+    Closed,
+}
+
+/// 通过 `netcat_event` 事件发送给前端
+#[derive(Serialize, Clone, Debug)]
+pub struct NetcatEvent {
+    session_id: String,
+    kind: NetcatEventKind,
+    peer: Option<String>,
+    size: usize,
+    /// 十六进制字符串，如 `48656c6c6f`
+    hex: Option<String>,
+    /// 不可打印字符替换为 `.`
+    ascii: Option<String>,
+    /// 十六进制与 ASCII 对照视图
+    hex_dump: Option<String>,
+    message: Option<String>,
+    /// 格式 "YYYY-MM-DD HH:mm:ss.SSS"
+    time: String,
+}
+
+impl NetcatEvent {
+    fn new(session_id: &str, kind: NetcatEventKind, peer: Option<SocketAddr>) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            kind,
+            peer: peer.map(|p| p.to_string()),
+            size: 0,
+            hex: None,
+            ascii: None,
+            hex_dump: None,
+            message: None,
+            time: Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+        }
+    }
+
+    fn with_data(mut self, data: &[u8]) -> Self {
+        self.size = data.len();
+        self.hex = Some(to_hex(data));
+        self.ascii = Some(ascii(data));
+        self.hex_dump = Some(hex_dump(data));
+        self
+    }
+
+    fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+enum SessionCommand {
+    Send {
+        data: Vec<u8>,
+        peer: Option<SocketAddr>,
+        reply: oneshot::Sender<Result<usize, String>>,
+    },
+}
+
+struct NetcatSession {
+    info: NetcatSessionInfo,
+    commands: mpsc::UnboundedSender<SessionCommand>,
+    handle: JoinHandle<()>,
+}
+
+/// 全局会话池，Key 是会话 ID
+static SESSION_POOL: Lazy<Mutex<HashMap<String, NetcatSession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 打开 TCP/UDP 会话或监听本地端口，收发的数据通过 `netcat_event` 事件通知前端
+#[tauri::command]
+pub async fn open_netcat_session(
+    app_handle: AppHandle,
+    request: NetcatRequest,
+) -> Result<NetcatSessionInfo, String> {
+    let sink: EventSink<NetcatEvent> = Arc::new(move |event| {
+        if let Err(e) = app_handle.emit_to("main", "netcat_event", event) {
+            eprintln!("发送 netcat 事件失败: {}", e);
+        }
+    });
+    open_session(request, sink).await
+}
+
+/// 发送数据
+/// - peer: 监听模式下的目标客户端地址，为空时 TCP 发送给所有客户端，UDP 发送给最近的对端
+#[tauri::command]
+pub async fn send_netcat_data(
+    id: String,
+    payload: String,
+    encoding: Option<PayloadEncoding>,
+    line_ending: Option<LineEnding>,
+    peer: Option<String>,
+) -> Result<usize, String> {
+    let data = encode_payload(
+        &payload,
+        encoding.unwrap_or_default(),
+        line_ending.unwrap_or_default(),
+    )?;
+    let peer = match peer.as_deref().map(str::trim) {
+        Some(peer) if !peer.is_empty() => Some(
+            peer.parse::<SocketAddr>()
+                .map_err(|_| format!("无效的对端地址: {}", peer))?,
+        ),
+        _ => None,
+    };
+    let (reply, result) = oneshot::channel();
+    {
+        let pool = SESSION_POOL.lock().unwrap();
+        let session = pool.get(&id).ok_or_else(|| format!("未找到会话: {}", id))?;
+        session
+            .commands
+            .send(SessionCommand::Send { data, peer, reply })
+            .map_err(|_| "会话已关闭".to_string())?;
+    }
+    result.await.map_err(|_| "会话已关闭".to_string())?
+}
+
+/// 关闭会话
+#[tauri::command]
+pub fn close_netcat_session(app_handle: AppHandle, id: String) -> Result<String, String> {
+    close_session(&id)?;
+    let event = NetcatEvent::new(&id, NetcatEventKind::Closed, None);
+    let _ = app_handle.emit_to("main", "netcat_event", event);
+    Ok(format!("已关闭会话: {}", id))
+}
+
+/// 当前打开的会话
+#[tauri::command]
+pub fn list_netcat_sessions() -> Vec<NetcatSessionInfo> {
+    let mut sessions: Vec<NetcatSessionInfo> = SESSION_POOL
+        .lock()
+        .unwrap()
+        .values()
+        .map(|s| s.info.clone())
+        .collect();
+    sessions.sort_by(|a, b| a.create_time.cmp(&b.create_time));
+    sessions
+}
+
+fn close_session(id: &str) -> Result<(), String> {
+    let session = SESSION_POOL
+        .lock()
+        .unwrap()
+        .remove(id)
+        .ok_or_else(|| format!("未找到会话: {}", id))?;
+    session.handle.abort();
+    Ok(())
+}
+
+/// 打开会话并加入会话池
+async fn open_session(
+    request: NetcatRequest,
+    sink: EventSink<NetcatEvent>,
+) -> Result<NetcatSessionInfo, String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let timeout = Duration::from_millis(
+        request
+            .timeout_ms
+            .unwrap_or(DEFAULT_NETCAT_TIMEOUT_MS)
+            .max(1),
+    );
+    let host = request
+        .host
+        .as_deref()
+        .map(str::trim)
+        .filter(|h| !h.is_empty());
+    let (commands, rx) = mpsc::unbounded_channel();
+
+    let (socket, local_addr, remote_addr) = match request.mode {
+        NetcatMode::TcpClient => {
+            let host = host.ok_or("请输入远程主机")?;
+            // 经代理连接时对端地址为代理服务器
+            let stream = open_stream(host, request.port, timeout, request.proxy.as_deref())
+                .await
+                .map_err(|e| format!("连接 {}:{} 失败: {}", host, request.port, e))?;
+            let local = stream.local_addr().map_err(|e| e.to_string())?;
+            let remote = stream.peer_addr().ok();
+            (Socket::Tcp(stream), local, remote)
+        }
+        NetcatMode::UdpClient => {
+            let host = host.ok_or("请输入远程主机")?;
+            let addr = resolve(host, request.port, timeout).await?;
+            let bind: SocketAddr = if addr.is_ipv4() {
+                "0.0.0.0:0".parse().unwrap()
+            } else {
+                "[::]:0".parse().unwrap()
+            };
+            let socket = UdpSocket::bind(bind).await.map_err(|e| e.to_string())?;
+            socket.connect(addr).await.map_err(|e| e.to_string())?;
+            let local = socket.local_addr().map_err(|e| e.to_string())?;
+            (Socket::UdpConnected(socket), local, Some(addr))
+        }
+        NetcatMode::TcpListen | NetcatMode::UdpListen => {
+            let bind = resolve(host.unwrap_or("0.0.0.0"), request.port, timeout).await?;
+            if request.mode == NetcatMode::TcpListen {
+                let listener = TcpListener::bind(bind)
+                    .await
+                    .map_err(|e| format!("监听 {} 失败: {}", bind, e))?;
+                let local = listener.local_addr().map_err(|e| e.to_string())?;
+                (Socket::TcpListener(listener), local, None)
+            } else {
+                let socket = UdpSocket::bind(bind)
+                    .await
+                    .map_err(|e| format!("监听 {} 失败: {}", bind, e))?;
+                let local = socket.local_addr().map_err(|e| e.to_string())?;
+                (Socket::UdpListener(socket), local, None)
+            }
+        }
+    };
+
+    let info = NetcatSessionInfo {
+        id: id.clone(),
+        mode: request.mode,
+        local_addr: local_addr.to_string(),
+        remote_addr: remote_addr.map(|a| a.to_string()),
+        create_time: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+    // 会话先加入会话池再启动，避免任务立即结束时找不到会话
+    let mut pool = SESSION_POOL.lock().unwrap();
+    let handle = tokio::spawn(run_session(id.clone(), socket, rx, sink));
+    pool.insert(
+        id,
+        NetcatSession {
+            info: info.clone(),
+            commands,
+            handle,
+        },
+    );
+    Ok(info)
+}
+
+async fn resolve(host: &str, port: u16, timeout: Duration) -> Result<SocketAddr, String> {
+    tokio::time::timeout(timeout, lookup_host((host.trim_matches(['[', ']']), port)))
+        .await
+        .map_err(|_| "域名解析超时".to_string())?
+        .map_err(|e| format!("域名解析失败: {}", e))?
+        .next()
+        .ok_or_else(|| "未解析到任何地址".to_string())
+}
+
+enum Socket {
+    Tcp(TcpStream),
+    UdpConnected(UdpSocket),
+    TcpListener(TcpListener),
+    UdpListener(UdpSocket),
+}
+
+async fn run_session(
+    id: String,
+    socket: Socket,
+    rx: mpsc::UnboundedReceiver<SessionCommand>,
+    sink: EventSink<NetcatEvent>,
+) {
+    match socket {
+        Socket::Tcp(stream) => {
+            let peer = stream.peer_addr().ok();
+            run_tcp(
+                &id,
+                None,
+                peer.into_iter().zip([stream]).collect(),
+                rx,
+                &sink,
+            )
+            .await
+        }
+        Socket::TcpListener(listener) => run_tcp(&id, Some(listener), Vec::new(), rx, &sink).await,
+        Socket::UdpConnected(socket) => run_udp_connected(&id, socket, rx, &sink).await,
+        Socket::UdpListener(socket) => run_udp_listener(&id, socket, rx, &sink).await,
+    }
+    // 会话自然结束（如对端断开）时从会话池移除
+    if SESSION_POOL.lock().unwrap().remove(&id).is_some() {
+        sink(NetcatEvent::new(&id, NetcatEventKind::Closed, None));
+    }
+}
+
+/// TCP 客户端与监听共用，客户端模式下连接断开即结束会话
+async fn run_tcp(
+    id: &str,
+    listener: Option<TcpListener>,
+    streams: Vec<(SocketAddr, TcpStream)>,
+    mut rx: mpsc::UnboundedReceiver<SessionCommand>,
+    sink: &EventSink<NetcatEvent>,
+) {
+    let mut writers: HashMap<SocketAddr, OwnedWriteHalf> = HashMap::new();
+    let (closed_tx, mut closed_rx) = mpsc::unbounded_channel();
+    // 会话任务被取消时 JoinSet 会一并取消读取任务
+    let mut readers = JoinSet::new();
+    let mut add_stream = |peer: SocketAddr, stream: TcpStream, writers: &mut HashMap<_, _>| {
+        let (reader, writer) = stream.into_split();
+        writers.insert(peer, writer);
+        readers.spawn(read_tcp(
+            id.to_string(),
+            peer,
+            reader,
+            sink.clone(),
+            closed_tx.clone(),
+        ));
+    };
+    for (peer, stream) in streams {
+        add_stream(peer, stream, &mut writers);
+    }
+
+    let mut accept_failed = false;
+    loop {
+        tokio::select! {
+            accepted = accept(listener.as_ref(), accept_failed) => match accepted {
+                Ok((stream, peer)) => {
+                    accept_failed = false;
+                    sink(NetcatEvent::new(id, NetcatEventKind::Connected, Some(peer)));
+                    add_stream(peer, stream, &mut writers);
+                }
+                Err(e) => {
+                    accept_failed = true;
+                    sink(NetcatEvent::new(id, NetcatEventKind::Error, None).with_message(e.to_string()));
+                }
+            },
+            Some(peer) = closed_rx.recv() => {
+                writers.remove(&peer);
+                if listener.is_none() {
+                    break;
+                }
+            }
+            command = rx.recv() => {
+                let Some(SessionCommand::Send { data, peer, reply }) = command else {
+                    break;
+                };
+                let targets: Vec<SocketAddr> = match peer {
+                    Some(peer) => vec![peer],
+                    None => writers.keys().copied().collect(),
+                };
+                if targets.is_empty() {
+                    let _ = reply.send(Err("没有已连接的客户端".to_string()));
+                    continue;
+                }
+                let mut result = Ok(0);
+                for target in targets {
+                    let Some(writer) = writers.get_mut(&target) else {
+                        result = Err(format!("客户端 {} 未连接", target));
+                        continue;
+                    };
+                    match writer.write_all(&data).await {
+                        Ok(()) => {
+                            sink(NetcatEvent::new(id, NetcatEventKind::Sent, Some(target)).with_data(&data));
+                            result = result.map(|n| n + data.len());
+                        }
+                        Err(e) => result = Err(format!("发送到 {} 失败: {}", target, e)),
+                    }
+                }
+                let _ = reply.send(result);
+            }
+        }
+    }
+}
+
+/// 客户端模式没有监听端口，永远不会返回
+/// 上次接受连接失败时先等待 [`ACCEPT_RETRY_DELAY`]，避免持续出错时空转
+async fn accept(
+    listener: Option<&TcpListener>,
+    retry: bool,
+) -> std::io::Result<(TcpStream, SocketAddr)> {
+    match listener {
+        Some(listener) => {
+            if retry {
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+            }
+            listener.accept().await
+        }
+        None => std::future::pending().await,
+    }
+}
+
+async fn read_tcp(
+    id: String,
+    peer: SocketAddr,
+    mut reader: OwnedReadHalf,
+    sink: EventSink<NetcatEvent>,
+    closed: mpsc::UnboundedSender<SocketAddr>,
+) {
+    let mut buf = vec![0u8; READ_BUFFER_SIZE];
+    loop {
+        match reader.read(&mut buf).await {
+            Ok(0) => {
+                sink(NetcatEvent::new(
+                    &id,
+                    NetcatEventKind::Disconnected,
+                    Some(peer),
+                ));
+                break;
+            }
+            Ok(n) => sink(
+                NetcatEvent::new(&id, NetcatEventKind::Received, Some(peer)).with_data(&buf[..n]),
+            ),
+            Err(e) => {
+                sink(
+                    NetcatEvent::new(&id, NetcatEventKind::Disconnected, Some(peer))
+                        .with_message(e.to_string()),
+                );
+                break;
+            }
+        }
+    }
+    let _ = closed.send(peer);
+}
+
+async fn run_udp_connected(
+    id: &str,
+    socket: UdpSocket,
+    mut rx: mpsc::UnboundedReceiver<SessionCommand>,
+    sink: &EventSink<NetcatEvent>,
+) {
+    let peer = socket.peer_addr().ok();
+    let mut buf = vec![0u8; READ_BUFFER_SIZE];
+    loop {
+        tokio::select! {
+            received = recv_or_error(&socket, &mut buf) => match received {
+                Ok(n) => sink(NetcatEvent::new(id, NetcatEventKind::Received, peer).with_data(&buf[..n])),
+                // ICMP 端口不可达等错误不结束会话
+                Err(e) => sink(NetcatEvent::new(id, NetcatEventKind::Error, peer).with_message(e.to_string())),
+            },
+            command = rx.recv() => {
+                let Some(SessionCommand::Send { data, reply, .. }) = command else {
+                    break;
+                };
+                let result = match socket.send(&data).await {
+                    Ok(n) => {
+                        sink(NetcatEvent::new(id, NetcatEventKind::Sent, peer).with_data(&data));
+                        Ok(n)
+                    }
+                    Err(e) => Err(format!("发送失败: {}", e)),
+                };
+                let _ = reply.send(result);
+            }
+        }
+    }
+}
+
+async fn run_udp_listener(
+    id: &str,
+    socket: UdpSocket,
+    mut rx: mpsc::UnboundedReceiver<SessionCommand>,
+    sink: &EventSink<NetcatEvent>,
+) {
+    let mut last_peer = None;
+    let mut buf = vec![0u8; READ_BUFFER_SIZE];
+    loop {
+        tokio::select! {
+            received = socket.recv_from(&mut buf) => match received {
+                Ok((n, peer)) => {
+                    last_peer = Some(peer);
+                    sink(NetcatEvent::new(id, NetcatEventKind::Received, Some(peer)).with_data(&buf[..n]));
+                }
+                Err(e) => sink(NetcatEvent::new(id, NetcatEventKind::Error, None).with_message(e.to_string())),
+            },
+            command = rx.recv() => {
+                let Some(SessionCommand::Send { data, peer, reply }) = command else {
+                    break;
+                };
+                let result = match peer.or(last_peer) {
+                    Some(peer) => match socket.send_to(&data, peer).await {
+                        Ok(n) => {
+                            sink(NetcatEvent::new(id, NetcatEventKind::Sent, Some(peer)).with_data(&data));
+                            Ok(n)
+                        }
+                        Err(e) => Err(format!("发送到 {} 失败: {}", peer, e)),
+                    },
+                    None => Err("还没有收到任何对端的数据，请指定对端地址".to_string()),
+                };
+                let _ = reply.send(result);
+            }
+        }
+    }
+}
+
+/// 把文本或十六进制内容转换为待发送的字节
+pub fn encode_payload(
+    payload: &str,
+    encoding: PayloadEncoding,
+    line_ending: LineEnding,
+) -> Result<Vec<u8>, String> {
+    match encoding {
+        PayloadEncoding::Text => Ok(format!("{}{}", payload, line_ending.as_str()).into_bytes()),
+        PayloadEncoding::Hex => {
+            let hex: String = payload
+                .split_whitespace()
+                .map(|s| s.trim_start_matches("0x"))
+                .collect();
+            from_hex(&hex).ok_or_else(|| "无效的十六进制内容".to_string())
+        }
+    }
+}
+
+/// 可打印 ASCII 字符原样保留，其余替换为 `.`
+fn ascii(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| {
+            if b.is_ascii_graphic() || *b == b' ' {
+                *b as char
+            } else {
+                '.'
+            }
+        })
+        .collect()
+}
+
+/// 生成 `00000000  48 65 6c 6c 6f  |Hello|` 形式的十六进制视图
+pub fn hex_dump(data: &[u8]) -> String {
+    data.chunks(HEX_DUMP_WIDTH)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut hex = String::with_capacity(HEX_DUMP_WIDTH * 3 + 1);
+            for (j, b) in chunk.iter().enumerate() {
+                if j == HEX_DUMP_WIDTH / 2 {
+                    hex.push(' ');
+                }
+                hex.push_str(&format!("{:02x} ", b));
+            }
+            format!(
+                "{:08x}  {:<width$} |{}|",
+                i * HEX_DUMP_WIDTH,
+                hex,
+                ascii(chunk),
+                width = HEX_DUMP_WIDTH * 3 + 1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::network_util::event_sink::{channel_sink, next_event};
+
+    async fn send(
+        id: &str,
+        payload: &str,
+        encoding: PayloadEncoding,
+        peer: Option<String>,
+    ) -> Result<usize, String> {
+        send_netcat_data(
+            id.to_string(),
+            payload.to_string(),
+            Some(encoding),
+            Some(LineEnding::Crlf),
+            peer,
+        )
+        .await
+    }
+
+    #[test]
+    fn test_encode_and_hex_dump() {
+        assert_eq!(
+            encode_payload("hi", PayloadEncoding::Text, LineEnding::Crlf).unwrap(),
+            b"hi\r\n"
+        );
+        assert_eq!(
+            encode_payload("0x48 65\n6c", PayloadEncoding::Hex, LineEnding::Lf).unwrap(),
+            b"Hel"
+        );
+        assert!(encode_payload("4", PayloadEncoding::Hex, LineEnding::None).is_err());
+
+        let dump = hex_dump(b"Hello, world!\x00\x01\x02\xffXYZ");
+        assert_eq!(
+            dump,
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 00 01 02  |Hello, world!...|\n\
+             00000010  ff 58 59 5a                                       |.XYZ|"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tcp_listen_and_client() {
+        let (sink, mut events) = channel_sink();
+        let server = open_session(
+            NetcatRequest {
+                mode: NetcatMode::TcpListen,
+                host: Some("127.0.0.1".to_string()),
+                port: 0,
+                timeout_ms: None,
+                proxy: None,
+            },
+            sink.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(send(&server.id, "x", PayloadEncoding::Text, None)
+            .await
+            .unwrap_err()
+            .contains("没有已连接"));
+
+        let port: u16 = server
+            .local_addr
+            .rsplit(':')
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let client = open_session(
+            NetcatRequest {
+                mode: NetcatMode::TcpClient,
+                host: Some("localhost".to_string()),
+                port,
+                timeout_ms: Some(2_000),
+                proxy: None,
+            },
+            sink,
+        )
+        .await
+        .unwrap();
+        let connected = next_event(&mut events, |e| e.kind == NetcatEventKind::Connected).await;
+        assert_eq!(connected.session_id, server.id);
+
+        assert_eq!(
+            send(&client.id, "ping", PayloadEncoding::Text, None)
+                .await
+                .unwrap(),
+            6
+        );
+        let received = next_event(&mut events, |e| e.kind == NetcatEventKind::Received).await;
+        assert_eq!(received.session_id, server.id);
+        assert_eq!(received.ascii.as_deref(), Some("ping.."));
+        assert_eq!(received.peer, connected.peer);
+
+        assert_eq!(
+            send(
+                &server.id,
+                "ff 00",
+                PayloadEncoding::Hex,
+                connected.peer.clone()
+            )
+            .await
+            .unwrap(),
+            2
+        );
+        let received = next_event(&mut events, |e| e.kind == NetcatEventKind::Received).await;
+        assert_eq!(received.session_id, client.id);
+        assert_eq!(received.hex.as_deref(), Some("ff00"));
+
+        // 关闭监听端后客户端会话随连接断开自动结束
+        close_session(&server.id).unwrap();
+        let closed = next_event(&mut events, |e| e.kind == NetcatEventKind::Closed).await;
+        assert_eq!(closed.session_id, client.id);
+        assert!(list_netcat_sessions()
+            .iter()
+            .all(|s| s.id != client.id && s.id != server.id));
+        assert!(close_session(&client.id).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_udp_listen_and_client() {
+        let (sink, mut events) = channel_sink();
+        let server = open_session(
+            NetcatRequest {
+                mode: NetcatMode::UdpListen,
+                host: Some("127.0.0.1".to_string()),
+                port: 0,
+                timeout_ms: None,
+                proxy: None,
+            },
+            sink.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(send(&server.id, "x", PayloadEncoding::Text, None)
+            .await
+            .is_err());
+
+        let port: u16 = server
+            .local_addr
+            .rsplit(':')
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let client = open_session(
+            NetcatRequest {
+                mode: NetcatMode::UdpClient,
+                host: Some("127.0.0.1".to_string()),
+                port,
+                timeout_ms: None,
+                proxy: None,
+            },
+            sink,
+        )
+        .await
+        .unwrap();
+        send(&client.id, "hello", PayloadEncoding::Text, None)
+            .await
+            .unwrap();
+        let received = next_event(&mut events, |e| e.kind == NetcatEventKind::Received).await;
+        assert_eq!(received.session_id, server.id);
+        assert_eq!(received.peer.as_deref(), Some(client.local_addr.as_str()));
+
+        // 未指定对端时回复最近的对端
+        send(&server.id, "world", PayloadEncoding::Text, None)
+            .await
+            .unwrap();
+        let received = next_event(&mut events, |e| e.kind == NetcatEventKind::Received).await;
+        assert_eq!(received.session_id, client.id);
+        assert_eq!(received.ascii.as_deref(), Some("world.."));
+
+        close_session(&server.id).unwrap();
+        close_session(&client.id).unwrap();
+    }
+}
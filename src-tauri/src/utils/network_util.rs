@@ -1,84 +1,590 @@
+use crate::utils::proxy;
+use futures_util::future::BoxFuture;
+use once_cell::sync::Lazy;
 use serde::Serialize;
-use std::time::Duration;
-use tokio::net::TcpStream;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::{lookup_host, TcpStream};
+use tokio::task::JoinHandle;
 
-#[derive(Serialize, Clone)]
+/// 默认连接超时时间（毫秒）
+pub const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 5_000;
+/// 允许设置的最大连接超时时间（毫秒）
+pub(crate) const MAX_CONNECT_TIMEOUT_MS: u64 = 60_000;
+/// 监听端口接受连接失败（如文件描述符耗尽）后，重试前等待的时间
+pub(crate) const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// 域名解析与建立 TCP 连接的方式，测试时替换为本地的模拟实现
+pub trait Connector: Send + Sync {
+    fn resolve<'a>(
+        &'a self,
+        host: &'a str,
+        port: u16,
+    ) -> BoxFuture<'a, io::Result<Vec<SocketAddr>>>;
+
+    fn connect(&self, addr: SocketAddr) -> BoxFuture<'_, io::Result<TcpStream>>;
+}
+
+/// 使用系统的域名解析与网络
+pub struct SystemConnector;
+
+impl Connector for SystemConnector {
+    fn resolve<'a>(
+        &'a self,
+        host: &'a str,
+        port: u16,
+    ) -> BoxFuture<'a, io::Result<Vec<SocketAddr>>> {
+        Box::pin(async move {
+            Ok(lookup_host((host.trim_matches(['[', ']']), port))
+                .await?
+                .collect())
+        })
+    }
+
+    fn connect(&self, addr: SocketAddr) -> BoxFuture<'_, io::Result<TcpStream>> {
+        Box::pin(TcpStream::connect(addr))
+    }
+}
+
+static SYSTEM_CONNECTOR: Lazy<Arc<dyn Connector>> = Lazy::new(|| Arc::new(SystemConnector));
+
+tokio::task_local! {
+    static CONNECTOR: Arc<dyn Connector>;
+}
+
+/// 当前任务使用的连接方式，未通过 [`with_connector`] 指定时为 [`SystemConnector`]
+pub fn connector() -> Arc<dyn Connector> {
+    CONNECTOR
+        .try_with(Arc::clone)
+        .unwrap_or_else(|_| SYSTEM_CONNECTOR.clone())
+}
+
+/// 在指定的连接方式下执行，期间的直接连接都经过 connector
+pub async fn with_connector<F: Future>(connector: Arc<dyn Connector>, future: F) -> F::Output {
+    CONNECTOR.scope(connector, future).await
+}
+
+/// 与 `tokio::spawn` 相同，新任务沿用当前任务的连接方式
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    tokio::spawn(with_connector(connector(), future))
+}
+
+/// 长时间运行的会话向前端推送事件的回调
+pub(crate) type EventSink<E> = Arc<dyn Fn(E) + Send + Sync>;
+
+/// 端口检测状态
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PortStatus {
+    /// 连接成功
+    Open,
+    /// 连接被拒绝（端口关闭）
+    Refused,
+    /// 连接超时（可能被防火墙丢弃）
+    Timeout,
+    /// 主机或网络不可达
+    Unreachable,
+    /// 域名解析失败
+    DnsFailure,
+    /// 没有权限（如被本机防火墙策略拦截）
+    PermissionDenied,
+    /// 其他错误
+    Error,
+}
+
+impl PortStatus {
+    /// 根据连接错误归类状态
+    pub fn from_io_error(e: &io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::ConnectionRefused => PortStatus::Refused,
+            io::ErrorKind::TimedOut => PortStatus::Timeout,
+            io::ErrorKind::HostUnreachable | io::ErrorKind::NetworkUnreachable => {
+                PortStatus::Unreachable
+            }
+            io::ErrorKind::PermissionDenied => PortStatus::PermissionDenied,
+            _ => PortStatus::Error,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
 pub struct CheckPortResult {
-    result: bool,
-    reason: String,
+    pub(crate) status: PortStatus,
+    /// 最后一次连接尝试的耗时（毫秒），域名解析失败时为空
+    pub(crate) latency_ms: Option<f64>,
+    /// 实际尝试连接的 IP 地址，经代理连接时为代理服务器的地址
+    pub(crate) ip: Option<String>,
+    /// 使用的代理，直接连接时为空
+    pub(crate) proxy: Option<String>,
+    /// 系统错误码
+    pub(crate) os_error: Option<i32>,
+    /// 错误信息
+    pub(crate) message: Option<String>,
 }
 
 impl CheckPortResult {
-    pub fn new(result: bool, reason: &str) -> Self {
+    pub fn new(status: PortStatus, latency: Option<Duration>, addr: Option<SocketAddr>) -> Self {
         Self {
-            result,
-            reason: reason.to_string(),
+            status,
+            latency_ms: latency.map(|d| d.as_secs_f64() * 1000.0),
+            ip: addr.map(|a| a.ip().to_string()),
+            proxy: None,
+            os_error: None,
+            message: None,
         }
     }
+
+    fn with_error(mut self, e: &io::Error) -> Self {
+        self.os_error = e.raw_os_error();
+        self.message = Some(e.to_string());
+        self
+    }
+
+    pub(crate) fn with_message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.status == PortStatus::Open
+    }
 }
 
+/// 检测端口是否开放
+/// - timeout_ms: 超时时间（毫秒），包含域名解析时间，默认 5000
+/// - proxy: 代理地址，为空时使用全局代理，`direct` 表示直接连接
 #[tauri::command]
-pub async fn is_port_open(host: String, port: u16) -> Result<CheckPortResult, String> {
-    let address = format!("{}:{}", host, port);
-    let connection_attempt = TcpStream::connect(&address);
+pub async fn is_port_open(
+    host: String,
+    port: u16,
+    timeout_ms: Option<u64>,
+    proxy: Option<String>,
+) -> Result<CheckPortResult, String> {
+    let timeout_ms = timeout_ms.unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS);
+    if timeout_ms == 0 || timeout_ms > MAX_CONNECT_TIMEOUT_MS {
+        return Err(format!(
+            "超时时间应在 1-{} 毫秒之间",
+            MAX_CONNECT_TIMEOUT_MS
+        ));
+    }
+    let timeout = Duration::from_millis(timeout_ms);
+    Ok(connect_with_proxy(&host, port, timeout, proxy.as_deref())
+        .await
+        .0)
+}
+
+/// 在指定超时时间内尝试建立 TCP 连接
+///
+/// 域名解析出多个地址时依次尝试，直到连接成功或超时，结果中记录最后尝试的地址。
+pub async fn check_port(host: &str, port: u16, timeout: Duration) -> CheckPortResult {
+    connect(host, port, timeout).await.0
+}
 
-    match tokio::time::timeout(Duration::from_secs(5), connection_attempt).await {
-        Ok(Ok(_)) => Ok(CheckPortResult::new(true, "端口开放")),
-        Ok(Err(_)) => Ok(CheckPortResult::new(false, "端口关闭或被拒绝")),
-        Err(_) => Err("连接超时".to_string()),
+/// 与 [`check_port`] 相同，连接成功时同时返回建立的连接
+pub async fn connect(
+    host: &str,
+    port: u16,
+    timeout: Duration,
+) -> (CheckPortResult, Option<TcpStream>) {
+    connect_with_proxy(host, port, timeout, None).await
+}
+
+/// 按单次请求的代理设置连接，为空时使用全局代理，见 [`proxy::resolve`]
+pub async fn connect_with_proxy(
+    host: &str,
+    port: u16,
+    timeout: Duration,
+    proxy: Option<&str>,
+) -> (CheckPortResult, Option<TcpStream>) {
+    match proxy::resolve(proxy, host) {
+        Ok(Some(proxy)) => proxy::connect_via(&proxy, host, port, timeout).await,
+        Ok(None) => connect_direct(host, port, timeout).await,
+        Err(e) => (
+            CheckPortResult::new(PortStatus::Error, None, None).with_message(&e),
+            None,
+        ),
+    }
+}
+
+/// 与 [`connect_with_proxy`] 相同，失败时只返回错误信息
+pub async fn open_stream(
+    host: &str,
+    port: u16,
+    timeout: Duration,
+    proxy: Option<&str>,
+) -> Result<TcpStream, String> {
+    let (check, stream) = connect_with_proxy(host, port, timeout, proxy).await;
+    stream.ok_or_else(|| check.message.unwrap_or_else(|| "连接失败".to_string()))
+}
+
+/// 不经过代理直接连接，域名解析与连接都经过当前任务的 [`Connector`]
+pub async fn connect_direct(
+    host: &str,
+    port: u16,
+    timeout: Duration,
+) -> (CheckPortResult, Option<TcpStream>) {
+    let deadline = Instant::now() + timeout;
+    let connector = connector();
+
+    let addrs: Vec<SocketAddr> =
+        match tokio::time::timeout(timeout, connector.resolve(host, port)).await {
+            Ok(Ok(addrs)) => addrs,
+            Ok(Err(e)) => {
+                return (
+                    CheckPortResult::new(PortStatus::DnsFailure, None, None).with_error(&e),
+                    None,
+                )
+            }
+            Err(_) => {
+                return (
+                    CheckPortResult::new(PortStatus::DnsFailure, None, None)
+                        .with_message("域名解析超时"),
+                    None,
+                )
+            }
+        };
+    if addrs.is_empty() {
+        return (
+            CheckPortResult::new(PortStatus::DnsFailure, None, None)
+                .with_message("未解析到任何地址"),
+            None,
+        );
+    }
+
+    let mut result = None;
+    for addr in addrs {
+        let start = Instant::now();
+        let remaining = deadline.saturating_duration_since(start);
+        if remaining.is_zero() {
+            break;
+        }
+        let attempt = match tokio::time::timeout(remaining, connector.connect(addr)).await {
+            Ok(Ok(stream)) => {
+                return (
+                    CheckPortResult::new(PortStatus::Open, Some(start.elapsed()), Some(addr)),
+                    Some(stream),
+                )
+            }
+            Ok(Err(e)) => CheckPortResult::new(
+                PortStatus::from_io_error(&e),
+                Some(start.elapsed()),
+                Some(addr),
+            )
+            .with_error(&e),
+            Err(_) => CheckPortResult::new(PortStatus::Timeout, Some(start.elapsed()), Some(addr))
+                .with_message("连接超时"),
+        };
+        result = Some(attempt);
+    }
+    let result = result.unwrap_or_else(|| {
+        CheckPortResult::new(PortStatus::Timeout, None, None).with_message("连接超时")
+    });
+    (result, None)
+}
+
+/// 测试用的模拟网络：按地址接受、拒绝或丢弃连接，不依赖外部网络
+#[cfg(test)]
+pub(crate) mod stand_in {
+    use super::Connector;
+    use futures_util::future::BoxFuture;
+    use std::collections::HashMap;
+    use std::io;
+    use std::net::{IpAddr, SocketAddr};
+    use std::sync::Arc;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::{TcpListener, TcpStream};
+
+    #[cfg(target_os = "windows")]
+    const CONNECTION_REFUSED: i32 = 10061;
+    #[cfg(target_os = "windows")]
+    const HOST_UNREACHABLE: i32 = 10065;
+    #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+    const CONNECTION_REFUSED: i32 = 61;
+    #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+    const HOST_UNREACHABLE: i32 = 65;
+    #[cfg(not(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd"
+    )))]
+    const CONNECTION_REFUSED: i32 = 111;
+    #[cfg(not(any(
+        target_os = "windows",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd"
+    )))]
+    const HOST_UNREACHABLE: i32 = 113;
+
+    /// 连接某个地址时的表现
+    #[derive(Clone, Copy, Debug)]
+    pub(crate) enum Behavior {
+        /// 连接成功，对端只接收数据
+        Accept,
+        /// 连接被拒绝（端口关闭）
+        Refuse,
+        /// 不响应，直到超时（被防火墙丢弃）
+        BlackHole,
+        /// 主机不可达
+        Unreachable,
+        /// 转发到本地的监听地址，用于接入测试中的模拟服务
+        Forward(SocketAddr),
+    }
+
+    /// 未配置的地址一律拒绝连接，未配置的域名解析失败
+    pub(crate) struct StandIn {
+        hosts: HashMap<String, Vec<SocketAddr>>,
+        routes: HashMap<SocketAddr, Behavior>,
+        sink: SocketAddr,
+    }
+
+    impl StandIn {
+        pub(crate) async fn new() -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let sink = listener.local_addr().unwrap();
+            tokio::spawn(async move {
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    tokio::spawn(async move {
+                        let mut buf = [0u8; 1024];
+                        while matches!(stream.read(&mut buf).await, Ok(n) if n > 0) {}
+                    });
+                }
+            });
+            Self {
+                hosts: HashMap::new(),
+                routes: HashMap::new(),
+                sink,
+            }
+        }
+
+        /// 域名解析到的地址，端口使用连接时的端口
+        pub(crate) fn host(mut self, name: &str, ips: &[&str]) -> Self {
+            let addrs = ips
+                .iter()
+                .map(|ip| SocketAddr::new(ip.parse().unwrap(), 0))
+                .collect();
+            self.hosts.insert(name.to_string(), addrs);
+            self
+        }
+
+        pub(crate) fn route(mut self, addr: &str, behavior: Behavior) -> Self {
+            self.routes.insert(addr.parse().unwrap(), behavior);
+            self
+        }
+
+        pub(crate) fn build(self) -> Arc<dyn Connector> {
+            Arc::new(self)
+        }
+    }
+
+    impl Connector for StandIn {
+        fn resolve<'a>(
+            &'a self,
+            host: &'a str,
+            port: u16,
+        ) -> BoxFuture<'a, io::Result<Vec<SocketAddr>>> {
+            let host = host.trim_matches(['[', ']']);
+            let result = match (host.parse::<IpAddr>(), self.hosts.get(host)) {
+                (Ok(ip), _) => Ok(vec![SocketAddr::new(ip, port)]),
+                (_, Some(addrs)) => Ok(addrs
+                    .iter()
+                    .map(|a| SocketAddr::new(a.ip(), port))
+                    .collect()),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("未知的主机 {}", host),
+                )),
+            };
+            Box::pin(std::future::ready(result))
+        }
+
+        fn connect(&self, addr: SocketAddr) -> BoxFuture<'_, io::Result<TcpStream>> {
+            let behavior = self.routes.get(&addr).copied().unwrap_or(Behavior::Refuse);
+            Box::pin(async move {
+                match behavior {
+                    Behavior::Accept => TcpStream::connect(self.sink).await,
+                    Behavior::Forward(target) => TcpStream::connect(target).await,
+                    Behavior::Refuse => Err(io::Error::from_raw_os_error(CONNECTION_REFUSED)),
+                    Behavior::Unreachable => Err(io::Error::from_raw_os_error(HOST_UNREACHABLE)),
+                    Behavior::BlackHole => std::future::pending().await,
+                }
+            })
+        }
+    }
+}
+
+/// 测试用的事件回调：事件写入通道，按条件等待
+#[cfg(test)]
+pub(crate) mod event_sink {
+    use super::EventSink;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    pub(crate) fn channel_sink<E: Send + 'static>() -> (EventSink<E>, mpsc::UnboundedReceiver<E>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (
+            Arc::new(move |event| {
+                let _ = tx.send(event);
+            }),
+            rx,
+        )
+    }
+
+    /// 跳过不满足条件的事件，5 秒内没有等到时失败
+    pub(crate) async fn next_event<E>(
+        events: &mut mpsc::UnboundedReceiver<E>,
+        matches: impl Fn(&E) -> bool,
+    ) -> E {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let event = events.recv().await.expect("event channel closed");
+                if matches(&event) {
+                    return event;
+                }
+            }
+        })
+        .await
+        .expect("timed out waiting for event")
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::stand_in::{Behavior, StandIn};
     use super::*;
     use tokio::net::TcpListener;
 
+    /// 文档保留地址（RFC 5737），不会路由到真实主机
+    const REMOTE: &str = "203.0.113.7";
+
+    async fn remote() -> Arc<dyn Connector> {
+        StandIn::new()
+            .await
+            .host("db.example.test", &[REMOTE])
+            .route("203.0.113.7:3306", Behavior::Accept)
+            .route("203.0.113.7:81", Behavior::BlackHole)
+            .route("203.0.113.7:82", Behavior::Unreachable)
+            .build()
+    }
+
+    async fn check(host: &str, port: u16, timeout_ms: Option<u64>) -> CheckPortResult {
+        with_connector(
+            remote().await,
+            is_port_open(host.to_string(), port, timeout_ms, None),
+        )
+        .await
+        .unwrap()
+    }
+
     #[tokio::test]
     async fn test_port_is_open() {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let port = listener.local_addr().unwrap().port();
         let host = "127.0.0.1".to_string();
         println!("临时监听器已在端口 {} 上启动", port);
-        let result = is_port_open(host, port).await.unwrap();
-        assert_eq!(result.result, true);
-        assert_eq!(result.reason, "端口开放");
+        let result = is_port_open(host, port, None, None).await.unwrap();
+        assert_eq!(result.status, PortStatus::Open);
+        assert_eq!(result.ip.as_deref(), Some("127.0.0.1"));
+        assert!(result.latency_ms.is_some());
+        assert!(result.os_error.is_none());
     }
 
-    /// 测试用例 2: 检查一个几乎可以肯定是关闭的端口
+    /// 测试用例 2: 检查一个关闭的端口
     #[tokio::test]
     async fn test_port_is_closed() {
-        let port = 49150;
-        let host = "127.0.0.1".to_string();
-        let result = is_port_open(host, port).await.unwrap();
-        assert_eq!(result.result, false);
-        assert!(result.reason == "端口关闭或被拒绝" || result.reason == "连接超时");
+        let result = check("127.0.0.1", 49150, None).await;
+        assert_eq!(result.status, PortStatus::Refused);
+        assert!(result.os_error.is_some());
     }
 
     /// 测试用例 3: 测试连接超时的情况
-    /// 注意：这个测试会比较慢，因为需要等待超时
     #[tokio::test]
     async fn test_port_timeout() {
-        let port = 81;
-        let host = "8.138.224.34".to_string();
-        let result = is_port_open(host, port).await.unwrap();
-        assert_eq!(result.result, false);
-        assert_eq!(result.reason, "连接超时");
+        let result = check(REMOTE, 81, Some(200)).await;
+        assert_eq!(result.status, PortStatus::Timeout);
+        assert_eq!(result.ip.as_deref(), Some(REMOTE));
+        assert!(result.latency_ms.unwrap() >= 200.0);
     }
 
     #[tokio::test]
     async fn test_port_success_remote() {
-        let host = "8.138.224.34".to_string();
-        let result = is_port_open(host, 3306).await.unwrap();
-        assert_eq!(result.result, true);
+        let result = check("db.example.test", 3306, None).await;
+        assert_eq!(result.status, PortStatus::Open);
+        assert_eq!(result.ip.as_deref(), Some(REMOTE));
     }
 
     #[tokio::test]
     async fn test_port_fail_remote() {
-        let host = "8.138.224.34".to_string();
-        let result = is_port_open(host, 33076).await.unwrap();
-        println!("{:?}", result.result);
-        println!("{:?}", result.reason);
-        assert_eq!(result.result, false);
+        let result = check(REMOTE, 33076, None).await;
+        assert_eq!(result.status, PortStatus::Refused);
+
+        let result = check(REMOTE, 82, None).await;
+        assert_eq!(result.status, PortStatus::Unreachable);
+        assert!(result.os_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_port_dns_failure() {
+        let result = check("lin-toolbox.invalid", 80, None).await;
+        assert_eq!(result.status, PortStatus::DnsFailure);
+        assert!(result.ip.is_none());
+    }
+
+    /// 解析出多个地址时依次尝试，结果记录成功的地址
+    #[tokio::test]
+    async fn test_port_next_address() {
+        let stand_in = StandIn::new()
+            .await
+            .host("multi.example.test", &["2001:db8::1", "192.0.2.1", REMOTE])
+            .route("[2001:db8::1]:443", Behavior::Unreachable)
+            .route("203.0.113.7:443", Behavior::Accept)
+            .build();
+        let (result, stream) = with_connector(
+            stand_in.clone(),
+            connect("multi.example.test", 443, Duration::from_secs(1)),
+        )
+        .await;
+        assert!(result.is_open());
+        assert_eq!(result.ip.as_deref(), Some(REMOTE));
+        assert!(stream.is_some());
+
+        // 新任务沿用当前任务的连接方式
+        let spawned = with_connector(stand_in, async {
+            spawn(check_port(
+                "multi.example.test",
+                443,
+                Duration::from_secs(1),
+            ))
+            .await
+            .unwrap()
+        })
+        .await;
+        assert!(spawned.is_open());
+        // 未指定时使用系统网络
+        assert!(Arc::ptr_eq(&connector(), &SYSTEM_CONNECTOR));
+    }
+
+    #[tokio::test]
+    async fn test_port_invalid_timeout() {
+        assert!(is_port_open("127.0.0.1".to_string(), 80, Some(0), None)
+            .await
+            .is_err());
+        assert!(is_port_open(
+            "127.0.0.1".to_string(),
+            80,
+            Some(MAX_CONNECT_TIMEOUT_MS + 1),
+            None
+        )
+        .await
+        .is_err());
     }
 }
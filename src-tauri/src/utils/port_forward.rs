@@ -0,0 +1,1038 @@
+use crate::utils::http_client::KeyValue;
+use crate::utils::network_util::{self, open_stream, EventSink, ACCEPT_RETRY_DELAY};
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::{lookup_host, TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+/// 默认连接目标的超时时间（毫秒）
+const DEFAULT_CONNECT_TIMEOUT_MS: u64 = 5_000;
+/// 单次读取的最大字节数
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+/// HTTP 模式下记录的消息体最大字节数，超出部分只计数
+const MAX_BODY_PREVIEW: usize = 64 * 1024;
+/// HTTP 消息头的最大长度，超出后不再解析该连接
+const MAX_HEAD_SIZE: usize = 64 * 1024;
+/// 托盘菜单中转发项的 ID 前缀
+pub const TRAY_MENU_PREFIX: &str = "forward:";
+
+/// 转发模式
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardMode {
+    /// 原样转发字节
+    #[default]
+    Tcp,
+    /// 转发的同时解析 HTTP/1.x 请求与响应
+    Http,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ForwardRequest {
+    /// 监听地址，默认 127.0.0.1
+    pub listen_host: Option<String>,
+    pub listen_port: u16,
+    pub target_host: String,
+    pub target_port: u16,
+    #[serde(default)]
+    pub mode: ForwardMode,
+    /// 连接目标的超时时间（毫秒），默认 5000
+    pub timeout_ms: Option<u64>,
+    /// 代理地址，为空时使用全局代理，`direct` 表示直接连接
+    pub proxy: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ForwardInfo {
+    id: String,
+    mode: ForwardMode,
+    listen_addr: String,
+    target: String,
+    /// 当前连接数
+    active_connections: u64,
+    /// 累计连接数
+    total_connections: u64,
+    /// 客户端发往目标的字节数
+    bytes_up: u64,
+    /// 目标发往客户端的字节数
+    bytes_down: u64,
+    create_time: String,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardEventKind {
+    /// 客户端已连接
+    Opened,
+    /// 连接已关闭
+    Closed,
+    /// HTTP 模式下一次请求与响应
+    Http,
+    Error,
+    /// 转发已停止
+    Stopped,
+}
+
+/// 通过 `port_forward_event` 事件发送给前端
+#[derive(Serialize, Clone, Debug)]
+pub struct ForwardEvent {
+    forward_id: String,
+    kind: ForwardEventKind,
+    /// 连接序号，从 1 开始
+    connection_id: Option<u64>,
+    peer: Option<String>,
+    bytes_up: u64,
+    bytes_down: u64,
+    duration_ms: Option<f64>,
+    exchange: Option<HttpExchange>,
+    message: Option<String>,
+    /// 格式 "YYYY-MM-DD HH:mm:ss.SSS"
+    time: String,
+}
+
+impl ForwardEvent {
+    fn new(forward_id: &str, kind: ForwardEventKind, connection_id: Option<u64>) -> Self {
+        Self {
+            forward_id: forward_id.to_string(),
+            kind,
+            connection_id,
+            peer: None,
+            bytes_up: 0,
+            bytes_down: 0,
+            duration_ms: None,
+            exchange: None,
+            message: None,
+            time: now(),
+        }
+    }
+
+    fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+/// HTTP 请求或响应
+#[derive(Serialize, Clone, Debug)]
+pub struct HttpMessage {
+    /// 请求行或状态行
+    start_line: String,
+    method: Option<String>,
+    /// 请求目标，如 `/api/users?id=1`
+    target: Option<String>,
+    status: Option<u16>,
+    headers: Vec<KeyValue>,
+    /// 消息体的实际大小（分块编码时为解码后的大小）
+    body_size: u64,
+    /// 消息体预览，非 UTF-8 内容按有损方式转换
+    body: String,
+    body_truncated: bool,
+    time: String,
+    #[serde(skip)]
+    body_bytes: Vec<u8>,
+    #[serde(skip)]
+    started: Instant,
+}
+
+impl HttpMessage {
+    fn append_body(&mut self, data: &[u8]) {
+        self.body_size += data.len() as u64;
+        let room = MAX_BODY_PREVIEW.saturating_sub(self.body_bytes.len());
+        self.body_bytes
+            .extend_from_slice(&data[..data.len().min(room)]);
+        self.body_truncated |= data.len() > room;
+    }
+
+    fn finish(mut self) -> Self {
+        self.body = String::from_utf8_lossy(&self.body_bytes).into_owned();
+        self
+    }
+}
+
+/// 一次请求与对应的响应，连接中断时其中一方可能为空
+#[derive(Serialize, Clone, Debug)]
+pub struct HttpExchange {
+    request: Option<HttpMessage>,
+    response: Option<HttpMessage>,
+    /// 从收到请求头到响应结束的耗时
+    duration_ms: Option<f64>,
+}
+
+impl HttpExchange {
+    fn new(request: Option<HttpMessage>, response: Option<HttpMessage>) -> Self {
+        let duration_ms = match (&request, &response) {
+            (Some(request), Some(_)) => Some(request.started.elapsed().as_secs_f64() * 1000.0),
+            _ => None,
+        };
+        Self {
+            request,
+            response,
+            duration_ms,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ForwardStats {
+    active: AtomicU64,
+    total: AtomicU64,
+    up: AtomicU64,
+    down: AtomicU64,
+}
+
+struct Forwarder {
+    id: String,
+    mode: ForwardMode,
+    listen_addr: SocketAddr,
+    target: String,
+    create_time: String,
+    stats: Arc<ForwardStats>,
+    token: CancellationToken,
+}
+
+impl Forwarder {
+    fn info(&self) -> ForwardInfo {
+        ForwardInfo {
+            id: self.id.clone(),
+            mode: self.mode,
+            listen_addr: self.listen_addr.to_string(),
+            target: self.target.clone(),
+            active_connections: self.stats.active.load(Ordering::Relaxed),
+            total_connections: self.stats.total.load(Ordering::Relaxed),
+            bytes_up: self.stats.up.load(Ordering::Relaxed),
+            bytes_down: self.stats.down.load(Ordering::Relaxed),
+            create_time: self.create_time.clone(),
+        }
+    }
+}
+
+/// 全局转发池，Key 是转发 ID
+static FORWARD_POOL: Lazy<Mutex<HashMap<String, Forwarder>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 启动端口转发，连接与 HTTP 记录通过 `port_forward_event` 事件通知前端
+#[tauri::command]
+pub async fn start_port_forward(
+    app_handle: AppHandle,
+    request: ForwardRequest,
+) -> Result<ForwardInfo, String> {
+    let emitter = app_handle.clone();
+    let sink: EventSink<ForwardEvent> = Arc::new(move |event| {
+        if let Err(e) = emitter.emit_to("main", "port_forward_event", event) {
+            eprintln!("发送端口转发事件失败: {}", e);
+        }
+    });
+    let info = start_forward(request, sink).await?;
+    refresh_tray(&app_handle);
+    Ok(info)
+}
+
+/// 停止端口转发，已建立的连接会一并断开
+#[tauri::command]
+pub fn stop_port_forward(app_handle: AppHandle, id: String) -> Result<String, String> {
+    let info = stop_forward(&id)?;
+    refresh_tray(&app_handle);
+    Ok(format!(
+        "已停止转发: {} -> {}",
+        info.listen_addr, info.target
+    ))
+}
+
+/// 当前运行的端口转发及流量统计
+#[tauri::command]
+pub fn list_port_forwards() -> Vec<ForwardInfo> {
+    let mut forwards: Vec<ForwardInfo> = FORWARD_POOL
+        .lock()
+        .unwrap()
+        .values()
+        .map(Forwarder::info)
+        .collect();
+    forwards.sort_by(|a, b| a.create_time.cmp(&b.create_time));
+    forwards
+}
+
+/// 托盘菜单中的转发项，返回菜单项ID与名称
+pub fn tray_forwards() -> Vec<(String, String)> {
+    list_port_forwards()
+        .into_iter()
+        .map(|f| {
+            let mode = match f.mode {
+                ForwardMode::Tcp => "",
+                ForwardMode::Http => " [HTTP]",
+            };
+            (
+                format!("{}{}", TRAY_MENU_PREFIX, f.id),
+                format!("停止 {} -> {}{}", f.listen_addr, f.target, mode),
+            )
+        })
+        .collect()
+}
+
+/// 处理托盘菜单的停止点击
+pub fn stop_from_tray(app_handle: &AppHandle, menu_id: &str) {
+    if let Some(id) = menu_id.strip_prefix(TRAY_MENU_PREFIX) {
+        if let Err(e) = stop_forward(id) {
+            eprintln!("{}", e);
+        }
+        refresh_tray(app_handle);
+    }
+}
+
+fn refresh_tray(app_handle: &AppHandle) {
+    if let Err(e) = crate::refresh_tray_menu(app_handle) {
+        eprintln!("更新托盘菜单失败: {}", e);
+    }
+}
+
+fn stop_forward(id: &str) -> Result<ForwardInfo, String> {
+    let forwarder = FORWARD_POOL
+        .lock()
+        .unwrap()
+        .remove(id)
+        .ok_or_else(|| format!("未找到端口转发: {}", id))?;
+    forwarder.token.cancel();
+    Ok(forwarder.info())
+}
+
+/// 监听本地端口并加入转发池
+async fn start_forward(
+    request: ForwardRequest,
+    sink: EventSink<ForwardEvent>,
+) -> Result<ForwardInfo, String> {
+    let target_host = request.target_host.trim().trim_matches(['[', ']']);
+    if target_host.is_empty() {
+        return Err("请输入目标主机".to_string());
+    }
+    if request.target_port == 0 {
+        return Err("目标端口应在 1-65535 之间".to_string());
+    }
+    let listen_host = request
+        .listen_host
+        .as_deref()
+        .map(|h| h.trim().trim_matches(['[', ']']))
+        .filter(|h| !h.is_empty())
+        .unwrap_or("127.0.0.1");
+    let bind = lookup_host((listen_host, request.listen_port))
+        .await
+        .map_err(|e| format!("无法解析监听地址 {}: {}", listen_host, e))?
+        .next()
+        .ok_or_else(|| format!("无法解析监听地址: {}", listen_host))?;
+    let listener = TcpListener::bind(bind)
+        .await
+        .map_err(|e| format!("监听 {} 失败: {}", bind, e))?;
+    let listen_addr = listener.local_addr().map_err(|e| e.to_string())?;
+
+    let target = if target_host.contains(':') {
+        format!("[{}]:{}", target_host, request.target_port)
+    } else {
+        format!("{}:{}", target_host, request.target_port)
+    };
+    let forwarder = Forwarder {
+        id: uuid::Uuid::new_v4().to_string(),
+        mode: request.mode,
+        listen_addr,
+        target: target.clone(),
+        create_time: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        stats: Arc::new(ForwardStats::default()),
+        token: CancellationToken::new(),
+    };
+    let context = Arc::new(ForwardContext {
+        id: forwarder.id.clone(),
+        target_host: target_host.to_string(),
+        target_port: request.target_port,
+        proxy: request.proxy.clone(),
+        timeout: Duration::from_millis(
+            request
+                .timeout_ms
+                .unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS)
+                .max(1),
+        ),
+        mode: request.mode,
+        stats: forwarder.stats.clone(),
+        token: forwarder.token.clone(),
+        sink,
+    });
+    let info = forwarder.info();
+    FORWARD_POOL
+        .lock()
+        .unwrap()
+        .insert(forwarder.id.clone(), forwarder);
+    network_util::spawn(run_forward(listener, context));
+    Ok(info)
+}
+
+struct ForwardContext {
+    id: String,
+    target_host: String,
+    target_port: u16,
+    proxy: Option<String>,
+    timeout: Duration,
+    mode: ForwardMode,
+    stats: Arc<ForwardStats>,
+    token: CancellationToken,
+    sink: EventSink<ForwardEvent>,
+}
+
+async fn run_forward(listener: TcpListener, context: Arc<ForwardContext>) {
+    let mut connection_id = 0u64;
+    loop {
+        let accepted = tokio::select! {
+            _ = context.token.cancelled() => break,
+            accepted = listener.accept() => accepted,
+        };
+        match accepted {
+            Ok((client, peer)) => {
+                connection_id += 1;
+                network_util::spawn(handle_connection(
+                    context.clone(),
+                    connection_id,
+                    client,
+                    peer,
+                ));
+            }
+            Err(e) => {
+                (context.sink)(
+                    ForwardEvent::new(&context.id, ForwardEventKind::Error, None)
+                        .with_message(format!("接受连接失败: {}", e)),
+                );
+                // 持续出错（如文件描述符耗尽）时稍后重试，避免空转刷屏
+                tokio::select! {
+                    _ = context.token.cancelled() => break,
+                    _ = tokio::time::sleep(ACCEPT_RETRY_DELAY) => {}
+                }
+            }
+        }
+    }
+    (context.sink)(ForwardEvent::new(
+        &context.id,
+        ForwardEventKind::Stopped,
+        None,
+    ));
+}
+
+/// 单个连接的流量计数
+#[derive(Default)]
+struct ConnectionBytes {
+    up: AtomicU64,
+    down: AtomicU64,
+}
+
+async fn handle_connection(
+    context: Arc<ForwardContext>,
+    connection_id: u64,
+    client: TcpStream,
+    peer: SocketAddr,
+) {
+    let stats = &context.stats;
+    stats.active.fetch_add(1, Ordering::Relaxed);
+    stats.total.fetch_add(1, Ordering::Relaxed);
+    let mut opened = ForwardEvent::new(&context.id, ForwardEventKind::Opened, Some(connection_id));
+    opened.peer = Some(peer.to_string());
+    (context.sink)(opened);
+
+    let started = Instant::now();
+    let bytes = ConnectionBytes::default();
+    let result = tokio::select! {
+        _ = context.token.cancelled() => Err("转发已停止".to_string()),
+        result = relay(&context, connection_id, client, &bytes) => result,
+    };
+    stats.active.fetch_sub(1, Ordering::Relaxed);
+
+    let mut closed = ForwardEvent::new(&context.id, ForwardEventKind::Closed, Some(connection_id));
+    closed.peer = Some(peer.to_string());
+    closed.bytes_up = bytes.up.load(Ordering::Relaxed);
+    closed.bytes_down = bytes.down.load(Ordering::Relaxed);
+    closed.duration_ms = Some(started.elapsed().as_secs_f64() * 1000.0);
+    closed.message = result.err();
+    (context.sink)(closed);
+}
+
+/// 连接目标并双向转发，任意一方出错即结束
+async fn relay(
+    context: &ForwardContext,
+    connection_id: u64,
+    client: TcpStream,
+    bytes: &ConnectionBytes,
+) -> Result<(), String> {
+    let target = open_stream(
+        &context.target_host,
+        context.target_port,
+        context.timeout,
+        context.proxy.as_deref(),
+    )
+    .await
+    .map_err(|e| format!("连接目标失败: {}", e))?;
+    let _ = client.set_nodelay(true);
+    let _ = target.set_nodelay(true);
+    let (client_reader, client_writer) = client.into_split();
+    let (target_reader, target_writer) = target.into_split();
+    let stats = &context.stats;
+
+    if context.mode == ForwardMode::Tcp {
+        tokio::try_join!(
+            pipe(client_reader, target_writer, &bytes.up, &stats.up, |_| {}),
+            pipe(
+                target_reader,
+                client_writer,
+                &bytes.down,
+                &stats.down,
+                |_| {}
+            ),
+        )?;
+        return Ok(());
+    }
+
+    // 两个方向在同一个任务中轮询，锁不会跨越 await
+    let inspector = Mutex::new(HttpInspector::default());
+    let emit = |exchanges: Vec<HttpExchange>| {
+        for exchange in exchanges {
+            let mut event =
+                ForwardEvent::new(&context.id, ForwardEventKind::Http, Some(connection_id));
+            event.exchange = Some(exchange);
+            (context.sink)(event);
+        }
+    };
+    let result = tokio::try_join!(
+        pipe(client_reader, target_writer, &bytes.up, &stats.up, |data| {
+            inspector.lock().unwrap().feed_request(data)
+        }),
+        pipe(
+            target_reader,
+            client_writer,
+            &bytes.down,
+            &stats.down,
+            |data| { emit(inspector.lock().unwrap().feed_response(data)) }
+        ),
+    );
+    emit(inspector.lock().unwrap().finish());
+    result.map(|_| ())
+}
+
+/// 从 reader 读取并写入 writer，数据先交给 inspect 再转发
+async fn pipe<R, W, F>(
+    mut reader: R,
+    mut writer: W,
+    connection_bytes: &AtomicU64,
+    total_bytes: &AtomicU64,
+    mut inspect: F,
+) -> Result<(), String>
+where
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+    F: FnMut(&[u8]),
+{
+    let mut buf = vec![0u8; READ_BUFFER_SIZE];
+    loop {
+        let n = reader.read(&mut buf).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            // 对端关闭写入后同样关闭另一侧的写入，保留半关闭语义
+            let _ = writer.shutdown().await;
+            return Ok(());
+        }
+        inspect(&buf[..n]);
+        writer
+            .write_all(&buf[..n])
+            .await
+            .map_err(|e| e.to_string())?;
+        connection_bytes.fetch_add(n as u64, Ordering::Relaxed);
+        total_bytes.fetch_add(n as u64, Ordering::Relaxed);
+    }
+}
+
+/// 解析一个连接上双向的 HTTP/1.x 流量，按顺序配对请求与响应
+pub struct HttpInspector {
+    requests: MessageParser,
+    responses: MessageParser,
+    /// 等待响应的请求
+    pending: VecDeque<HttpMessage>,
+}
+
+impl Default for HttpInspector {
+    fn default() -> Self {
+        Self {
+            requests: MessageParser::default(),
+            responses: MessageParser {
+                response: true,
+                ..Default::default()
+            },
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl HttpInspector {
+    pub fn feed_request(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let (consumed, message) = self.requests.step(data, None);
+            if let Some(message) = message {
+                self.pending.push_back(message);
+            } else if consumed == 0 {
+                break;
+            }
+            data = &data[consumed..];
+        }
+    }
+
+    pub fn feed_response(&mut self, mut data: &[u8]) -> Vec<HttpExchange> {
+        let mut exchanges = Vec::new();
+        while !data.is_empty() {
+            let method = self.pending.front().and_then(|r| r.method.clone());
+            let (consumed, message) = self.responses.step(data, method.as_deref());
+            if let Some(message) = message {
+                // 100 Continue 等临时响应不对应请求
+                if !matches!(message.status, Some(100..=199)) || message.status == Some(101) {
+                    exchanges.push(HttpExchange::new(self.pending.pop_front(), Some(message)));
+                }
+            } else if consumed == 0 {
+                break;
+            }
+            data = &data[consumed..];
+        }
+        exchanges
+    }
+
+    /// 连接关闭时调用，返回以关闭连接结束的响应与未收到响应的请求
+    pub fn finish(&mut self) -> Vec<HttpExchange> {
+        let mut exchanges = Vec::new();
+        if let Some(response) = self.responses.finish() {
+            exchanges.push(HttpExchange::new(self.pending.pop_front(), Some(response)));
+        }
+        exchanges.extend(
+            self.pending
+                .drain(..)
+                .map(|request| HttpExchange::new(Some(request), None)),
+        );
+        exchanges
+    }
+}
+
+#[derive(Default)]
+enum ParseState {
+    #[default]
+    Head,
+    Length(u64),
+    ChunkSize,
+    ChunkData(u64),
+    /// 分块数据后的 CRLF
+    ChunkDataEnd,
+    Trailer,
+    /// 没有长度的响应，以连接关闭结束
+    UntilClose,
+    /// 协议升级、CONNECT 隧道或无法解析时不再解析
+    Passthrough,
+}
+
+#[derive(Default)]
+struct MessageParser {
+    response: bool,
+    state: ParseState,
+    line: Vec<u8>,
+    message: Option<HttpMessage>,
+    /// 当前消息结束后转为透传
+    upgrade: bool,
+}
+
+impl MessageParser {
+    /// 解析一段数据，返回消耗的字节数与解析完成的消息
+    fn step(&mut self, data: &[u8], request_method: Option<&str>) -> (usize, Option<HttpMessage>) {
+        match self.state {
+            ParseState::Passthrough => (data.len(), None),
+            ParseState::UntilClose => {
+                if let Some(message) = self.message.as_mut() {
+                    message.append_body(data);
+                }
+                (data.len(), None)
+            }
+            ParseState::Head => {
+                let searched = self.line.len().saturating_sub(3);
+                self.line.extend_from_slice(data);
+                let Some(end) = find(&self.line[searched..], b"\r\n\r\n").map(|i| searched + i + 4)
+                else {
+                    if self.line.len() > MAX_HEAD_SIZE {
+                        self.passthrough();
+                    }
+                    return (data.len(), None);
+                };
+                let consumed = data.len() - (self.line.len() - end);
+                let head = std::mem::take(&mut self.line);
+                match self.parse_head(&head[..end - 4], request_method) {
+                    Some(body) => {
+                        self.state = body;
+                        if matches!(self.state, ParseState::Length(0)) {
+                            return (consumed, self.complete());
+                        }
+                        (consumed, None)
+                    }
+                    None => {
+                        self.passthrough();
+                        (consumed, None)
+                    }
+                }
+            }
+            ParseState::Length(remaining) | ParseState::ChunkData(remaining) => {
+                let take = data.len().min(remaining as usize);
+                if let Some(message) = self.message.as_mut() {
+                    message.append_body(&data[..take]);
+                }
+                let remaining = remaining - take as u64;
+                match (&self.state, remaining) {
+                    (ParseState::Length(_), 0) => (take, self.complete()),
+                    (ParseState::Length(_), _) => {
+                        self.state = ParseState::Length(remaining);
+                        (take, None)
+                    }
+                    (_, 0) => {
+                        self.state = ParseState::ChunkDataEnd;
+                        (take, None)
+                    }
+                    _ => {
+                        self.state = ParseState::ChunkData(remaining);
+                        (take, None)
+                    }
+                }
+            }
+            ParseState::ChunkSize | ParseState::ChunkDataEnd | ParseState::Trailer => {
+                let (consumed, line) = self.read_line(data);
+                let Some(line) = line else {
+                    return (consumed, None);
+                };
+                let line = String::from_utf8_lossy(&line);
+                let line = line.trim();
+                match self.state {
+                    ParseState::ChunkSize => {
+                        let size = line.split(';').next().unwrap_or("").trim();
+                        match u64::from_str_radix(size, 16) {
+                            Ok(0) => self.state = ParseState::Trailer,
+                            Ok(size) => self.state = ParseState::ChunkData(size),
+                            Err(_) => self.passthrough(),
+                        }
+                        (consumed, None)
+                    }
+                    ParseState::ChunkDataEnd => {
+                        self.state = ParseState::ChunkSize;
+                        (consumed, None)
+                    }
+                    _ if line.is_empty() => (consumed, self.complete()),
+                    _ => (consumed, None),
+                }
+            }
+        }
+    }
+
+    /// 连接关闭，以关闭结束的响应在此完成
+    fn finish(&mut self) -> Option<HttpMessage> {
+        match self.state {
+            ParseState::UntilClose => self.complete(),
+            _ => None,
+        }
+    }
+
+    fn read_line(&mut self, data: &[u8]) -> (usize, Option<Vec<u8>>) {
+        match data.iter().position(|b| *b == b'\n') {
+            Some(i) => {
+                self.line.extend_from_slice(&data[..=i]);
+                (i + 1, Some(std::mem::take(&mut self.line)))
+            }
+            None => {
+                self.line.extend_from_slice(data);
+                if self.line.len() > MAX_HEAD_SIZE {
+                    self.passthrough();
+                }
+                (data.len(), None)
+            }
+        }
+    }
+
+    fn complete(&mut self) -> Option<HttpMessage> {
+        self.state = if self.upgrade {
+            ParseState::Passthrough
+        } else {
+            ParseState::Head
+        };
+        self.message.take().map(HttpMessage::finish)
+    }
+
+    fn passthrough(&mut self) {
+        self.state = ParseState::Passthrough;
+        self.line.clear();
+        self.message = None;
+    }
+
+    /// 解析消息头，返回消息体的读取方式
+    fn parse_head(&mut self, head: &[u8], request_method: Option<&str>) -> Option<ParseState> {
+        let head = String::from_utf8_lossy(head);
+        let mut lines = head.split("\r\n");
+        let start_line = lines.next()?.trim().to_string();
+        let headers: Vec<KeyValue> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| KeyValue::new(name.trim(), value.trim()))
+            .collect();
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|h| h.name.eq_ignore_ascii_case(name))
+                .map(|h| h.value.as_str())
+        };
+        let chunked =
+            header("Transfer-Encoding").is_some_and(|v| v.to_ascii_lowercase().contains("chunked"));
+        let content_length = header("Content-Length").and_then(|v| v.parse::<u64>().ok());
+        let body = if chunked {
+            ParseState::ChunkSize
+        } else {
+            ParseState::Length(content_length.unwrap_or(0))
+        };
+
+        let mut message = HttpMessage {
+            start_line: start_line.clone(),
+            method: None,
+            target: None,
+            status: None,
+            headers: Vec::new(),
+            body_size: 0,
+            body: String::new(),
+            body_truncated: false,
+            time: now(),
+            body_bytes: Vec::new(),
+            started: Instant::now(),
+        };
+        let mut parts = start_line.split_whitespace();
+        let body = if self.response {
+            if !parts.next()?.starts_with("HTTP/") {
+                return None;
+            }
+            let status: u16 = parts.next()?.parse().ok()?;
+            message.status = Some(status);
+            let connect = request_method.is_some_and(|m| m.eq_ignore_ascii_case("CONNECT"));
+            self.upgrade = status == 101 || (connect && (200..300).contains(&status));
+            let head_only = request_method.is_some_and(|m| m.eq_ignore_ascii_case("HEAD"));
+            if self.upgrade || head_only || matches!(status, 100..=199 | 204 | 304) {
+                ParseState::Length(0)
+            } else if chunked || content_length.is_some() {
+                body
+            } else {
+                ParseState::UntilClose
+            }
+        } else {
+            let method = parts.next()?;
+            let target = parts.next()?;
+            if !parts.next()?.starts_with("HTTP/") {
+                return None;
+            }
+            // CONNECT 之后是隧道，升级请求的后续数据由响应决定，均不再解析
+            self.upgrade = method.eq_ignore_ascii_case("CONNECT") || header("Upgrade").is_some();
+            message.method = Some(method.to_string());
+            message.target = Some(target.to_string());
+            body
+        };
+        message.headers = headers;
+        self.message = Some(message);
+        Some(body)
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+fn now() -> String {
+    Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::network_util::event_sink::{channel_sink, next_event};
+    use crate::utils::network_util::stand_in::{Behavior, StandIn};
+    use crate::utils::network_util::with_connector;
+
+    fn request(port: u16, mode: ForwardMode) -> ForwardRequest {
+        ForwardRequest {
+            listen_host: None,
+            listen_port: 0,
+            target_host: "127.0.0.1".to_string(),
+            target_port: port,
+            mode,
+            timeout_ms: Some(1_000),
+            proxy: None,
+        }
+    }
+
+    #[test]
+    fn test_http_inspector() {
+        let mut inspector = HttpInspector::default();
+        // 两个流水线请求，分多次到达
+        let requests = b"POST /users HTTP/1.1\r\nHost: a\r\nContent-Length: 5\r\n\r\nhelloHEAD /ping HTTP/1.1\r\nHost: a\r\n\r\n";
+        inspector.feed_request(&requests[..20]);
+        inspector.feed_request(&requests[20..]);
+        assert_eq!(inspector.pending.len(), 2);
+
+        let responses = b"HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 201 Created\r\nTransfer-Encoding: chunked\r\n\r\n3;x=1\r\nabc\r\n2\r\nde\r\n0\r\n\r\nHTTP/1.1 200 OK\r\nContent-Length: 42\r\n\r\n";
+        let mut exchanges = Vec::new();
+        for chunk in responses.chunks(7) {
+            exchanges.extend(inspector.feed_response(chunk));
+        }
+        assert_eq!(exchanges.len(), 2);
+        let first = &exchanges[0];
+        let request = first.request.as_ref().unwrap();
+        assert_eq!(request.method.as_deref(), Some("POST"));
+        assert_eq!(request.target.as_deref(), Some("/users"));
+        assert_eq!(request.body, "hello");
+        let response = first.response.as_ref().unwrap();
+        assert_eq!(response.status, Some(201));
+        assert_eq!(response.body, "abcde");
+        assert_eq!(response.body_size, 5);
+        assert!(first.duration_ms.is_some());
+        // HEAD 的响应没有消息体，即使带有 Content-Length
+        assert_eq!(exchanges[1].response.as_ref().unwrap().body_size, 0);
+
+        // 没有长度的响应以连接关闭结束，未响应的请求单独记录
+        inspector.feed_request(b"GET /stream HTTP/1.0\r\n\r\nGET /never HTTP/1.1\r\n\r\n");
+        assert!(inspector
+            .feed_response(b"HTTP/1.0 200 OK\r\n\r\npart1")
+            .is_empty());
+        inspector.feed_response(b"part2");
+        let exchanges = inspector.finish();
+        assert_eq!(exchanges.len(), 2);
+        assert_eq!(exchanges[0].response.as_ref().unwrap().body, "part1part2");
+        assert!(exchanges[1].response.is_none());
+
+        // 非 HTTP 流量直接透传
+        let mut inspector = HttpInspector::default();
+        inspector.feed_request(b"\x16\x03\x01\x02\x00garbage\r\n\r\n");
+        assert!(inspector.pending.is_empty());
+        assert!(matches!(inspector.requests.state, ParseState::Passthrough));
+    }
+
+    #[tokio::test]
+    async fn test_tcp_forward() {
+        let echo = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let echo_port = echo.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut stream, _) = echo.accept().await.unwrap();
+            let (mut reader, mut writer) = stream.split();
+            tokio::io::copy(&mut reader, &mut writer).await.unwrap();
+        });
+
+        let (sink, mut rx) = channel_sink();
+        let info = start_forward(request(echo_port, ForwardMode::Tcp), sink)
+            .await
+            .unwrap();
+        let mut client = TcpStream::connect(&info.listen_addr).await.unwrap();
+        client.write_all(b"hello forward").await.unwrap();
+        let mut buf = [0u8; 13];
+        client.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"hello forward");
+        assert!(next_event(&mut rx, |e| e.kind == ForwardEventKind::Opened)
+            .await
+            .peer
+            .is_some());
+
+        client.shutdown().await.unwrap();
+        let closed = next_event(&mut rx, |e| e.kind == ForwardEventKind::Closed).await;
+        assert_eq!(closed.connection_id, Some(1));
+        assert_eq!((closed.bytes_up, closed.bytes_down), (13, 13));
+        let stats = list_port_forwards()
+            .into_iter()
+            .find(|f| f.id == info.id)
+            .unwrap();
+        assert_eq!((stats.total_connections, stats.active_connections), (1, 0));
+        assert_eq!(stats.bytes_up, 13);
+
+        stop_forward(&info.id).unwrap();
+        next_event(&mut rx, |e| e.kind == ForwardEventKind::Stopped).await;
+        assert!(stop_forward(&info.id).is_err());
+        assert!(
+            start_forward(request(0, ForwardMode::Tcp), channel_sink().0)
+                .await
+                .is_err()
+        );
+    }
+
+    /// 转发任务沿用启动时的连接方式，目标主机名经模拟网络解析
+    #[tokio::test]
+    async fn test_forward_stand_in_target() {
+        let echo = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let echo_addr = echo.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = echo.accept().await.unwrap();
+            let (mut reader, mut writer) = stream.split();
+            tokio::io::copy(&mut reader, &mut writer).await.unwrap();
+        });
+        let connector = StandIn::new()
+            .await
+            .host("echo.example.test", &["192.0.2.20"])
+            .route("192.0.2.20:7", Behavior::Forward(echo_addr))
+            .build();
+
+        let mut forward = request(7, ForwardMode::Tcp);
+        forward.target_host = "echo.example.test".to_string();
+        let (sink, _rx) = channel_sink();
+        let info = with_connector(connector.clone(), start_forward(forward, sink))
+            .await
+            .unwrap();
+        let mut client = TcpStream::connect(&info.listen_addr).await.unwrap();
+        client.write_all(b"ping").await.unwrap();
+        let mut buf = [0u8; 4];
+        client.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"ping");
+        stop_forward(&info.id).unwrap();
+
+        // 目标拒绝连接时关闭客户端连接并记录原因
+        let mut refused = request(3306, ForwardMode::Tcp);
+        refused.target_host = "echo.example.test".to_string();
+        let (sink, mut rx) = channel_sink();
+        let info = with_connector(connector, start_forward(refused, sink))
+            .await
+            .unwrap();
+        let mut client = TcpStream::connect(&info.listen_addr).await.unwrap();
+        let closed = next_event(&mut rx, |e| e.kind == ForwardEventKind::Closed).await;
+        assert!(closed.message.is_some());
+        assert_eq!(client.read(&mut buf).await.unwrap(), 0);
+        stop_forward(&info.id).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_http_forward() {
+        let server = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = server.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut stream, _) = server.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let n = stream.read(&mut buf).await.unwrap();
+            assert!(buf[..n].starts_with(b"GET /health"));
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .await
+                .unwrap();
+        });
+
+        let (sink, mut rx) = channel_sink();
+        let info = start_forward(request(port, ForwardMode::Http), sink)
+            .await
+            .unwrap();
+        let mut client = TcpStream::connect(&info.listen_addr).await.unwrap();
+        client
+            .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = Vec::new();
+        client.read_to_end(&mut response).await.unwrap();
+        assert!(response.ends_with(b"\r\n\r\nok"));
+
+        let event = next_event(&mut rx, |e| e.kind == ForwardEventKind::Http).await;
+        let exchange = event.exchange.unwrap();
+        assert_eq!(exchange.request.unwrap().target.as_deref(), Some("/health"));
+        let response = exchange.response.unwrap();
+        assert_eq!(response.status, Some(200));
+        assert_eq!(response.body, "ok");
+        stop_forward(&info.id).unwrap();
+    }
+}
@@ -0,0 +1,506 @@
+use crate::utils::network_util::{self, check_port, CheckPortResult, PortStatus};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::Semaphore;
+use tokio::task::{Id, JoinError, JoinSet};
+use tokio_util::sync::CancellationToken;
+
+/// 单次扫描最多展开的主机数量
+const MAX_HOSTS: usize = 65_536;
+/// 单次扫描最多的探测次数（主机数 × 端口数）
+const MAX_PROBES: usize = 1_000_000;
+/// 默认并发连接数
+const DEFAULT_CONCURRENCY: usize = 200;
+/// 最大并发连接数
+const MAX_CONCURRENCY: usize = 2_000;
+/// 默认单个连接超时时间（毫秒）
+const DEFAULT_TIMEOUT_MS: u64 = 1_000;
+/// 进度事件的最小发送间隔
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 全局扫描任务池，Key 是扫描任务ID，Value 是取消令牌
+pub static SCAN_TASK_POOL: Lazy<Mutex<HashMap<String, CancellationToken>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 端口扫描请求
+#[derive(Deserialize, Clone)]
+pub struct PortScanRequest {
+    /// 扫描任务ID，由前端生成，用于区分事件与取消任务
+    pub id: String,
+    /// 主机列表，以逗号、空白或换行分隔，支持域名、IP 与 CIDR（如 `10.0.0.0/28`）
+    pub hosts: String,
+    /// 端口列表，支持单个端口与范围（如 `22,80,443,8000-8100`）
+    pub ports: String,
+    /// 并发连接数，默认 200
+    pub concurrency: Option<usize>,
+    /// 单个连接超时时间（毫秒），默认 1000
+    pub timeout_ms: Option<u64>,
+    /// 是否同时推送未开放的端口，默认只推送开放端口
+    pub report_closed: Option<bool>,
+}
+
+/// 扫描开始时返回的任务规模
+#[derive(Serialize, Debug)]
+pub struct PortScanStarted {
+    id: String,
+    hosts: usize,
+    ports: usize,
+    total: usize,
+}
+
+/// 单个端口的扫描结果，通过 `port_scan_result` 事件推送
+#[derive(Serialize, Clone, Debug)]
+pub struct PortScanResult {
+    scan_id: String,
+    host: String,
+    port: u16,
+    #[serde(flatten)]
+    check: CheckPortResult,
+}
+
+/// 扫描进度，通过 `port_scan_progress` 事件推送
+#[derive(Serialize, Clone, Debug)]
+pub struct PortScanProgress {
+    scan_id: String,
+    scanned: usize,
+    total: usize,
+}
+
+/// 扫描汇总，通过 `port_scan_finished` 事件推送
+#[derive(Serialize, Clone, Debug)]
+pub struct PortScanSummary {
+    scan_id: String,
+    total: usize,
+    scanned: usize,
+    open: usize,
+    /// 连接被拒绝
+    closed: usize,
+    timeout: usize,
+    /// 不可达、域名解析失败等其他错误
+    failed: usize,
+    cancelled: bool,
+    elapsed_ms: u64,
+    open_ports: Vec<PortScanResult>,
+}
+
+/// 启动端口扫描，扫描在后台执行，结果以事件形式推送给前端
+#[tauri::command]
+pub async fn start_port_scan(
+    app_handle: AppHandle,
+    request: PortScanRequest,
+) -> Result<PortScanStarted, String> {
+    let hosts = parse_hosts(&request.hosts)?;
+    let ports = parse_ports(&request.ports)?;
+    let total = hosts.len() * ports.len();
+    if total > MAX_PROBES {
+        return Err(format!("探测次数 {} 超过上限 {}", total, MAX_PROBES));
+    }
+
+    let token = CancellationToken::new();
+    {
+        let mut pool = SCAN_TASK_POOL.lock().unwrap();
+        if pool.contains_key(&request.id) {
+            return Err(format!("扫描任务已存在: {}", request.id));
+        }
+        pool.insert(request.id.clone(), token.clone());
+    }
+
+    let started = PortScanStarted {
+        id: request.id.clone(),
+        hosts: hosts.len(),
+        ports: ports.len(),
+        total,
+    };
+
+    network_util::spawn(async move {
+        let scan_id = request.id.clone();
+        let report_closed = request.report_closed.unwrap_or(false);
+        let mut last_progress = Instant::now();
+        let summary = run_scan(&request, hosts, ports, token, |result, scanned| {
+            if result.check.is_open() || report_closed {
+                let _ = app_handle.emit_to("main", "port_scan_result", result);
+            }
+            if last_progress.elapsed() >= PROGRESS_INTERVAL || scanned == total {
+                last_progress = Instant::now();
+                let progress = PortScanProgress {
+                    scan_id: scan_id.clone(),
+                    scanned,
+                    total,
+                };
+                let _ = app_handle.emit_to("main", "port_scan_progress", progress);
+            }
+        })
+        .await;
+
+        SCAN_TASK_POOL.lock().unwrap().remove(&scan_id);
+        let _ = app_handle.emit_to("main", "port_scan_finished", summary);
+    });
+
+    Ok(started)
+}
+
+/// 取消端口扫描，已完成的结果仍会出现在最终汇总中
+#[tauri::command]
+pub fn cancel_port_scan(id: String) -> Result<String, String> {
+    let pool = SCAN_TASK_POOL.lock().unwrap();
+    if let Some(token) = pool.get(&id) {
+        token.cancel();
+        Ok(format!("已取消扫描任务: {}", id))
+    } else {
+        Err(format!("未找到扫描任务: {}", id))
+    }
+}
+
+/// 以有限并发执行扫描，每得到一个结果调用一次 `on_result(结果, 已完成数量)`
+pub async fn run_scan<F>(
+    request: &PortScanRequest,
+    hosts: Vec<String>,
+    ports: Vec<u16>,
+    token: CancellationToken,
+    mut on_result: F,
+) -> PortScanSummary
+where
+    F: FnMut(&PortScanResult, usize),
+{
+    let start = Instant::now();
+    let concurrency = request
+        .concurrency
+        .unwrap_or(DEFAULT_CONCURRENCY)
+        .clamp(1, MAX_CONCURRENCY);
+    let timeout = Duration::from_millis(request.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).max(1));
+
+    let mut summary = PortScanSummary {
+        scan_id: request.id.clone(),
+        total: hosts.len() * ports.len(),
+        scanned: 0,
+        open: 0,
+        closed: 0,
+        timeout: 0,
+        failed: 0,
+        cancelled: false,
+        elapsed_ms: 0,
+        open_ports: Vec::new(),
+    };
+    let mut record = |result: PortScanResult, summary: &mut PortScanSummary| {
+        summary.scanned += 1;
+        match result.check.status {
+            PortStatus::Open => summary.open += 1,
+            PortStatus::Refused => summary.closed += 1,
+            PortStatus::Timeout => summary.timeout += 1,
+            _ => summary.failed += 1,
+        }
+        on_result(&result, summary.scanned);
+        if result.check.is_open() {
+            summary.open_ports.push(result);
+        }
+    };
+
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut join_set = JoinSet::new();
+    // 任务异常退出时用于找回对应的主机与端口
+    let mut targets = HashMap::new();
+    let connector = network_util::connector();
+    'outer: for host in &hosts {
+        for &port in &ports {
+            // 等待空闲的并发名额，期间处理已完成的结果
+            let permit = tokio::select! {
+                biased;
+                _ = token.cancelled() => break 'outer,
+                permit = semaphore.clone().acquire_owned() => permit.expect("semaphore closed"),
+            };
+            while let Some(joined) = join_set.try_join_next_with_id() {
+                record(
+                    joined_result(&request.id, &mut targets, joined),
+                    &mut summary,
+                );
+            }
+
+            let scan_id = request.id.clone();
+            let target = host.clone();
+            let handle = join_set.spawn(network_util::with_connector(
+                connector.clone(),
+                async move {
+                    let result = probe(scan_id, target, port, timeout).await;
+                    drop(permit);
+                    result
+                },
+            ));
+            targets.insert(handle.id(), (host.clone(), port));
+        }
+    }
+
+    loop {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => {
+                join_set.abort_all();
+                break;
+            }
+            next = join_set.join_next_with_id() => match next {
+                Some(joined) => record(joined_result(&request.id, &mut targets, joined), &mut summary),
+                None => break,
+            },
+        }
+    }
+
+    summary.cancelled = token.is_cancelled();
+    summary.elapsed_ms = start.elapsed().as_millis() as u64;
+    summary
+}
+
+/// 取出已结束任务的结果，任务异常退出时记为错误，保证进度能达到总数
+fn joined_result(
+    scan_id: &str,
+    targets: &mut HashMap<Id, (String, u16)>,
+    joined: Result<(Id, PortScanResult), JoinError>,
+) -> PortScanResult {
+    match joined {
+        Ok((id, result)) => {
+            targets.remove(&id);
+            result
+        }
+        Err(e) => {
+            let (host, port) = targets.remove(&e.id()).unwrap_or_default();
+            let message = format!("检测任务异常退出: {}", e);
+            PortScanResult {
+                scan_id: scan_id.to_string(),
+                host,
+                port,
+                check: CheckPortResult::new(PortStatus::Error, None, None).with_message(&message),
+            }
+        }
+    }
+}
+
+async fn probe(scan_id: String, host: String, port: u16, timeout: Duration) -> PortScanResult {
+    let check = check_port(&host, port, timeout).await;
+    PortScanResult {
+        scan_id,
+        host,
+        port,
+        check,
+    }
+}
+
+/// 解析主机列表，展开 CIDR 并去重（保持输入顺序）
+pub fn parse_hosts(input: &str) -> Result<Vec<String>, String> {
+    let mut hosts = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for entry in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+    {
+        let expanded = match entry.split_once('/') {
+            Some((addr, prefix)) => expand_cidr(addr, prefix)?,
+            None => vec![entry.to_string()],
+        };
+        for host in expanded {
+            if seen.insert(host.clone()) {
+                hosts.push(host);
+            }
+            if hosts.len() > MAX_HOSTS {
+                return Err(format!("主机数量超过上限 {}", MAX_HOSTS));
+            }
+        }
+    }
+    if hosts.is_empty() {
+        return Err("请输入主机".to_string());
+    }
+    Ok(hosts)
+}
+
+/// 展开 CIDR 中的全部地址（包含网络地址与广播地址）
+fn expand_cidr(addr: &str, prefix: &str) -> Result<Vec<String>, String> {
+    let invalid = || format!("无效的 CIDR: {}/{}", addr, prefix);
+    let ip: IpAddr = addr.parse().map_err(|_| invalid())?;
+    let prefix: u32 = prefix.parse().map_err(|_| invalid())?;
+    let bits = if ip.is_ipv4() { 32 } else { 128 };
+    if prefix > bits {
+        return Err(invalid());
+    }
+    let host_bits = bits - prefix;
+    if host_bits > 16 {
+        return Err(format!(
+            "CIDR {}/{} 过大，最多展开 {} 个地址",
+            addr, prefix, MAX_HOSTS
+        ));
+    }
+    let count = 1u128 << host_bits;
+
+    Ok(match ip {
+        IpAddr::V4(ip) => {
+            let network = u32::from(ip) & (u32::MAX.checked_shl(host_bits).unwrap_or(0));
+            (0..count as u32)
+                .map(|i| Ipv4Addr::from(network + i).to_string())
+                .collect()
+        }
+        IpAddr::V6(ip) => {
+            let network = u128::from(ip) & (u128::MAX.checked_shl(host_bits).unwrap_or(0));
+            (0..count)
+                .map(|i| Ipv6Addr::from(network + i).to_string())
+                .collect()
+        }
+    })
+}
+
+/// 解析端口列表，如 `22,80,443,8000-8100`，结果升序去重
+pub fn parse_ports(input: &str) -> Result<Vec<u16>, String> {
+    let parse_port = |s: &str| -> Result<u16, String> {
+        match s.trim().parse::<u16>() {
+            Ok(port) if port > 0 => Ok(port),
+            _ => Err(format!("端口范围应该在1-65535: {}", s.trim())),
+        }
+    };
+
+    let mut ports = BTreeSet::new();
+    for entry in input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+    {
+        match entry.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_port(start)?, parse_port(end)?);
+                if start > end {
+                    return Err(format!("无效的端口范围: {}", entry));
+                }
+                ports.extend(start..=end);
+            }
+            None => {
+                ports.insert(parse_port(entry)?);
+            }
+        }
+    }
+    if ports.is_empty() {
+        return Err("请输入端口".to_string());
+    }
+    Ok(ports.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::network_util::stand_in::{Behavior, StandIn};
+    use crate::utils::network_util::with_connector;
+
+    fn request(id: &str) -> PortScanRequest {
+        PortScanRequest {
+            id: id.to_string(),
+            hosts: String::new(),
+            ports: String::new(),
+            concurrency: Some(16),
+            timeout_ms: Some(500),
+            report_closed: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_ports() {
+        assert_eq!(
+            parse_ports("22, 80,443 8000-8003,80").unwrap(),
+            vec![22, 80, 443, 8000, 8001, 8002, 8003]
+        );
+        assert!(parse_ports("0").is_err());
+        assert!(parse_ports("65536").is_err());
+        assert!(parse_ports("100-90").is_err());
+        assert!(parse_ports("").is_err());
+    }
+
+    #[test]
+    fn test_parse_hosts() {
+        let hosts = parse_hosts("10.0.0.0/30, example.com\n10.0.0.1").unwrap();
+        assert_eq!(
+            hosts,
+            vec![
+                "10.0.0.0",
+                "10.0.0.1",
+                "10.0.0.2",
+                "10.0.0.3",
+                "example.com"
+            ]
+        );
+
+        assert_eq!(parse_hosts("10.0.0.5/28").unwrap().len(), 16);
+        assert_eq!(parse_hosts("10.0.0.5/28").unwrap()[0], "10.0.0.0");
+        assert_eq!(parse_hosts("192.168.1.1/32").unwrap(), vec!["192.168.1.1"]);
+        assert_eq!(
+            parse_hosts("fd00::/126").unwrap(),
+            vec!["fd00::", "fd00::1", "fd00::2", "fd00::3"]
+        );
+        assert!(parse_hosts("10.0.0.0/8").is_err());
+        assert!(parse_hosts("10.0.0.0/33").is_err());
+        assert!(parse_hosts(" ").is_err());
+    }
+
+    /// 扫描本地监听端口与已关闭端口
+    #[tokio::test]
+    async fn test_run_scan_local() {
+        let connector = StandIn::new()
+            .await
+            .route("192.0.2.1:22", Behavior::Accept)
+            .route("192.0.2.1:443", Behavior::BlackHole)
+            .route("192.0.2.1:8080", Behavior::Unreachable)
+            .build();
+
+        let mut streamed = Vec::new();
+        let summary = with_connector(
+            connector,
+            run_scan(
+                &request("local"),
+                vec!["192.0.2.1".to_string()],
+                vec![22, 80, 443, 8080],
+                CancellationToken::new(),
+                |result, _| streamed.push(result.clone()),
+            ),
+        )
+        .await;
+
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.scanned, 4);
+        assert_eq!(summary.open, 1);
+        assert_eq!(summary.closed, 1);
+        assert_eq!(summary.timeout, 1);
+        assert_eq!(summary.failed, 1);
+        assert!(!summary.cancelled);
+        assert_eq!(summary.open_ports[0].port, 22);
+        assert_eq!(streamed.len(), 4);
+    }
+
+    /// 异常退出的任务也计入已扫描数量
+    #[tokio::test]
+    async fn test_joined_result_panicked_task() {
+        let mut join_set = JoinSet::new();
+        let mut targets = HashMap::new();
+        let handle = join_set.spawn(async { panic!("probe") });
+        targets.insert(handle.id(), ("10.0.0.1".to_string(), 22));
+        let result = joined_result(
+            "panic",
+            &mut targets,
+            join_set.join_next_with_id().await.unwrap(),
+        );
+        assert_eq!((result.host.as_str(), result.port), ("10.0.0.1", 22));
+        assert_eq!(result.check.status, PortStatus::Error);
+        assert!(targets.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_scan_cancelled() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let summary = run_scan(
+            &request("cancelled"),
+            vec!["127.0.0.1".to_string()],
+            (1..=1000).collect(),
+            token,
+            |_, _| {},
+        )
+        .await;
+
+        assert!(summary.cancelled);
+        assert!(summary.scanned < summary.total);
+    }
+}
@@ -0,0 +1,608 @@
+use crate::utils::network_util::{
+    connect_with_proxy, CheckPortResult, DEFAULT_CONNECT_TIMEOUT_MS, MAX_CONNECT_TIMEOUT_MS,
+};
+use serde::Serialize;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// 等待服务端主动发送 banner 或响应的最长时间
+const MAX_BANNER_WAIT: Duration = Duration::from_millis(2_000);
+/// 收到首个数据包后，等待后续数据的空闲时间
+const READ_IDLE: Duration = Duration::from_millis(150);
+/// 读取响应的最大字节数
+const MAX_BANNER_BYTES: usize = 4_096;
+/// 返回给前端的 banner 最大字符数
+const MAX_BANNER_CHARS: usize = 1_024;
+/// 通常只提供 TLS 服务的端口
+const TLS_PORTS: &[u16] = &[443, 465, 636, 853, 989, 990, 993, 995, 5986, 8443];
+
+/// 识别服务所用的探测方式
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceProbe {
+    /// 服务端连接后主动发送的 banner
+    Banner,
+    /// HTTP HEAD 请求
+    Http,
+    /// TLS ClientHello
+    Tls,
+    /// Redis PING
+    Redis,
+    /// PostgreSQL SSLRequest
+    Postgres,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ServiceFingerprint {
+    #[serde(flatten)]
+    check: CheckPortResult,
+    /// 识别出的服务，如 ssh、http、mysql
+    service: Option<String>,
+    /// 服务版本或服务端自报的软件信息
+    version: Option<String>,
+    probe: Option<ServiceProbe>,
+    /// 服务端返回的原始内容（不可打印字符替换为 `.`）
+    banner: Option<String>,
+}
+
+/// 识别结果
+struct Detected {
+    service: &'static str,
+    version: Option<String>,
+}
+
+impl Detected {
+    fn new(service: &'static str, version: Option<String>) -> Self {
+        Self { service, version }
+    }
+}
+
+/// 连接端口并识别监听的服务
+/// - timeout_ms: 连接超时时间（毫秒），默认 5000；等待响应的时间不超过 2 秒
+/// - proxy: 代理地址，为空时使用全局代理，`direct` 表示直接连接
+#[tauri::command]
+pub async fn grab_banner(
+    host: String,
+    port: u16,
+    timeout_ms: Option<u64>,
+    proxy: Option<String>,
+) -> Result<ServiceFingerprint, String> {
+    let timeout_ms = timeout_ms.unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS);
+    if timeout_ms == 0 || timeout_ms > MAX_CONNECT_TIMEOUT_MS {
+        return Err(format!(
+            "超时时间应在 1-{} 毫秒之间",
+            MAX_CONNECT_TIMEOUT_MS
+        ));
+    }
+    let timeout = Duration::from_millis(timeout_ms);
+    Ok(fingerprint(&host, port, timeout, proxy.as_deref()).await)
+}
+
+/// 先读取服务端主动发送的 banner，没有时依次发送各协议的问候报文
+pub async fn fingerprint(
+    host: &str,
+    port: u16,
+    timeout: Duration,
+    proxy: Option<&str>,
+) -> ServiceFingerprint {
+    let (check, stream) = connect_with_proxy(host, port, timeout, proxy).await;
+    let mut result = ServiceFingerprint {
+        check,
+        service: None,
+        version: None,
+        probe: None,
+        banner: None,
+    };
+    let Some(mut stream) = stream else {
+        return result;
+    };
+    let wait = timeout.min(MAX_BANNER_WAIT);
+
+    let banner = read_response(&mut stream, wait).await;
+    if !banner.is_empty() {
+        let (detected, banner) = identify_banner(&mut stream, banner, wait).await;
+        result.probe = Some(ServiceProbe::Banner);
+        result.banner = Some(printable(&banner));
+        if let Some(detected) = detected {
+            result.service = Some(detected.service.to_string());
+            result.version = detected.version;
+        }
+        return result;
+    }
+
+    // 服务端在等待客户端先发言，首个探测复用当前连接，之后每个探测使用新连接
+    let mut stream = Some(stream);
+    for probe in probe_order(port) {
+        let mut conn = match stream.take() {
+            Some(conn) => conn,
+            None => match connect_with_proxy(host, port, timeout, proxy).await.1 {
+                Some(conn) => conn,
+                None => break,
+            },
+        };
+        let (detected, response) = run_probe(&mut conn, probe, host, wait).await;
+        if let Some(detected) = detected {
+            result.probe = Some(probe);
+            result.service = Some(detected.service.to_string());
+            result.version = detected.version;
+            result.banner = Some(printable(&response));
+            return result;
+        }
+        if !response.is_empty() && result.banner.is_none() {
+            result.banner = Some(printable(&response));
+        }
+    }
+    result
+}
+
+/// 按端口猜测最可能的协议，决定探测顺序
+fn probe_order(port: u16) -> Vec<ServiceProbe> {
+    let hint = match port {
+        p if TLS_PORTS.contains(&p) => ServiceProbe::Tls,
+        6379 => ServiceProbe::Redis,
+        5432 => ServiceProbe::Postgres,
+        _ => ServiceProbe::Http,
+    };
+    let mut order = vec![hint];
+    for probe in [
+        ServiceProbe::Http,
+        ServiceProbe::Tls,
+        ServiceProbe::Redis,
+        ServiceProbe::Postgres,
+    ] {
+        if probe != hint {
+            order.push(probe);
+        }
+    }
+    order
+}
+
+/// 读取响应：等待首个数据包，随后读到连接空闲、关闭或达到上限为止
+async fn read_response(stream: &mut TcpStream, wait: Duration) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 1024];
+    let mut idle = wait;
+    while data.len() < MAX_BANNER_BYTES {
+        match tokio::time::timeout(idle, stream.read(&mut buf)).await {
+            Ok(Ok(n)) if n > 0 => data.extend_from_slice(&buf[..n]),
+            _ => break,
+        }
+        idle = READ_IDLE;
+    }
+    data.truncate(MAX_BANNER_BYTES);
+    data
+}
+
+async fn exchange(stream: &mut TcpStream, request: &[u8], wait: Duration) -> Vec<u8> {
+    if stream.write_all(request).await.is_err() {
+        return Vec::new();
+    }
+    read_response(stream, wait).await
+}
+
+/// 识别服务端主动发送的 banner，必要时继续交互补充信息
+async fn identify_banner(
+    stream: &mut TcpStream,
+    mut banner: Vec<u8>,
+    wait: Duration,
+) -> (Option<Detected>, Vec<u8>) {
+    if let Some(detected) = parse_mysql_handshake(&banner) {
+        return (Some(detected), banner);
+    }
+
+    let text = String::from_utf8_lossy(&banner).into_owned();
+    let first_line = text.lines().next().unwrap_or("").trim().to_string();
+    let rest_of = |prefix: &str| {
+        let rest = first_line[prefix.len()..].trim();
+        (!rest.is_empty()).then(|| rest.to_string())
+    };
+
+    let detected = if let Some(version) = first_line.strip_prefix("SSH-") {
+        // SSH-2.0-OpenSSH_9.6p1 Ubuntu-3
+        let software = version.split_once('-').map(|(_, s)| s.to_string());
+        Some(Detected::new("ssh", software))
+    } else if first_line.starts_with("220") {
+        if first_line.to_ascii_uppercase().contains("FTP") {
+            Some(Detected::new("ftp", rest_of("220")))
+        } else {
+            let reply = exchange(stream, b"EHLO lin-toolbox\r\n", wait).await;
+            banner.extend_from_slice(&reply);
+            Some(Detected::new("smtp", rest_of("220")))
+        }
+    } else if first_line.starts_with("+OK") {
+        Some(Detected::new("pop3", rest_of("+OK")))
+    } else if first_line.starts_with("* OK") {
+        Some(Detected::new("imap", rest_of("* OK")))
+    } else if first_line.starts_with("RFB ") {
+        Some(Detected::new("vnc", Some(first_line.clone())))
+    } else {
+        None
+    };
+    (detected, banner)
+}
+
+/// MySQL 初始握手包：3 字节长度 + 序号 0 + 协议版本 10 + 以 0 结尾的服务端版本
+fn parse_mysql_handshake(data: &[u8]) -> Option<Detected> {
+    if data.len() < 6 || data[3] != 0 {
+        return None;
+    }
+    // 包长度来自对端，不能信任
+    let len = u32::from_le_bytes([data[0], data[1], data[2], 0]) as usize;
+    if len < 1 || len + 4 > data.len() {
+        return None;
+    }
+    match data[4] {
+        0x0A => {
+            let payload = data.get(5..4 + len)?;
+            let end = payload.iter().position(|b| *b == 0)?;
+            let version = String::from_utf8_lossy(&payload[..end]).into_owned();
+            let service = if version.contains("MariaDB") {
+                "mariadb"
+            } else {
+                "mysql"
+            };
+            Some(Detected::new(service, Some(version)))
+        }
+        // 错误包，如 "Host is not allowed to connect"
+        0xFF if len > 3 => Some(Detected::new("mysql", None)),
+        _ => None,
+    }
+}
+
+/// 发送探测报文并识别响应
+async fn run_probe(
+    stream: &mut TcpStream,
+    probe: ServiceProbe,
+    host: &str,
+    wait: Duration,
+) -> (Option<Detected>, Vec<u8>) {
+    match probe {
+        ServiceProbe::Http => {
+            let request = format!(
+                "HEAD / HTTP/1.0\r\nHost: {}\r\nUser-Agent: lin-toolbox\r\n\r\n",
+                host
+            );
+            let response = exchange(stream, request.as_bytes(), wait).await;
+            (parse_http_response(&response), response)
+        }
+        ServiceProbe::Tls => {
+            let response = exchange(stream, &client_hello(host), wait).await;
+            (parse_server_hello(&response), response)
+        }
+        ServiceProbe::Redis => {
+            let mut response = exchange(stream, b"*1\r\n$4\r\nPING\r\n", wait).await;
+            let detected = if response.starts_with(b"+PONG") {
+                let info = exchange(stream, b"*2\r\n$4\r\nINFO\r\n$6\r\nserver\r\n", wait).await;
+                let version = String::from_utf8_lossy(&info)
+                    .lines()
+                    .find_map(|line| line.strip_prefix("redis_version:").map(str::to_string));
+                response.extend_from_slice(&info);
+                Some(Detected::new("redis", version))
+            } else if response.starts_with(b"-NOAUTH") || response.starts_with(b"-DENIED") {
+                Some(Detected::new("redis", None))
+            } else {
+                None
+            };
+            (detected, response)
+        }
+        ServiceProbe::Postgres => {
+            // SSLRequest：长度 8 + 请求码 80877103，服务端只回复 'S' 或 'N'
+            let mut request = 8u32.to_be_bytes().to_vec();
+            request.extend_from_slice(&80877103u32.to_be_bytes());
+            let response = exchange(stream, &request, wait).await;
+            let detected = match response.as_slice() {
+                b"S" | b"N" => Some(Detected::new("postgresql", None)),
+                _ => None,
+            };
+            (detected, response)
+        }
+        ServiceProbe::Banner => (None, Vec::new()),
+    }
+}
+
+fn parse_http_response(response: &[u8]) -> Option<Detected> {
+    let text = String::from_utf8_lossy(response);
+    if !text.starts_with("HTTP/") {
+        return None;
+    }
+    let server = text.lines().skip(1).find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.eq_ignore_ascii_case("server")
+            .then(|| value.trim().to_string())
+    });
+    Some(Detected::new("http", server))
+}
+
+/// 构造 TLS ClientHello，同时声明支持 TLS 1.2 与 TLS 1.3
+fn client_hello(host: &str) -> Vec<u8> {
+    fn extension(kind: u16, data: &[u8]) -> Vec<u8> {
+        let mut out = kind.to_be_bytes().to_vec();
+        out.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        out.extend_from_slice(data);
+        out
+    }
+    fn u16_list(items: &[u16]) -> Vec<u8> {
+        let mut out = ((items.len() * 2) as u16).to_be_bytes().to_vec();
+        out.extend(items.iter().flat_map(|i| i.to_be_bytes()));
+        out
+    }
+
+    let mut extensions = Vec::new();
+    let host = host.trim_matches(['[', ']']);
+    if host.parse::<std::net::IpAddr>().is_err() {
+        let name = host.as_bytes();
+        let mut sni = ((name.len() + 3) as u16).to_be_bytes().to_vec();
+        sni.push(0);
+        sni.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        sni.extend_from_slice(name);
+        extensions.extend(extension(0x0000, &sni));
+    }
+    extensions.extend(extension(0x000a, &u16_list(&[0x001d, 0x0017, 0x0018])));
+    extensions.extend(extension(0x000b, &[1, 0]));
+    extensions.extend(extension(
+        0x000d,
+        &u16_list(&[
+            0x0403, 0x0804, 0x0401, 0x0503, 0x0805, 0x0501, 0x0806, 0x0601,
+        ]),
+    ));
+    extensions.extend(extension(0x002b, &[4, 0x03, 0x04, 0x03, 0x03]));
+    extensions.extend(extension(0x002d, &[1, 1]));
+    // x25519 公钥可以是任意 32 字节，这里只用于让服务端回复 ServerHello
+    let mut key_share = vec![0, 36, 0x00, 0x1d, 0, 32];
+    key_share.extend(rand::random::<[u8; 32]>());
+    extensions.extend(extension(0x0033, &key_share));
+
+    let mut hello = vec![0x03, 0x03];
+    hello.extend(rand::random::<[u8; 32]>());
+    hello.push(32);
+    hello.extend(rand::random::<[u8; 32]>());
+    hello.extend(u16_list(&[
+        0x1301, 0x1302, 0x1303, 0xc02b, 0xc02f, 0xc02c, 0xc030, 0xcca9, 0xcca8, 0x009c, 0x009d,
+        0x002f, 0x0035,
+    ]));
+    hello.extend_from_slice(&[1, 0]);
+    hello.extend_from_slice(&(extensions.len() as u16).to_be_bytes());
+    hello.extend(extensions);
+
+    let mut handshake = vec![0x01];
+    handshake.extend_from_slice(&(hello.len() as u32).to_be_bytes()[1..]);
+    handshake.extend(hello);
+
+    let mut record = vec![0x16, 0x03, 0x01];
+    record.extend_from_slice(&(handshake.len() as u16).to_be_bytes());
+    record.extend(handshake);
+    record
+}
+
+/// 解析 ServerHello，返回协商的 TLS 版本与密码套件；收到 TLS 告警时只标记为 tls
+fn parse_server_hello(response: &[u8]) -> Option<Detected> {
+    if response.len() < 5 || response[1] != 0x03 {
+        return None;
+    }
+    match response[0] {
+        0x15 => return Some(Detected::new("tls", None)),
+        0x16 => {}
+        _ => return None,
+    }
+    let body = response.get(5..)?;
+    if *body.first()? != 0x02 {
+        return Some(Detected::new("tls", None));
+    }
+    let mut version = u16::from_be_bytes([*body.get(4)?, *body.get(5)?]);
+    let mut pos = 4 + 2 + 32;
+    pos += 1 + *body.get(pos)? as usize;
+    let cipher = u16::from_be_bytes([*body.get(pos)?, *body.get(pos + 1)?]);
+    pos += 3;
+
+    // supported_versions 扩展中的版本才是 TLS 1.3 实际协商的版本
+    if let Some(len) = body.get(pos..pos + 2) {
+        let end = (pos + 2 + u16::from_be_bytes([len[0], len[1]]) as usize).min(body.len());
+        pos += 2;
+        while pos + 4 <= end {
+            let kind = u16::from_be_bytes([body[pos], body[pos + 1]]);
+            let len = u16::from_be_bytes([body[pos + 2], body[pos + 3]]) as usize;
+            if kind == 0x002b && len == 2 && pos + 6 <= end {
+                version = u16::from_be_bytes([body[pos + 4], body[pos + 5]]);
+            }
+            pos += 4 + len;
+        }
+    }
+    Some(Detected::new(
+        "tls",
+        Some(format!(
+            "{} {}",
+            tls_version_name(version),
+            cipher_suite_name(cipher)
+        )),
+    ))
+}
+
+pub(crate) fn tls_version_name(version: u16) -> String {
+    match version {
+        0x0300 => "SSL 3.0".to_string(),
+        0x0301 => "TLS 1.0".to_string(),
+        0x0302 => "TLS 1.1".to_string(),
+        0x0303 => "TLS 1.2".to_string(),
+        0x0304 => "TLS 1.3".to_string(),
+        v => format!("0x{:04x}", v),
+    }
+}
+
+fn cipher_suite_name(cipher: u16) -> String {
+    match cipher {
+        0x1301 => "TLS_AES_128_GCM_SHA256".to_string(),
+        0x1302 => "TLS_AES_256_GCM_SHA384".to_string(),
+        0x1303 => "TLS_CHACHA20_POLY1305_SHA256".to_string(),
+        0xc02b => "TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256".to_string(),
+        0xc02f => "TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256".to_string(),
+        0xc02c => "TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384".to_string(),
+        0xc030 => "TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384".to_string(),
+        0xcca9 => "TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256".to_string(),
+        0xcca8 => "TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256".to_string(),
+        0x009c => "TLS_RSA_WITH_AES_128_GCM_SHA256".to_string(),
+        0x009d => "TLS_RSA_WITH_AES_256_GCM_SHA384".to_string(),
+        0x002f => "TLS_RSA_WITH_AES_128_CBC_SHA".to_string(),
+        0x0035 => "TLS_RSA_WITH_AES_256_CBC_SHA".to_string(),
+        c => format!("0x{:04x}", c),
+    }
+}
+
+/// 将响应转换为可显示的文本
+fn printable(data: &[u8]) -> String {
+    String::from_utf8_lossy(data)
+        .chars()
+        .map(|c| {
+            if c.is_control() && !matches!(c, '\r' | '\n' | '\t') {
+                '.'
+            } else {
+                c
+            }
+        })
+        .take(MAX_BANNER_CHARS)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::network_util::stand_in::StandIn;
+    use crate::utils::network_util::{with_connector, PortStatus};
+    use tokio::net::TcpListener;
+
+    /// 本地替身服务：连接后发送 `greeting`，之后对每个请求调用 `reply`，返回 None 时断开连接
+    async fn spawn_stub(greeting: &'static [u8], reply: fn(&[u8]) -> Option<Vec<u8>>) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    stream.write_all(greeting).await.unwrap();
+                    let mut buf = [0u8; 2048];
+                    while let Ok(n) = stream.read(&mut buf).await {
+                        match reply(&buf[..n]).filter(|_| n > 0) {
+                            Some(response) => stream.write_all(&response).await.unwrap(),
+                            None => break,
+                        }
+                    }
+                });
+            }
+        });
+        port
+    }
+
+    async fn probe(port: u16) -> ServiceFingerprint {
+        grab_banner("127.0.0.1".to_string(), port, Some(1_000), None)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_banner_ssh_and_smtp() {
+        let port = spawn_stub(b"SSH-2.0-OpenSSH_9.6p1 Ubuntu-3\r\n", |_| None).await;
+        let result = probe(port).await;
+        assert_eq!(result.probe, Some(ServiceProbe::Banner));
+        assert_eq!(result.service.as_deref(), Some("ssh"));
+        assert_eq!(result.version.as_deref(), Some("OpenSSH_9.6p1 Ubuntu-3"));
+
+        let port = spawn_stub(b"220 mail.example.test ESMTP Postfix\r\n", |req| {
+            req.starts_with(b"EHLO")
+                .then(|| b"250-mail.example.test\r\n250 PIPELINING\r\n".to_vec())
+        })
+        .await;
+        let result = probe(port).await;
+        assert_eq!(result.service.as_deref(), Some("smtp"));
+        assert_eq!(
+            result.version.as_deref(),
+            Some("mail.example.test ESMTP Postfix")
+        );
+        assert!(result.banner.unwrap().ends_with("250 PIPELINING"));
+    }
+
+    #[tokio::test]
+    async fn test_banner_mysql_handshake() {
+        const HANDSHAKE: &[u8] = b"\x0f\x00\x00\x00\x0a8.0.36\x00\x08\x00\x00\x00\x00\x00\x00";
+        let port = spawn_stub(HANDSHAKE, |_| None).await;
+        let result = probe(port).await;
+        assert_eq!(result.service.as_deref(), Some("mysql"));
+        assert_eq!(result.version.as_deref(), Some("8.0.36"));
+
+        // 包长度为 0 或版本号没有结尾的 0 时不识别
+        assert!(parse_mysql_handshake(b"\x00\x00\x00\x00\x0a8.0.36\x00").is_none());
+        assert!(parse_mysql_handshake(b"\x02\x00\x00\x00\x0a8.0.36\x00").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_probe_http_redis_postgres() {
+        let port = spawn_stub(b"", |req| {
+            req.starts_with(b"HEAD / HTTP/1.0")
+                .then(|| b"HTTP/1.1 200 OK\r\nServer: nginx/1.25.3\r\n\r\n".to_vec())
+        })
+        .await;
+        let result = probe(port).await;
+        assert_eq!(result.probe, Some(ServiceProbe::Http));
+        assert_eq!(result.version.as_deref(), Some("nginx/1.25.3"));
+
+        // HTTP 与 TLS 探测都会被断开，依次尝试到 Redis
+        let port = spawn_stub(b"", |req| match req {
+            b"*1\r\n$4\r\nPING\r\n" => Some(b"+PONG\r\n".to_vec()),
+            r if r.starts_with(b"*2\r\n$4\r\nINFO") => {
+                Some(b"$40\r\n# Server\r\nredis_version:7.2.4\r\nos:Linux\r\n\r\n".to_vec())
+            }
+            _ => None,
+        })
+        .await;
+        let result = probe(port).await;
+        assert_eq!(result.probe, Some(ServiceProbe::Redis));
+        assert_eq!(result.version.as_deref(), Some("7.2.4"));
+
+        let port = spawn_stub(b"", |req| {
+            (req == b"\x00\x00\x00\x08\x04\xd2\x16\x2f").then(|| b"N".to_vec())
+        })
+        .await;
+        let result = probe(port).await;
+        assert_eq!(result.service.as_deref(), Some("postgresql"));
+    }
+
+    #[tokio::test]
+    async fn test_probe_tls_server_hello() {
+        let port = spawn_stub(b"", |req| {
+            if !req.starts_with(&[0x16, 0x03, 0x01]) {
+                return None;
+            }
+            // ServerHello：版本 0x0303、随机数、空会话ID、TLS_AES_128_GCM_SHA256、supported_versions=TLS 1.3
+            let mut hello = vec![0x03, 0x03];
+            hello.extend([0u8; 32]);
+            hello.extend([0, 0x13, 0x01, 0]);
+            hello.extend([0, 6, 0x00, 0x2b, 0, 2, 0x03, 0x04]);
+            let mut record = vec![0x16, 0x03, 0x03, 0, (hello.len() + 4) as u8, 0x02, 0, 0];
+            record.push(hello.len() as u8);
+            record.extend(hello);
+            Some(record)
+        })
+        .await;
+        let result = probe(port).await;
+        assert_eq!(result.probe, Some(ServiceProbe::Tls));
+        assert_eq!(
+            result.version.as_deref(),
+            Some("TLS 1.3 TLS_AES_128_GCM_SHA256")
+        );
+
+        let hello = client_hello("example.test");
+        assert_eq!(
+            hello.len(),
+            5 + u16::from_be_bytes([hello[3], hello[4]]) as usize
+        );
+        assert!(hello.windows(12).any(|w| w == b"example.test"));
+    }
+
+    #[tokio::test]
+    async fn test_banner_closed_port() {
+        let connector = StandIn::new().await.build();
+        let result = with_connector(connector, probe(22)).await;
+        assert_eq!(result.check.status, PortStatus::Refused);
+        assert!(result.service.is_none() && result.banner.is_none());
+    }
+}
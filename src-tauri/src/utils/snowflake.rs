@@ -1,5 +1,11 @@
+use chrono::{Local, NaiveDateTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 // 定义雪花ID的各部分位数
 const WORKER_ID_BITS: u64 = 5;
@@ -22,10 +28,22 @@ const SEQUENCE_MASK: u64 = (1 << SEQUENCE_BITS) - 1; // 4095
 // 使用一个较近的日期可以延长雪花算法的使用寿命
 const CUSTOM_EPOCH: u64 = 1577836800000;
 
+// 时间戳部分的位数，41 位可以使用约 69 年
+const TIMESTAMP_BITS: u64 = 41;
+
+// 持久化时预留的时间窗口（毫秒），时间戳每越过一次已持久化的上界才写一次文件
+const PERSIST_LEASE_MS: u64 = 1000;
+// 重启后首次生成ID时若时钟落后于已持久化的上界，最多等待的时间（毫秒），超过则拒绝生成直到时钟追上
+const MAX_STARTUP_WAIT_MS: u64 = 5000;
+
 /// 用于保护在多线程环境下共享的状态
 struct SnowflakeState {
     last_timestamp: u64,
     sequence: u64,
+    /// 已写入状态文件的时间戳上界，已发放ID的时间戳均不超过该值
+    persisted_until: u64,
+    /// 从状态文件恢复后尚未生成过ID，首次生成时允许等待时钟追上
+    restored: bool,
 }
 
 /// 雪花ID生成器
@@ -34,6 +52,8 @@ pub struct Snowflake {
     worker_id: u64,
     datacenter_id: u64,
     state: Arc<Mutex<SnowflakeState>>,
+    /// 状态文件路径，为空时不持久化
+    state_file: Option<PathBuf>,
 }
 
 impl Snowflake {
@@ -52,13 +72,52 @@ impl Snowflake {
         let state = Arc::new(Mutex::new(SnowflakeState {
             last_timestamp: 0,
             sequence: 0,
+            persisted_until: 0,
+            restored: false,
         }));
 
         Snowflake {
             worker_id,
             datacenter_id,
             state,
+            state_file: None,
+        }
+    }
+
+    /// 创建一个将最后发放的时间戳持久化到 `state_file` 的雪花ID生成器，保证重启前后ID单调递增
+    ///
+    /// 创建时不阻塞。若首次生成ID时时钟落后于文件中记录的时间戳，落后不超过 5 秒时等待，
+    /// 否则生成器会一直拒绝生成ID，直到时钟追上。
+    /// 状态文件无法读取或内容损坏时，以文件修改时间与当前时间中较晚者加一个持久化窗口作为上界。
+    ///
+    /// # Panics
+    /// 如果 `worker_id` 或 `datacenter_id` 超出范围 (0-31)，则会 panic。
+    pub fn with_state_file(worker_id: u64, datacenter_id: u64, state_file: PathBuf) -> Self {
+        let mut generator = Self::new(worker_id, datacenter_id);
+
+        let persisted = read_state_file(&state_file).unwrap_or_else(|e| {
+            eprintln!("{}, falling back to a conservative timestamp.", e);
+            Some(fallback_timestamp(&state_file))
+        });
+        if let Some(persisted) = persisted {
+            let now = Self::get_time_ms();
+            if persisted > now + MAX_STARTUP_WAIT_MS {
+                eprintln!(
+                    "Clock is {}ms behind persisted snowflake state, refusing to generate id until it catches up.",
+                    persisted - now
+                );
+            }
+
+            let mut state = generator.state.lock().unwrap();
+            state.last_timestamp = persisted;
+            // 置为最大序列号，若仍处于同一毫秒则会等待下一毫秒
+            state.sequence = SEQUENCE_MASK;
+            state.persisted_until = persisted;
+            state.restored = true;
         }
+
+        generator.state_file = Some(state_file);
+        generator
     }
 
     /// 生成下一个唯一的雪花ID
@@ -66,14 +125,14 @@ impl Snowflake {
     /// # Returns
     /// 返回一个 `Result<u64, &'static str>`。
     /// - `Ok(u64)`: 成功生成ID。
-    /// - `Err(&'static str)`: 如果系统时钟回拨，则返回错误。
+    /// - `Err(&'static str)`: 如果系统时钟回拨，或启用持久化时状态文件写入失败，则返回错误。
     pub fn next_id(&self) -> Result<u64, &'static str> {
         // 获取互斥锁，保护状态
         let mut state = self.state.lock().unwrap();
 
         let mut timestamp = Self::get_time_ms();
 
-        // 检测到时钟回拨
+        // 检测到时钟回拨；重启后时钟尚未追上已持久化的上界时同样拒绝，由调用方在锁外等待后重试，见 [`Snowflake::startup_wait`]
         if timestamp < state.last_timestamp {
             return Err("Clock moved backwards. Refusing to generate id.");
         }
@@ -84,15 +143,26 @@ impl Snowflake {
             state.sequence = (state.sequence + 1) & SEQUENCE_MASK;
             // 如果序列号达到最大值（即溢出后变为0），则需要等待下一毫秒
             if state.sequence == 0 {
-                timestamp = self.til_next_millis(state.last_timestamp);
+                timestamp = Self::til_next_millis(state.last_timestamp);
             }
         } else {
             // 如果是新的毫秒，则序列号重置为0
             state.sequence = 0;
         }
 
+        // 时间戳越过已持久化的上界时，先写入新的上界再发放ID
+        if let Some(state_file) = &self.state_file {
+            if timestamp > state.persisted_until {
+                let persisted_until = timestamp + PERSIST_LEASE_MS;
+                write_state_file(state_file, persisted_until)
+                    .map_err(|_| "Failed to persist snowflake state.")?;
+                state.persisted_until = persisted_until;
+            }
+        }
+
         // 更新最后的时间戳
         state.last_timestamp = timestamp;
+        state.restored = false;
 
         // 组合ID的各个部分
         let id = ((timestamp - CUSTOM_EPOCH) << TIMESTAMP_SHIFT)
@@ -103,8 +173,18 @@ impl Snowflake {
         Ok(id)
     }
 
+    /// 重启后时钟略微落后于已持久化的上界时，返回还需等待的时长
+    pub fn startup_wait(&self) -> Option<Duration> {
+        let state = self.state.lock().unwrap();
+        let now = Self::get_time_ms();
+        (state.restored && now < state.last_timestamp)
+            .then(|| state.last_timestamp - now)
+            .filter(|wait| *wait <= MAX_STARTUP_WAIT_MS)
+            .map(Duration::from_millis)
+    }
+
     /// 阻塞直到下一毫秒
-    fn til_next_millis(&self, last_timestamp: u64) -> u64 {
+    fn til_next_millis(last_timestamp: u64) -> u64 {
         let mut timestamp = Self::get_time_ms();
         while timestamp <= last_timestamp {
             // 可以选择 `std::thread::yield_now()` 或短暂 sleep 来避免CPU空转
@@ -123,19 +203,263 @@ impl Snowflake {
     }
 }
 
+/// 读取状态文件中记录的时间戳上界，文件不存在时返回 `None`
+fn read_state_file(path: &Path) -> Result<Option<u64>, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => content
+            .trim()
+            .parse::<u64>()
+            .map(Some)
+            .map_err(|e| format!("Invalid snowflake state file {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!(
+            "Failed to read snowflake state file {}: {}",
+            path.display(),
+            e
+        )),
+    }
+}
+
+/// 状态文件损坏时使用的上界：文件最后写入时记录的值不超过写入时间加一个持久化窗口
+fn fallback_timestamp(path: &Path) -> u64 {
+    let modified = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as u64);
+    modified.max(Snowflake::get_time_ms()) + PERSIST_LEASE_MS
+}
+
+/// 先写临时文件并落盘再重命名，避免写入中途退出或断电导致状态文件损坏
+fn write_state_file(path: &Path, timestamp: u64) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(timestamp.to_string().as_bytes())?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
+/// 无锁雪花ID生成器
+///
+/// 将相对纪元的时间戳与序列号打包进同一个 `AtomicU64`（高位为时间戳，低 12 位为序列号），
+/// 通过 CAS 更新状态，适合批量生成等高并发场景。生成的ID结构与 [`Snowflake`] 完全一致。
+pub struct AtomicSnowflake {
+    worker_id: u64,
+    datacenter_id: u64,
+    /// `(timestamp - CUSTOM_EPOCH) << SEQUENCE_BITS | sequence`
+    state: AtomicU64,
+}
+
+impl AtomicSnowflake {
+    /// 创建一个新的无锁雪花ID生成器
+    ///
+    /// # Panics
+    /// 如果 `worker_id` 或 `datacenter_id` 超出范围 (0-31)，则会 panic。
+    pub fn new(worker_id: u64, datacenter_id: u64) -> Self {
+        if worker_id > MAX_WORKER_ID {
+            panic!("Worker ID must be between 0 and {}", MAX_WORKER_ID);
+        }
+        if datacenter_id > MAX_DATACENTER_ID {
+            panic!("Datacenter ID must be between 0 and {}", MAX_DATACENTER_ID);
+        }
+
+        AtomicSnowflake {
+            worker_id,
+            datacenter_id,
+            state: AtomicU64::new(0),
+        }
+    }
+
+    /// 生成下一个唯一的雪花ID
+    ///
+    /// 返回值语义与 [`Snowflake::next_id`] 相同，时钟回拨时返回错误。
+    pub fn next_id(&self) -> Result<u64, &'static str> {
+        let mut current = self.state.load(Ordering::Acquire);
+        loop {
+            // 先读取状态再读取时间，保证读到的时间不早于该状态写入时的时间
+            let now = Snowflake::get_time_ms();
+            let last_timestamp = (current >> SEQUENCE_BITS) + CUSTOM_EPOCH;
+
+            // 检测到时钟回拨
+            if now < last_timestamp {
+                return Err("Clock moved backwards. Refusing to generate id.");
+            }
+
+            let next = if now == last_timestamp {
+                // 同一毫秒内序列号已用尽，等待下一毫秒后重新竞争
+                if current & SEQUENCE_MASK == SEQUENCE_MASK {
+                    Snowflake::til_next_millis(last_timestamp);
+                    current = self.state.load(Ordering::Acquire);
+                    continue;
+                }
+                current + 1
+            } else {
+                // 新的毫秒，序列号重置为0
+                (now - CUSTOM_EPOCH) << SEQUENCE_BITS
+            };
+
+            match self.state.compare_exchange_weak(
+                current,
+                next,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    let timestamp = next >> SEQUENCE_BITS;
+                    let sequence = next & SEQUENCE_MASK;
+                    return Ok((timestamp << TIMESTAMP_SHIFT)
+                        | (self.datacenter_id << DATACENTER_ID_SHIFT)
+                        | (self.worker_id << WORKER_ID_SHIFT)
+                        | sequence);
+                }
+                // 其他线程抢先更新了状态，使用最新值重试
+                Err(actual) => current = actual,
+            }
+        }
+    }
+}
+
 #[tauri::command]
-pub fn generate_snowflake_id(
-    generator: tauri::State<'_, Arc<Snowflake>>
+pub async fn generate_snowflake_id(
+    generator: tauri::State<'_, Arc<Snowflake>>,
 ) -> Result<String, String> {
-    // 调用 next_id()
-    match generator.next_id() {
-        Ok(id) => {
-            Ok(id.to_string())
+    loop {
+        // 调用 next_id()
+        match generator.next_id() {
+            Ok(id) => return Ok(id.to_string()),
+            // 重启后时钟尚未追上持久化的上界，不持有锁地等待后重试
+            Err(e) => match generator.startup_wait() {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => return Err(e.to_string()),
+            },
+        }
+    }
+}
+
+/// 雪花ID的位布局，未指定的字段使用本模块的默认常量
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct SnowflakeLayout {
+    /// 纪元（UNIX 毫秒）
+    pub epoch: u64,
+    pub timestamp_bits: u64,
+    pub datacenter_id_bits: u64,
+    pub worker_id_bits: u64,
+    pub sequence_bits: u64,
+}
+
+impl Default for SnowflakeLayout {
+    fn default() -> Self {
+        Self {
+            epoch: CUSTOM_EPOCH,
+            timestamp_bits: TIMESTAMP_BITS,
+            datacenter_id_bits: DATACENTER_ID_BITS,
+            worker_id_bits: WORKER_ID_BITS,
+            sequence_bits: SEQUENCE_BITS,
+        }
+    }
+}
+
+impl SnowflakeLayout {
+    /// 位数由用户传入，相加溢出时返回 None
+    fn timestamp_shift(&self) -> Option<u64> {
+        self.sequence_bits
+            .checked_add(self.worker_id_bits)?
+            .checked_add(self.datacenter_id_bits)
+    }
+
+    /// 计算 `[start, end]`（UNIX 毫秒，闭区间）内可能出现的最小与最大ID
+    pub fn id_range(&self, start: u64, end: u64) -> Result<(u64, u64), String> {
+        let shift = self.timestamp_shift();
+        let total = shift.and_then(|shift| self.timestamp_bits.checked_add(shift));
+        // 保留符号位，保证ID可以存入有符号的 BIGINT 字段
+        let shift = match (shift, total) {
+            (Some(shift), Some(total)) if self.timestamp_bits != 0 && total <= 63 => shift,
+            _ => return Err("布局总位数应在 1-63 之间".to_string()),
+        };
+        if start > end {
+            return Err("开始时间不能晚于结束时间".to_string());
         }
-        Err(e) => Err(e.to_string()),
+        if start < self.epoch {
+            return Err("开始时间不能早于纪元".to_string());
+        }
+        let max_offset = (1u64 << self.timestamp_bits) - 1;
+        if end - self.epoch > max_offset {
+            return Err("结束时间超出时间戳位数可表示的范围".to_string());
+        }
+
+        let min_id = (start - self.epoch) << shift;
+        let max_id = ((end - self.epoch) << shift) | ((1u64 << shift) - 1);
+        Ok((min_id, max_id))
     }
 }
 
+/// 时间范围对应的雪花ID范围
+#[derive(Serialize)]
+pub struct SnowflakeIdRange {
+    /// ID使用字符串返回，避免前端精度丢失
+    min_id: String,
+    max_id: String,
+    start_timestamp: u64,
+    end_timestamp: u64,
+    where_clause: Option<String>,
+}
+
+/// 根据时间范围计算雪花ID的闭区间，用于按ID范围查询数据库
+/// - start/end: 本地时间，格式 "YYYY-MM-DD HH:mm:ss[.SSS]"，结束时间所在毫秒包含在内
+/// - column: 指定列名时额外生成 `WHERE column BETWEEN min AND max` 语句
+#[tauri::command]
+pub fn snowflake_id_range(
+    start: String,
+    end: String,
+    layout: Option<SnowflakeLayout>,
+    column: Option<String>,
+) -> Result<SnowflakeIdRange, String> {
+    let start_timestamp = parse_local_millis(&start)?;
+    let end_timestamp = parse_local_millis(&end)?;
+    let (min_id, max_id) = layout
+        .unwrap_or_default()
+        .id_range(start_timestamp, end_timestamp)?;
+
+    let where_clause = match column.as_deref().map(str::trim) {
+        Some(column) if !column.is_empty() => {
+            if !column
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+            {
+                return Err(format!("无效的列名: {}", column));
+            }
+            Some(format!(
+                "WHERE {} BETWEEN {} AND {}",
+                column, min_id, max_id
+            ))
+        }
+        _ => None,
+    };
+
+    Ok(SnowflakeIdRange {
+        min_id: min_id.to_string(),
+        max_id: max_id.to_string(),
+        start_timestamp,
+        end_timestamp,
+        where_clause,
+    })
+}
+
+/// 将本地时间字符串解析为 UNIX 毫秒
+fn parse_local_millis(datetime: &str) -> Result<u64, String> {
+    let naive = NaiveDateTime::parse_from_str(datetime.trim(), "%Y-%m-%d %H:%M:%S%.f")
+        .map_err(|e| format!("Failed to parse date time: {}", e))?;
+    let local = Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or_else(|| "无效的本地时间".to_string())?;
+    u64::try_from(local.timestamp_millis()).map_err(|_| "时间不能早于 1970-01-01".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,7 +566,11 @@ mod tests {
 
         // 使用 HashSet 验证唯一性
         let id_set: HashSet<_> = all_ids.into_iter().collect();
-        assert_eq!(id_set.len(), total_ids, "Duplicate IDs were generated in a multi-threaded context.");
+        assert_eq!(
+            id_set.len(),
+            total_ids,
+            "Duplicate IDs were generated in a multi-threaded context."
+        );
     }
 
     /// 测试跨毫秒边界时序列号重置
@@ -287,6 +615,262 @@ mod tests {
         // 再次尝试生成ID，此时当前时间会小于 `last_timestamp`
         let result = generator.next_id();
         assert!(result.is_err());
-        assert_eq!(result.err().unwrap(), "Clock moved backwards. Refusing to generate id.");
+        assert_eq!(
+            result.err().unwrap(),
+            "Clock moved backwards. Refusing to generate id."
+        );
+    }
+
+    /// 测试无锁生成器的ID结构
+    #[test]
+    fn test_atomic_id_deconstruction() {
+        let generator = AtomicSnowflake::new(7, 9);
+        let id = generator.next_id().unwrap();
+
+        let decoded_worker_id = (id >> WORKER_ID_SHIFT) & MAX_WORKER_ID;
+        let decoded_datacenter_id = (id >> DATACENTER_ID_SHIFT) & MAX_DATACENTER_ID;
+        let timestamp = (id >> TIMESTAMP_SHIFT) + CUSTOM_EPOCH;
+        assert_eq!(id & SEQUENCE_MASK, 0);
+        assert_eq!(decoded_worker_id, 7);
+        assert_eq!(decoded_datacenter_id, 9);
+        assert!(timestamp <= Snowflake::get_time_ms());
+    }
+
+    /// 测试无锁生成器单线程下ID单调递增
+    #[test]
+    fn test_atomic_monotonic_single_thread() {
+        let generator = AtomicSnowflake::new(2, 2);
+        let mut last = generator.next_id().unwrap();
+        for _ in 0..50_000 {
+            let id = generator.next_id().unwrap();
+            assert!(id > last, "ID not increasing: {} -> {}", last, id);
+            last = id;
+        }
+    }
+
+    /// 测试无锁生成器多线程生成ID的唯一性
+    #[test]
+    fn test_atomic_uniqueness_multi_thread() {
+        let generator = Arc::new(AtomicSnowflake::new(3, 3));
+        let num_threads = 10;
+        let ids_per_thread = 10_000;
+        let total_ids = num_threads * ids_per_thread;
+
+        let handles: Vec<_> = (0..num_threads)
+            .map(|_| {
+                let generator_clone = Arc::clone(&generator);
+                thread::spawn(move || {
+                    (0..ids_per_thread)
+                        .map(|_| generator_clone.next_id().unwrap())
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let mut id_set = HashSet::with_capacity(total_ids);
+        for handle in handles {
+            id_set.extend(handle.join().unwrap());
+        }
+        assert_eq!(
+            id_set.len(),
+            total_ids,
+            "Duplicate IDs were generated in a multi-threaded context."
+        );
+    }
+
+    /// 模拟时钟回拨，测试无锁生成器是否返回错误
+    #[test]
+    fn test_atomic_clock_moving_backwards() {
+        let generator = AtomicSnowflake::new(5, 5);
+        let future_timestamp = Snowflake::get_time_ms() + 100;
+        generator.state.store(
+            (future_timestamp - CUSTOM_EPOCH) << SEQUENCE_BITS,
+            Ordering::Release,
+        );
+
+        let result = generator.next_id();
+        assert_eq!(
+            result.err().unwrap(),
+            "Clock moved backwards. Refusing to generate id."
+        );
+    }
+
+    /// 测试默认布局下时间范围与生成的ID一致
+    #[test]
+    fn test_id_range_contains_generated_id() {
+        let now = Snowflake::get_time_ms();
+        let (min_id, max_id) = SnowflakeLayout::default()
+            .id_range(now - 1000, now + 1000)
+            .unwrap();
+        let id = Snowflake::new(31, 31).next_id().unwrap();
+        assert!(min_id <= id && id <= max_id);
+
+        // 同一毫秒的范围覆盖该毫秒内全部的数据中心、机器与序列号
+        let (min_id, max_id) = SnowflakeLayout::default().id_range(now, now).unwrap();
+        assert_eq!(min_id, (now - CUSTOM_EPOCH) << TIMESTAMP_SHIFT);
+        assert_eq!(max_id - min_id, (1 << TIMESTAMP_SHIFT) - 1);
+    }
+
+    #[test]
+    fn test_id_range_invalid() {
+        let layout = SnowflakeLayout::default();
+        assert!(layout.id_range(CUSTOM_EPOCH + 2, CUSTOM_EPOCH + 1).is_err());
+        assert!(layout.id_range(CUSTOM_EPOCH - 1, CUSTOM_EPOCH).is_err());
+        assert!(layout
+            .id_range(CUSTOM_EPOCH, CUSTOM_EPOCH + (1 << TIMESTAMP_BITS))
+            .is_err());
+
+        let layout = SnowflakeLayout {
+            timestamp_bits: 42,
+            ..SnowflakeLayout::default()
+        };
+        assert!(layout.id_range(CUSTOM_EPOCH, CUSTOM_EPOCH).is_err());
+
+        // 位数相加溢出
+        let layout = SnowflakeLayout {
+            timestamp_bits: u64::MAX,
+            sequence_bits: 1,
+            ..SnowflakeLayout::default()
+        };
+        assert!(layout.id_range(CUSTOM_EPOCH, CUSTOM_EPOCH).is_err());
+        let layout = SnowflakeLayout {
+            worker_id_bits: u64::MAX,
+            ..SnowflakeLayout::default()
+        };
+        assert!(layout.id_range(CUSTOM_EPOCH, CUSTOM_EPOCH).is_err());
     }
-}
\ No newline at end of file
+
+    /// 测试自定义布局（Twitter 纪元）与 WHERE 子句
+    #[test]
+    fn test_snowflake_id_range_command() {
+        let layout = SnowflakeLayout {
+            epoch: 1288834974657,
+            ..SnowflakeLayout::default()
+        };
+        let start = "2024-01-01 00:00:00".to_string();
+        let end = "2024-01-01 23:59:59.999".to_string();
+        let range = snowflake_id_range(
+            start.clone(),
+            end.clone(),
+            Some(layout),
+            Some("t.id".to_string()),
+        )
+        .unwrap();
+
+        let expected_start = parse_local_millis(&start).unwrap();
+        assert_eq!(range.start_timestamp, expected_start);
+        assert_eq!(range.end_timestamp, expected_start + 86_399_999);
+        assert_eq!(
+            range.min_id,
+            ((expected_start - 1288834974657) << 22).to_string()
+        );
+        assert_eq!(
+            range.where_clause.unwrap(),
+            format!("WHERE t.id BETWEEN {} AND {}", range.min_id, range.max_id)
+        );
+
+        assert!(snowflake_id_range(
+            start.clone(),
+            end.clone(),
+            None,
+            Some("id; drop".to_string())
+        )
+        .is_err());
+        assert!(snowflake_id_range(start, end, None, None)
+            .unwrap()
+            .where_clause
+            .is_none());
+    }
+
+    fn temp_state_file(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("lin_toolbox_{}_{}.state", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    /// 测试重启后生成的ID大于重启前的ID
+    #[test]
+    fn test_state_file_survives_restart() {
+        let path = temp_state_file("restart");
+
+        let generator = Snowflake::with_state_file(6, 6, path.clone());
+        let last_id = (0..1000)
+            .map(|_| generator.next_id().unwrap())
+            .max()
+            .unwrap();
+        let persisted = read_state_file(&path).unwrap().unwrap();
+        assert!(persisted >= (last_id >> TIMESTAMP_SHIFT) + CUSTOM_EPOCH);
+        drop(generator);
+
+        let restarted = Snowflake::with_state_file(6, 6, path.clone());
+        let id = next_id_after_wait(&restarted);
+        assert!((id >> TIMESTAMP_SHIFT) + CUSTOM_EPOCH > persisted);
+        assert!(id > last_id);
+        assert_eq!(id & SEQUENCE_MASK, 0);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    /// 与 generate_snowflake_id 相同，在锁外等待时钟追上后重试
+    fn next_id_after_wait(generator: &Snowflake) -> u64 {
+        loop {
+            match generator.next_id() {
+                Ok(id) => return id,
+                Err(e) => std::thread::sleep(generator.startup_wait().expect(e)),
+            }
+        }
+    }
+
+    /// 时钟大幅落后于持久化的时间戳时拒绝生成ID
+    #[test]
+    fn test_state_file_refuses_when_clock_far_behind() {
+        let path = temp_state_file("behind");
+        write_state_file(&path, Snowflake::get_time_ms() + 60_000).unwrap();
+
+        let generator = Snowflake::with_state_file(7, 7, path.clone());
+        assert_eq!(
+            generator.next_id().err().unwrap(),
+            "Clock moved backwards. Refusing to generate id."
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_state_file_invalid_content() {
+        let path = temp_state_file("invalid");
+        fs::write(&path, "not a timestamp").unwrap();
+
+        // 损坏的状态文件不阻止启动，首次生成的ID晚于当前时间加一个持久化窗口
+        let start = Snowflake::get_time_ms();
+        let generator = Snowflake::with_state_file(8, 8, path.clone());
+        let id = next_id_after_wait(&generator);
+        assert!((id >> TIMESTAMP_SHIFT) + CUSTOM_EPOCH > start + PERSIST_LEASE_MS);
+        assert!(read_state_file(&path).unwrap().is_some());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    /// 创建时不等待，时钟略微落后时在首次生成ID时等待追上
+    #[test]
+    fn test_state_file_waits_lazily() {
+        let path = temp_state_file("lazy");
+        let persisted = Snowflake::get_time_ms() + 300;
+        write_state_file(&path, persisted).unwrap();
+
+        let start = std::time::Instant::now();
+        let generator = Snowflake::with_state_file(9, 9, path.clone());
+        assert!(start.elapsed() < Duration::from_millis(200));
+        // 时钟追上之前不阻塞，而是报错并给出需要等待的时长
+        assert!(generator.next_id().is_err());
+        let wait = generator.startup_wait().unwrap();
+        assert!(wait <= Duration::from_millis(300));
+        std::thread::sleep(wait);
+        let id = generator.next_id().unwrap();
+        assert!((id >> TIMESTAMP_SHIFT) + CUSTOM_EPOCH > persisted);
+        assert_eq!(generator.startup_wait(), None);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
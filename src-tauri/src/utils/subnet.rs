@@ -0,0 +1,580 @@
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// 单次拆分最多返回的子网数量
+const MAX_SUBNETS: u128 = 1_024;
+/// 汇总与重叠检测最多处理的网段数量
+const MAX_RANGES: usize = 1_000;
+/// `::/0` 的地址数量 2^128，超出 u128 范围
+const IPV6_ADDRESS_COUNT: &str = "340282366920938463463374607431768211456";
+
+/// 网段信息，地址数量可能超出 JS 安全整数范围，使用字符串表示
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SubnetInfo {
+    /// 4 或 6
+    version: u8,
+    /// 输入的地址
+    address: String,
+    prefix: u32,
+    /// 规范化后的网段，如 `192.168.1.0/24`
+    cidr: String,
+    netmask: String,
+    /// 反掩码，如 `0.0.0.255`
+    wildcard_mask: String,
+    network: String,
+    /// 广播地址，IPv6 没有广播地址
+    broadcast: Option<String>,
+    first_host: String,
+    last_host: String,
+    total_addresses: String,
+    usable_hosts: String,
+}
+
+/// 拆分方式
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(tag = "by", content = "value", rename_all = "snake_case")]
+pub enum SplitMode {
+    /// 拆分为 N 个子网，N 向上取整到 2 的幂
+    Count(u32),
+    /// 按新的前缀长度拆分
+    Prefix(u32),
+    /// 按每个子网至少容纳的主机数拆分
+    Hosts(u64),
+}
+
+/// 两个网段的关系
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapRelation {
+    Equal,
+    /// 第一个网段包含第二个
+    Contains,
+    /// 第一个网段被第二个包含
+    Contained,
+    /// 部分重叠
+    Partial,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct CidrOverlap {
+    first: String,
+    second: String,
+    relation: OverlapRelation,
+}
+
+/// 同一地址族内的网段，地址统一用 u128 表示
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Cidr {
+    v6: bool,
+    network: u128,
+    prefix: u32,
+}
+
+/// 闭区间地址范围
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct IpRange {
+    v6: bool,
+    start: u128,
+    end: u128,
+}
+
+impl Cidr {
+    fn range(&self) -> IpRange {
+        IpRange {
+            v6: self.v6,
+            start: self.network,
+            end: self.network | host_mask(self.v6, self.prefix),
+        }
+    }
+}
+
+impl std::fmt::Display for Cidr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", to_ip(self.v6, self.network), self.prefix)
+    }
+}
+
+/// 计算网段信息
+/// - input: `192.168.1.10/24`、`192.168.1.10/255.255.255.0`、`192.168.1.10 255.255.255.0` 或单个地址
+#[tauri::command]
+pub fn subnet_info(input: String) -> Result<SubnetInfo, String> {
+    let (address, cidr) = parse_cidr(&input)?;
+    Ok(describe(address, cidr))
+}
+
+/// 拆分网段
+#[tauri::command]
+pub fn split_subnet(input: String, mode: SplitMode) -> Result<Vec<SubnetInfo>, String> {
+    let (_, cidr) = parse_cidr(&input)?;
+    let bits = bits(cidr.v6);
+    let new_prefix = match mode {
+        SplitMode::Count(count) => {
+            if count == 0 {
+                return Err("子网数量应大于 0".to_string());
+            }
+            let count = count
+                .checked_next_power_of_two()
+                .ok_or_else(|| format!("子网数量 {} 超过上限 {}", count, MAX_SUBNETS))?;
+            cidr.prefix + count.trailing_zeros()
+        }
+        SplitMode::Prefix(prefix) => prefix,
+        SplitMode::Hosts(hosts) => {
+            let host_bits = (0..=bits)
+                .find(|host_bits| usable_hosts(cidr.v6, bits - host_bits) >= u128::from(hosts))
+                .ok_or("主机数量过大")?;
+            bits - host_bits
+        }
+    };
+    if new_prefix < cidr.prefix || new_prefix > bits {
+        return Err(format!(
+            "{} 无法拆分为 /{} 的子网",
+            cidr,
+            new_prefix.min(bits + 1)
+        ));
+    }
+    let count = match 1u128.checked_shl(new_prefix - cidr.prefix) {
+        Some(count) if count <= MAX_SUBNETS => count,
+        Some(count) => return Err(format!("子网数量 {} 超过上限 {}", count, MAX_SUBNETS)),
+        None => {
+            return Err(format!(
+                "子网数量 2^{} 超过上限 {}",
+                new_prefix - cidr.prefix,
+                MAX_SUBNETS
+            ))
+        }
+    };
+    // 只有 ::/0 拆分为 /0 时步长为 2^128 而溢出，此时只有一个子网，步长不会被用到
+    let step = host_mask(cidr.v6, new_prefix)
+        .checked_add(1)
+        .unwrap_or_default();
+    Ok((0..count)
+        .map(|i| {
+            let subnet = Cidr {
+                v6: cidr.v6,
+                network: cidr.network + i * step,
+                prefix: new_prefix,
+            };
+            describe(to_ip(subnet.v6, subnet.network), subnet)
+        })
+        .collect())
+}
+
+/// 合并网段列表为最少的 CIDR，支持 CIDR、`起始-结束` 范围与单个地址，IPv4 在前
+#[tauri::command]
+pub fn summarize_cidrs(inputs: Vec<String>) -> Result<Vec<String>, String> {
+    let mut ranges = parse_ranges(&inputs)?;
+    ranges.sort();
+    let mut merged: Vec<IpRange> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            // 重叠或相邻的范围合并
+            Some(last) if last.v6 == range.v6 && range.start <= last.end.saturating_add(1) => {
+                last.end = last.end.max(range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+    Ok(merged
+        .into_iter()
+        .flat_map(range_to_cidrs)
+        .map(|cidr| cidr.to_string())
+        .collect())
+}
+
+/// 检测网段列表中两两重叠的网段
+#[tauri::command]
+pub fn check_cidr_overlap(inputs: Vec<String>) -> Result<Vec<CidrOverlap>, String> {
+    let ranges = parse_ranges(&inputs)?;
+    let names: Vec<&str> = inputs
+        .iter()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let mut overlaps = Vec::new();
+    for (i, a) in ranges.iter().enumerate() {
+        for (j, b) in ranges.iter().enumerate().skip(i + 1) {
+            if a.v6 != b.v6 || a.end < b.start || b.end < a.start {
+                continue;
+            }
+            let relation = if a == b {
+                OverlapRelation::Equal
+            } else if a.start <= b.start && a.end >= b.end {
+                OverlapRelation::Contains
+            } else if b.start <= a.start && b.end >= a.end {
+                OverlapRelation::Contained
+            } else {
+                OverlapRelation::Partial
+            };
+            overlaps.push(CidrOverlap {
+                first: names[i].to_string(),
+                second: names[j].to_string(),
+                relation,
+            });
+        }
+    }
+    Ok(overlaps)
+}
+
+/// 判断地址是否属于网段或 `起始-结束` 范围
+#[tauri::command]
+pub fn ip_in_range(ip: String, range: String) -> Result<bool, String> {
+    let (v6, value) = parse_ip(&ip)?;
+    let range = parse_range(&range)?;
+    Ok(range.v6 == v6 && range.start <= value && value <= range.end)
+}
+
+fn bits(v6: bool) -> u32 {
+    if v6 {
+        128
+    } else {
+        32
+    }
+}
+
+/// 主机位全为 1 的掩码
+fn host_mask(v6: bool, prefix: u32) -> u128 {
+    let host_bits = bits(v6) - prefix;
+    u128::MAX.checked_shr(128 - host_bits).unwrap_or(0)
+}
+
+/// 网络位全为 1 的掩码
+fn net_mask(v6: bool, prefix: u32) -> u128 {
+    let all = if v6 { u128::MAX } else { u128::from(u32::MAX) };
+    all & !host_mask(v6, prefix)
+}
+
+/// 可用主机数，IPv4 的 /31 与 /32 按 RFC 3021 全部可用，IPv6 不保留广播地址
+fn usable_hosts(v6: bool, prefix: u32) -> u128 {
+    let total = host_mask(v6, prefix).saturating_add(1);
+    if v6 || prefix >= 31 {
+        total
+    } else {
+        total - 2
+    }
+}
+
+fn to_ip(v6: bool, value: u128) -> IpAddr {
+    if v6 {
+        IpAddr::V6(Ipv6Addr::from(value))
+    } else {
+        IpAddr::V4(Ipv4Addr::from(value as u32))
+    }
+}
+
+fn parse_ip(input: &str) -> Result<(bool, u128), String> {
+    let input = input.trim();
+    match input.trim_matches(['[', ']']).parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => Ok((false, u128::from(u32::from(ip)))),
+        Ok(IpAddr::V6(ip)) => Ok((true, u128::from(ip))),
+        Err(_) => Err(format!("无效的 IP 地址: {}", input)),
+    }
+}
+
+/// 解析网段，返回输入的地址与规范化后的网段
+fn parse_cidr(input: &str) -> Result<(IpAddr, Cidr), String> {
+    let input = input.trim();
+    let (addr, suffix) = match input.split_once(['/', ' ', '\t']) {
+        Some((addr, suffix)) => (addr, Some(suffix.trim())),
+        None => (input, None),
+    };
+    let (v6, value) = parse_ip(addr)?;
+    let invalid = || format!("无效的前缀长度或掩码: {}", input);
+    let prefix = match suffix {
+        None => bits(v6),
+        Some(mask) if mask.contains(['.', ':']) => {
+            let (mask_v6, mask) = parse_ip(mask)?;
+            let prefix = mask.count_ones();
+            if mask_v6 != v6 || prefix > bits(v6) || net_mask(v6, prefix) != mask {
+                return Err(invalid());
+            }
+            prefix
+        }
+        Some(prefix) => prefix.parse::<u32>().map_err(|_| invalid())?,
+    };
+    if prefix > bits(v6) {
+        return Err(invalid());
+    }
+    let cidr = Cidr {
+        v6,
+        network: value & net_mask(v6, prefix),
+        prefix,
+    };
+    Ok((to_ip(v6, value), cidr))
+}
+
+/// 解析网段、`起始-结束` 范围或单个地址
+fn parse_range(input: &str) -> Result<IpRange, String> {
+    let Some((start, end)) = input.split_once('-') else {
+        return Ok(parse_cidr(input)?.1.range());
+    };
+    let (v6, start) = parse_ip(start)?;
+    let (end_v6, end) = parse_ip(end)?;
+    if v6 != end_v6 || start > end {
+        return Err(format!("无效的地址范围: {}", input.trim()));
+    }
+    Ok(IpRange { v6, start, end })
+}
+
+fn parse_ranges(inputs: &[String]) -> Result<Vec<IpRange>, String> {
+    let ranges: Vec<IpRange> = inputs
+        .iter()
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| parse_range(s))
+        .collect::<Result<_, _>>()?;
+    if ranges.is_empty() {
+        return Err("请输入网段".to_string());
+    }
+    if ranges.len() > MAX_RANGES {
+        return Err(format!("网段数量超过上限 {}", MAX_RANGES));
+    }
+    Ok(ranges)
+}
+
+/// 把地址范围拆分为最少的 CIDR
+fn range_to_cidrs(range: IpRange) -> Vec<Cidr> {
+    let bits = bits(range.v6);
+    let mut cidrs = Vec::new();
+    let mut start = range.start;
+    loop {
+        // 从起始地址对齐的最大块开始，缩小到不超过结束地址
+        let mut host_bits = start.trailing_zeros().min(bits);
+        while host_bits > 0
+            && start + (u128::MAX.checked_shr(128 - host_bits).unwrap_or(0)) > range.end
+        {
+            host_bits -= 1;
+        }
+        let cidr = Cidr {
+            v6: range.v6,
+            network: start,
+            prefix: bits - host_bits,
+        };
+        cidrs.push(cidr);
+        let last = cidr.range().end;
+        if last >= range.end {
+            break;
+        }
+        start = last + 1;
+    }
+    cidrs
+}
+
+fn describe(address: IpAddr, cidr: Cidr) -> SubnetInfo {
+    let range = cidr.range();
+    let bits = bits(cidr.v6);
+    // IPv4 除 /31 与 /32 外不包含网络地址与广播地址
+    let (first, last) = if !cidr.v6 && cidr.prefix < 31 {
+        (range.start + 1, range.end - 1)
+    } else {
+        (range.start, range.end)
+    };
+    let (total_addresses, usable_hosts) = if bits - cidr.prefix == 128 {
+        (
+            IPV6_ADDRESS_COUNT.to_string(),
+            IPV6_ADDRESS_COUNT.to_string(),
+        )
+    } else {
+        let total = host_mask(cidr.v6, cidr.prefix) + 1;
+        (
+            total.to_string(),
+            usable_hosts(cidr.v6, cidr.prefix).to_string(),
+        )
+    };
+    let ip = |value: u128| to_ip(cidr.v6, value).to_string();
+    SubnetInfo {
+        version: if cidr.v6 { 6 } else { 4 },
+        address: address.to_string(),
+        prefix: cidr.prefix,
+        cidr: cidr.to_string(),
+        netmask: ip(net_mask(cidr.v6, cidr.prefix)),
+        wildcard_mask: ip(host_mask(cidr.v6, cidr.prefix)),
+        network: ip(range.start),
+        broadcast: (!cidr.v6).then(|| ip(range.end)),
+        first_host: ip(first),
+        last_host: ip(last),
+        total_addresses,
+        usable_hosts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_subnet_info() {
+        let info = subnet_info("192.168.1.77 255.255.255.192".to_string()).unwrap();
+        assert_eq!(info.cidr, "192.168.1.64/26");
+        assert_eq!(info.address, "192.168.1.77");
+        assert_eq!(info.netmask, "255.255.255.192");
+        assert_eq!(info.wildcard_mask, "0.0.0.63");
+        assert_eq!(info.broadcast.as_deref(), Some("192.168.1.127"));
+        assert_eq!(
+            (info.first_host.as_str(), info.last_host.as_str()),
+            ("192.168.1.65", "192.168.1.126")
+        );
+        assert_eq!(
+            (info.total_addresses.as_str(), info.usable_hosts.as_str()),
+            ("64", "62")
+        );
+
+        let p2p = subnet_info("10.0.0.1/31".to_string()).unwrap();
+        assert_eq!(
+            (
+                p2p.first_host.as_str(),
+                p2p.last_host.as_str(),
+                p2p.usable_hosts.as_str()
+            ),
+            ("10.0.0.0", "10.0.0.1", "2")
+        );
+        assert_eq!(
+            subnet_info("0.0.0.0/0".to_string())
+                .unwrap()
+                .total_addresses,
+            "4294967296"
+        );
+
+        let v6 = subnet_info("2001:db8::1/64".to_string()).unwrap();
+        assert_eq!(v6.network, "2001:db8::");
+        assert_eq!(v6.netmask, "ffff:ffff:ffff:ffff::");
+        assert_eq!(v6.last_host, "2001:db8::ffff:ffff:ffff:ffff");
+        assert_eq!(v6.broadcast, None);
+        assert_eq!(v6.total_addresses, "18446744073709551616");
+        assert_eq!(
+            subnet_info("::/0".to_string()).unwrap().usable_hosts,
+            IPV6_ADDRESS_COUNT
+        );
+
+        assert!(subnet_info("10.0.0.0/33".to_string()).is_err());
+        assert!(subnet_info("10.0.0.0/255.0.255.0".to_string()).is_err());
+        assert!(subnet_info("10.0.0.256".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_split_subnet() {
+        let cidrs = |mode| -> Vec<String> {
+            split_subnet("10.0.0.0/24".to_string(), mode)
+                .unwrap()
+                .into_iter()
+                .map(|s| s.cidr)
+                .collect()
+        };
+        assert_eq!(
+            cidrs(SplitMode::Count(3)),
+            [
+                "10.0.0.0/26",
+                "10.0.0.64/26",
+                "10.0.0.128/26",
+                "10.0.0.192/26"
+            ]
+        );
+        assert_eq!(
+            cidrs(SplitMode::Prefix(25)),
+            ["10.0.0.0/25", "10.0.0.128/25"]
+        );
+        // 60 台主机需要 /26（62 个可用地址）
+        assert_eq!(cidrs(SplitMode::Hosts(60)).len(), 4);
+        assert_eq!(cidrs(SplitMode::Hosts(63)).len(), 2);
+
+        let v6 = split_subnet("2001:db8::/48".to_string(), SplitMode::Prefix(50)).unwrap();
+        assert_eq!(v6[3].cidr, "2001:db8:0:c000::/50");
+        assert!(split_subnet("10.0.0.0/24".to_string(), SplitMode::Prefix(23)).is_err());
+        assert!(split_subnet("10.0.0.0/8".to_string(), SplitMode::Prefix(24)).is_err());
+        assert!(split_subnet("10.0.0.0/24".to_string(), SplitMode::Count(0)).is_err());
+
+        let all = |mode| split_subnet("::/0".to_string(), mode);
+        assert_eq!(all(SplitMode::Prefix(0)).unwrap()[0].cidr, "::/0");
+        assert_eq!(all(SplitMode::Count(1)).unwrap().len(), 1);
+        assert_eq!(all(SplitMode::Count(2)).unwrap()[1].cidr, "8000::/1");
+        assert!(all(SplitMode::Prefix(128)).is_err());
+        assert!(all(SplitMode::Hosts(1)).is_err());
+        assert!(all(SplitMode::Count(u32::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_summarize_cidrs() {
+        let summary = summarize_cidrs(strings(&[
+            "10.0.1.0/24",
+            "10.0.0.0/24",
+            "10.0.2.0-10.0.3.255",
+            "10.0.2.5",
+            "",
+            "2001:db8::/33",
+            "2001:db8:8000::/33",
+            "192.168.0.1-192.168.0.6",
+        ]))
+        .unwrap();
+        assert_eq!(
+            summary,
+            [
+                "10.0.0.0/22",
+                "192.168.0.1/32",
+                "192.168.0.2/31",
+                "192.168.0.4/31",
+                "192.168.0.6/32",
+                "2001:db8::/32"
+            ]
+        );
+        assert_eq!(
+            summarize_cidrs(strings(&["0.0.0.0/1", "128.0.0.0/1"])).unwrap(),
+            ["0.0.0.0/0"]
+        );
+        assert_eq!(
+            summarize_cidrs(strings(&["::/0", "::1"])).unwrap(),
+            ["::/0"]
+        );
+        assert!(summarize_cidrs(strings(&["10.0.0.9-10.0.0.1"])).is_err());
+    }
+
+    #[test]
+    fn test_overlap_and_contains() {
+        let overlaps = check_cidr_overlap(strings(&[
+            "10.0.0.0/16",
+            "10.0.5.0/24",
+            "10.1.0.0/16",
+            "10.0.255.0-10.1.0.10",
+            "fd00::/8",
+            "10.0.5.0 255.255.255.0",
+        ]))
+        .unwrap();
+        let relations: Vec<(&str, &str, OverlapRelation)> = overlaps
+            .iter()
+            .map(|o| (o.first.as_str(), o.second.as_str(), o.relation))
+            .collect();
+        assert_eq!(
+            relations,
+            [
+                ("10.0.0.0/16", "10.0.5.0/24", OverlapRelation::Contains),
+                (
+                    "10.0.0.0/16",
+                    "10.0.255.0-10.1.0.10",
+                    OverlapRelation::Partial
+                ),
+                (
+                    "10.0.0.0/16",
+                    "10.0.5.0 255.255.255.0",
+                    OverlapRelation::Contains
+                ),
+                (
+                    "10.0.5.0/24",
+                    "10.0.5.0 255.255.255.0",
+                    OverlapRelation::Equal
+                ),
+                (
+                    "10.1.0.0/16",
+                    "10.0.255.0-10.1.0.10",
+                    OverlapRelation::Partial
+                ),
+            ]
+        );
+
+        assert!(ip_in_range("172.17.0.3".to_string(), "172.16.0.0/12".to_string()).unwrap());
+        assert!(!ip_in_range("172.32.0.1".to_string(), "172.16.0.0/12".to_string()).unwrap());
+        assert!(ip_in_range("[fd00::1]".to_string(), "fd00::/8".to_string()).unwrap());
+        assert!(!ip_in_range("10.0.0.1".to_string(), "::/0".to_string()).unwrap());
+        assert!(ip_in_range("10.0.0.5".to_string(), "10.0.0.1-10.0.0.9".to_string()).unwrap());
+    }
+}
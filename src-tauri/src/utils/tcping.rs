@@ -0,0 +1,333 @@
+use crate::utils::network_util::{
+    self, connect_with_proxy, CheckPortResult, MAX_CONNECT_TIMEOUT_MS,
+};
+use chrono::Local;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+
+/// 默认探测间隔（毫秒）
+const DEFAULT_INTERVAL_MS: u64 = 1_000;
+/// 最小探测间隔（毫秒）
+const MIN_INTERVAL_MS: u64 = 100;
+/// 默认单次连接超时时间（毫秒）
+const DEFAULT_TIMEOUT_MS: u64 = 2_000;
+
+/// 全局 tcping 任务池，Key 是任务ID，Value 是取消令牌
+pub static TCPING_TASK_POOL: Lazy<Mutex<HashMap<String, CancellationToken>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct TcpingRequest {
+    /// 任务ID，由前端生成，用于区分事件与停止任务
+    pub id: String,
+    pub host: String,
+    pub port: u16,
+    /// 探测次数，为空或 0 时持续探测直到停止
+    pub count: Option<u32>,
+    /// 探测间隔（毫秒），默认 1000，最小 100
+    pub interval_ms: Option<u64>,
+    /// 单次连接超时时间（毫秒），默认 2000
+    pub timeout_ms: Option<u64>,
+    /// 代理地址，为空时使用全局代理，`direct` 表示直接连接
+    pub proxy: Option<String>,
+}
+
+/// 延迟统计，只统计连接成功的探测
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct TcpingStats {
+    sent: u32,
+    received: u32,
+    lost: u32,
+    /// 丢失率（百分比）
+    loss_percent: f64,
+    min_ms: Option<f64>,
+    avg_ms: Option<f64>,
+    max_ms: Option<f64>,
+    /// 标准差
+    stddev_ms: Option<f64>,
+    p50_ms: Option<f64>,
+    p90_ms: Option<f64>,
+    p99_ms: Option<f64>,
+}
+
+/// 单次探测结果与当前统计，通过 `tcping_attempt` 事件推送
+#[derive(Serialize, Clone, Debug)]
+pub struct TcpingAttempt {
+    tcping_id: String,
+    /// 序号，从 1 开始
+    seq: u32,
+    #[serde(flatten)]
+    check: CheckPortResult,
+    time: String,
+    stats: TcpingStats,
+}
+
+/// 探测结束，通过 `tcping_finished` 事件推送
+#[derive(Serialize, Clone, Debug)]
+pub struct TcpingSummary {
+    tcping_id: String,
+    host: String,
+    port: u16,
+    cancelled: bool,
+    stats: TcpingStats,
+}
+
+/// 启动 tcping，每次探测的结果以 `tcping_attempt` 事件推送给前端
+#[tauri::command]
+pub fn start_tcping(app_handle: AppHandle, request: TcpingRequest) -> Result<String, String> {
+    validate(&request)?;
+    let token = CancellationToken::new();
+    {
+        let mut pool = TCPING_TASK_POOL.lock().unwrap();
+        if pool.contains_key(&request.id) {
+            return Err(format!("tcping 任务已存在: {}", request.id));
+        }
+        pool.insert(request.id.clone(), token.clone());
+    }
+
+    let id = request.id.clone();
+    network_util::spawn(async move {
+        let summary = run_tcping(&request, token, |attempt| {
+            let _ = app_handle.emit_to("main", "tcping_attempt", attempt);
+        })
+        .await;
+        TCPING_TASK_POOL.lock().unwrap().remove(&request.id);
+        let _ = app_handle.emit_to("main", "tcping_finished", summary);
+    });
+    Ok(id)
+}
+
+/// 停止 tcping，统计结果通过 `tcping_finished` 事件推送
+#[tauri::command]
+pub fn stop_tcping(id: String) -> Result<String, String> {
+    let pool = TCPING_TASK_POOL.lock().unwrap();
+    if let Some(token) = pool.get(&id) {
+        token.cancel();
+        Ok(format!("已停止 tcping 任务: {}", id))
+    } else {
+        Err(format!("未找到 tcping 任务: {}", id))
+    }
+}
+
+fn validate(request: &TcpingRequest) -> Result<(), String> {
+    if request.host.trim().is_empty() {
+        return Err("请输入主机".to_string());
+    }
+    if request.port == 0 {
+        return Err("端口应在 1-65535 之间".to_string());
+    }
+    let timeout_ms = request.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS);
+    if timeout_ms == 0 || timeout_ms > MAX_CONNECT_TIMEOUT_MS {
+        return Err(format!(
+            "超时时间应在 1-{} 毫秒之间",
+            MAX_CONNECT_TIMEOUT_MS
+        ));
+    }
+    Ok(())
+}
+
+/// 按间隔依次探测，每次探测后调用 `on_attempt`
+pub async fn run_tcping<F>(
+    request: &TcpingRequest,
+    token: CancellationToken,
+    mut on_attempt: F,
+) -> TcpingSummary
+where
+    F: FnMut(&TcpingAttempt),
+{
+    let host = request.host.trim();
+    let proxy = request.proxy.as_deref();
+    let count = request.count.filter(|c| *c > 0);
+    let timeout = Duration::from_millis(request.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).max(1));
+    let interval = Duration::from_millis(
+        request
+            .interval_ms
+            .unwrap_or(DEFAULT_INTERVAL_MS)
+            .max(MIN_INTERVAL_MS),
+    );
+    // 超时时间大于间隔时顺延下一次探测，不会并发连接
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let mut sent = 0u32;
+    let mut latencies = Vec::new();
+    while count.is_none_or(|count| sent < count) {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => break,
+            _ = ticker.tick() => {}
+        }
+        let check = tokio::select! {
+            biased;
+            _ = token.cancelled() => break,
+            check = connect_with_proxy(host, request.port, timeout, proxy) => check.0,
+        };
+        sent += 1;
+        if let (true, Some(latency)) = (check.is_open(), check.latency_ms) {
+            latencies.push(latency);
+        }
+        let attempt = TcpingAttempt {
+            tcping_id: request.id.clone(),
+            seq: sent,
+            check,
+            time: Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            stats: compute_stats(sent, &latencies),
+        };
+        on_attempt(&attempt);
+    }
+
+    TcpingSummary {
+        tcping_id: request.id.clone(),
+        host: host.to_string(),
+        port: request.port,
+        cancelled: token.is_cancelled(),
+        stats: compute_stats(sent, &latencies),
+    }
+}
+
+/// 根据发送次数与成功探测的延迟计算统计，百分位使用最近秩法
+pub fn compute_stats(sent: u32, latencies: &[f64]) -> TcpingStats {
+    let received = latencies.len() as u32;
+    let mut stats = TcpingStats {
+        sent,
+        received,
+        lost: sent.saturating_sub(received),
+        ..Default::default()
+    };
+    if sent > 0 {
+        stats.loss_percent = f64::from(stats.lost) * 100.0 / f64::from(sent);
+    }
+    if latencies.is_empty() {
+        return stats;
+    }
+
+    let mut sorted = latencies.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let n = sorted.len() as f64;
+    let avg = sorted.iter().sum::<f64>() / n;
+    let variance = sorted.iter().map(|v| (v - avg).powi(2)).sum::<f64>() / n;
+    let percentile = |p: f64| {
+        let rank = ((p / 100.0) * n).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    };
+    stats.min_ms = sorted.first().copied();
+    stats.max_ms = sorted.last().copied();
+    stats.avg_ms = Some(avg);
+    stats.stddev_ms = Some(variance.sqrt());
+    stats.p50_ms = Some(percentile(50.0));
+    stats.p90_ms = Some(percentile(90.0));
+    stats.p99_ms = Some(percentile(99.0));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::network_util::stand_in::{Behavior, StandIn};
+    use crate::utils::network_util::{with_connector, PortStatus};
+    use tokio::net::TcpListener;
+
+    fn request(id: &str, port: u16, count: Option<u32>) -> TcpingRequest {
+        TcpingRequest {
+            id: id.to_string(),
+            host: "127.0.0.1".to_string(),
+            port,
+            count,
+            interval_ms: Some(MIN_INTERVAL_MS),
+            timeout_ms: Some(1_000),
+            proxy: None,
+        }
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let stats = compute_stats(5, &[4.0, 2.0, 8.0, 6.0]);
+        assert_eq!(stats.received, 4);
+        assert_eq!(stats.lost, 1);
+        assert_eq!(stats.loss_percent, 20.0);
+        assert_eq!(stats.min_ms, Some(2.0));
+        assert_eq!(stats.max_ms, Some(8.0));
+        assert_eq!(stats.avg_ms, Some(5.0));
+        assert_eq!(stats.stddev_ms, Some(5.0f64.sqrt()));
+        assert_eq!(stats.p50_ms, Some(4.0));
+        assert_eq!(stats.p90_ms, Some(8.0));
+
+        let empty = compute_stats(3, &[]);
+        assert_eq!(empty.loss_percent, 100.0);
+        assert_eq!(empty.avg_ms, None);
+        assert_eq!(compute_stats(0, &[]), TcpingStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_run_tcping_local() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let open_port = listener.local_addr().unwrap().port();
+        let mut attempts = Vec::new();
+        let summary = run_tcping(
+            &request("open", open_port, Some(3)),
+            CancellationToken::new(),
+            |a| attempts.push(a.clone()),
+        )
+        .await;
+        assert_eq!(
+            attempts.iter().map(|a| a.seq).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert!(attempts.iter().all(|a| a.check.is_open()));
+        assert_eq!(attempts[2].stats, summary.stats);
+        assert_eq!(summary.stats.received, 3);
+        assert_eq!(summary.stats.loss_percent, 0.0);
+        assert!(!summary.cancelled);
+
+        let connector = StandIn::new()
+            .await
+            .route("127.0.0.1:81", Behavior::BlackHole)
+            .build();
+        let summary = with_connector(
+            connector.clone(),
+            run_tcping(
+                &request("closed", 80, Some(2)),
+                CancellationToken::new(),
+                |a| assert_eq!(a.check.status, PortStatus::Refused),
+            ),
+        )
+        .await;
+        assert_eq!(summary.stats.lost, 2);
+        assert_eq!(summary.stats.min_ms, None);
+
+        let mut filtered = request("filtered", 81, Some(1));
+        filtered.timeout_ms = Some(100);
+        let summary = with_connector(
+            connector,
+            run_tcping(&filtered, CancellationToken::new(), |a| {
+                assert_eq!(a.check.status, PortStatus::Timeout)
+            }),
+        )
+        .await;
+        assert_eq!(summary.stats.loss_percent, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_run_tcping_cancelled() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        let summary = run_tcping(&request("continuous", port, None), token, |a| {
+            if a.seq == 2 {
+                cancel.cancel();
+            }
+        })
+        .await;
+        assert!(summary.cancelled);
+        assert_eq!(summary.stats.sent, 2);
+
+        assert!(validate(&request("invalid", 0, None)).is_err());
+    }
+}
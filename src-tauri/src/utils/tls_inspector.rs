@@ -0,0 +1,605 @@
+use crate::utils::network_util::{
+    connect_with_proxy, DEFAULT_CONNECT_TIMEOUT_MS, MAX_CONNECT_TIMEOUT_MS,
+};
+use crate::utils::service_probe::tls_version_name;
+use base64::Engine;
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::client::WebPkiServerVerifier;
+use tokio_rustls::rustls::crypto::ring;
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{
+    ClientConfig, DigitallySignedStruct, Error as TlsError, ProtocolVersion, RootCertStore,
+    SignatureScheme,
+};
+use tokio_rustls::TlsConnector;
+use x509_parser::extensions::GeneralName;
+use x509_parser::pem::Pem;
+use x509_parser::prelude::{FromDer, X509Certificate};
+use x509_parser::public_key::PublicKey;
+
+/// 默认检查的端口
+const DEFAULT_TLS_PORT: u16 = 443;
+
+/// 握手前需要先升级为 TLS 的明文协议
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartTls {
+    Smtp,
+    Imap,
+    Postgres,
+}
+
+/// TLS 检查请求
+#[derive(Deserialize)]
+pub struct TlsInspectRequest {
+    pub host: String,
+    /// 端口，默认 443
+    pub port: Option<u16>,
+    /// 覆盖 SNI 与证书校验使用的域名，默认使用 host
+    pub sni: Option<String>,
+    pub starttls: Option<StartTls>,
+    /// 超时时间（毫秒），包含连接、STARTTLS 与握手，默认 5000
+    pub timeout_ms: Option<u64>,
+    /// 代理地址，为空时使用全局代理，`direct` 表示直接连接
+    pub proxy: Option<String>,
+}
+
+/// 证书信息
+#[derive(Serialize, Clone, Debug)]
+pub struct CertificateInfo {
+    subject: String,
+    issuer: String,
+    serial: String,
+    /// 使用者可选名称（DNS、IP、邮箱、URI）
+    sans: Vec<String>,
+    not_before: String,
+    not_after: String,
+    /// 距离过期的天数，已过期时为负数
+    days_to_expiry: i64,
+    expired: bool,
+    key_type: String,
+    key_bits: Option<usize>,
+    signature_algorithm: String,
+    is_ca: bool,
+    self_signed: bool,
+    sha1_fingerprint: String,
+    sha256_fingerprint: String,
+    pem: String,
+}
+
+/// TLS 检查结果
+#[derive(Serialize, Debug)]
+pub struct TlsInspection {
+    host: String,
+    port: u16,
+    ip: Option<String>,
+    /// 实际发送的 SNI，目标为 IP 地址时不发送
+    sni: Option<String>,
+    protocol_version: Option<String>,
+    cipher_suite: Option<String>,
+    alpn: Option<String>,
+    /// TLS 握手耗时（毫秒），不含连接与 STARTTLS
+    handshake_ms: f64,
+    /// 证书链是否通过系统内置根证书与域名校验
+    verified: bool,
+    verification_error: Option<String>,
+    /// 服务端发送的证书链，第一个为站点证书
+    chain: Vec<CertificateInfo>,
+}
+
+/// 记录校验结果但始终放行的证书校验器，使过期或不匹配的证书也能被检查
+#[derive(Debug)]
+struct RecordingVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    error: Mutex<Option<String>>,
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        if let Err(e) = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        ) {
+            *self.error.lock().unwrap() = Some(e.to_string());
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// 连接服务端并完成 TLS 握手，返回证书链与协商结果
+#[tauri::command]
+pub async fn inspect_tls(request: TlsInspectRequest) -> Result<TlsInspection, String> {
+    let timeout_ms = request.timeout_ms.unwrap_or(DEFAULT_CONNECT_TIMEOUT_MS);
+    if timeout_ms == 0 || timeout_ms > MAX_CONNECT_TIMEOUT_MS {
+        return Err(format!(
+            "超时时间应在 1-{} 毫秒之间",
+            MAX_CONNECT_TIMEOUT_MS
+        ));
+    }
+    let timeout = Duration::from_millis(timeout_ms);
+    tokio::time::timeout(timeout, inspect(request, timeout))
+        .await
+        .map_err(|_| "TLS 检查超时".to_string())?
+}
+
+/// 解析粘贴或拖入的 PEM / DER 证书
+#[tauri::command]
+pub fn parse_certificates(data: Vec<u8>) -> Result<Vec<CertificateInfo>, String> {
+    if data.windows(11).any(|w| w == b"-----BEGIN ") {
+        let mut certificates = Vec::new();
+        for pem in Pem::iter_from_buffer(&data) {
+            let pem = pem.map_err(|e| format!("PEM 解析失败: {}", e))?;
+            if pem.label == "CERTIFICATE" || pem.label == "TRUSTED CERTIFICATE" {
+                certificates.push(certificate_info(&pem.contents)?);
+            }
+        }
+        if certificates.is_empty() {
+            return Err("未找到证书".to_string());
+        }
+        Ok(certificates)
+    } else {
+        Ok(vec![certificate_info(&data)?])
+    }
+}
+
+async fn inspect(request: TlsInspectRequest, timeout: Duration) -> Result<TlsInspection, String> {
+    let port = request.port.unwrap_or(DEFAULT_TLS_PORT);
+    let host = request.host.trim().to_string();
+    let (check, stream) = connect_with_proxy(&host, port, timeout, request.proxy.as_deref()).await;
+    let mut stream = stream.ok_or_else(|| {
+        check
+            .message
+            .clone()
+            .unwrap_or_else(|| "连接失败".to_string())
+    })?;
+    if let Some(starttls) = request.starttls {
+        stream = upgrade(stream, starttls).await?;
+    }
+
+    let name = request
+        .sni
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or(&host)
+        .trim_matches(['[', ']'])
+        .to_string();
+    let server_name =
+        ServerName::try_from(name.clone()).map_err(|e| format!("无效的域名 {}: {}", name, e))?;
+    let sni = matches!(server_name, ServerName::DnsName(_)).then(|| name.clone());
+
+    let (config, verifier) = recording_config()?;
+
+    let start = Instant::now();
+    let tls = TlsConnector::from(Arc::new(config))
+        .connect(server_name, stream)
+        .await
+        .map_err(|e| format!("TLS 握手失败: {}", e))?;
+    let handshake_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    let (_, conn) = tls.get_ref();
+    let chain = conn
+        .peer_certificates()
+        .unwrap_or_default()
+        .iter()
+        .map(|cert| certificate_info(cert))
+        .collect::<Result<Vec<_>, _>>()?;
+    let verification_error = verifier.error.lock().unwrap().take();
+
+    Ok(TlsInspection {
+        host,
+        port,
+        ip: check.ip,
+        sni,
+        protocol_version: conn.protocol_version().map(protocol_name),
+        cipher_suite: conn
+            .negotiated_cipher_suite()
+            .map(|s| format!("{:?}", s.suite())),
+        alpn: conn
+            .alpn_protocol()
+            .map(|p| String::from_utf8_lossy(p).into_owned()),
+        handshake_ms,
+        verified: verification_error.is_none(),
+        verification_error,
+        chain,
+    })
+}
+
+/// 使用 webpki-roots 内置根证书的客户端配置
+fn root_store() -> RootCertStore {
+    RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    }
+}
+
+/// 记录证书校验结果但不中断握手的客户端配置
+fn recording_config() -> Result<(ClientConfig, Arc<RecordingVerifier>), String> {
+    let provider = Arc::new(ring::default_provider());
+    let inner =
+        WebPkiServerVerifier::builder_with_provider(Arc::new(root_store()), provider.clone())
+            .build()
+            .map_err(|e| e.to_string())?;
+    let verifier = Arc::new(RecordingVerifier {
+        inner,
+        error: Mutex::new(None),
+    });
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| e.to_string())?
+        .dangerous()
+        .with_custom_certificate_verifier(verifier.clone())
+        .with_no_client_auth();
+    Ok((config, verifier))
+}
+
+/// 供其他网络工具使用的 TLS 客户端配置，`verify` 为假时不校验证书
+pub(crate) fn client_config(verify: bool) -> Result<ClientConfig, String> {
+    if !verify {
+        return recording_config().map(|(config, _)| config);
+    }
+    Ok(
+        ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| e.to_string())?
+            .with_root_certificates(root_store())
+            .with_no_client_auth(),
+    )
+}
+
+fn protocol_name(version: ProtocolVersion) -> String {
+    tls_version_name(u16::from(version))
+}
+
+/// 通过 STARTTLS 将明文连接升级为 TLS
+async fn upgrade(stream: TcpStream, starttls: StartTls) -> Result<TcpStream, String> {
+    let mut reader = BufReader::new(stream);
+    match starttls {
+        StartTls::Smtp => {
+            expect_smtp(&mut reader, "220").await?;
+            send(&mut reader, b"EHLO lin-toolbox\r\n").await?;
+            expect_smtp(&mut reader, "250").await?;
+            send(&mut reader, b"STARTTLS\r\n").await?;
+            expect_smtp(&mut reader, "220").await?;
+        }
+        StartTls::Imap => {
+            let greeting = read_line(&mut reader).await?;
+            if !greeting.starts_with("* OK") {
+                return Err(format!("IMAP 服务端响应异常: {}", greeting));
+            }
+            send(&mut reader, b"a1 STARTTLS\r\n").await?;
+            loop {
+                let line = read_line(&mut reader).await?;
+                if line.starts_with("a1 OK") {
+                    break;
+                }
+                if line.starts_with("a1 ") {
+                    return Err(format!("IMAP STARTTLS 失败: {}", line));
+                }
+            }
+        }
+        StartTls::Postgres => {
+            let mut request = 8u32.to_be_bytes().to_vec();
+            request.extend_from_slice(&80877103u32.to_be_bytes());
+            send(&mut reader, &request).await?;
+            match reader.read_u8().await.map_err(|e| e.to_string())? {
+                b'S' => {}
+                b'N' => return Err("PostgreSQL 服务端未启用 SSL".to_string()),
+                b => return Err(format!("PostgreSQL 服务端响应异常: 0x{:02x}", b)),
+            }
+        }
+    }
+    Ok(reader.into_inner())
+}
+
+async fn send(reader: &mut BufReader<TcpStream>, data: &[u8]) -> Result<(), String> {
+    reader
+        .get_mut()
+        .write_all(data)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn read_line(reader: &mut BufReader<TcpStream>) -> Result<String, String> {
+    let mut line = String::new();
+    if reader
+        .read_line(&mut line)
+        .await
+        .map_err(|e| e.to_string())?
+        == 0
+    {
+        return Err("连接已被服务端关闭".to_string());
+    }
+    Ok(line.trim_end().to_string())
+}
+
+/// 读取 SMTP 多行响应，并检查最后一行的响应码
+async fn expect_smtp(reader: &mut BufReader<TcpStream>, code: &str) -> Result<(), String> {
+    loop {
+        let line = read_line(reader).await?;
+        if !line.starts_with(code) {
+            return Err(format!("SMTP 服务端响应异常: {}", line));
+        }
+        if line.as_bytes().get(3) != Some(&b'-') {
+            return Ok(());
+        }
+    }
+}
+
+/// 解析 DER 证书
+fn certificate_info(der: &[u8]) -> Result<CertificateInfo, String> {
+    let (_, cert) = X509Certificate::from_der(der).map_err(|e| format!("证书解析失败: {}", e))?;
+
+    let sans = match cert.subject_alternative_name() {
+        Ok(Some(ext)) => ext
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(s) => Some(format!("DNS:{}", s)),
+                GeneralName::RFC822Name(s) => Some(format!("email:{}", s)),
+                GeneralName::URI(s) => Some(format!("URI:{}", s)),
+                GeneralName::IPAddress(ip) => ip_from_bytes(ip).map(|ip| format!("IP:{}", ip)),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let not_before = cert.validity().not_before.timestamp();
+    let not_after = cert.validity().not_after.timestamp();
+    let now = Local::now().timestamp();
+    let (key_type, key_bits) = key_info(&cert);
+
+    Ok(CertificateInfo {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        serial: cert.raw_serial_as_string().to_uppercase(),
+        sans,
+        not_before: format_timestamp(not_before),
+        not_after: format_timestamp(not_after),
+        days_to_expiry: (not_after - now).div_euclid(86_400),
+        expired: now > not_after,
+        key_type,
+        key_bits,
+        signature_algorithm: signature_name(&cert.signature_algorithm.algorithm.to_id_string()),
+        is_ca: cert.is_ca(),
+        self_signed: cert.subject().as_raw() == cert.issuer().as_raw(),
+        sha1_fingerprint: fingerprint(&Sha1::digest(der)),
+        sha256_fingerprint: fingerprint(&Sha256::digest(der)),
+        pem: to_pem(der),
+    })
+}
+
+fn key_info(cert: &X509Certificate) -> (String, Option<usize>) {
+    let spki = cert.public_key();
+    match spki.algorithm.algorithm.to_id_string().as_str() {
+        "1.3.101.112" => return ("Ed25519".to_string(), Some(256)),
+        "1.3.101.113" => return ("Ed448".to_string(), Some(456)),
+        _ => {}
+    }
+    match spki.parsed() {
+        Ok(PublicKey::RSA(rsa)) => ("RSA".to_string(), Some(rsa.key_size())),
+        Ok(PublicKey::EC(_)) => {
+            let curve = spki
+                .algorithm
+                .parameters
+                .as_ref()
+                .and_then(|p| p.as_oid().ok())
+                .map(|oid| oid.to_id_string());
+            match curve.as_deref() {
+                Some("1.2.840.10045.3.1.7") => ("EC P-256".to_string(), Some(256)),
+                Some("1.3.132.0.34") => ("EC P-384".to_string(), Some(384)),
+                Some("1.3.132.0.35") => ("EC P-521".to_string(), Some(521)),
+                Some(oid) => (format!("EC {}", oid), None),
+                None => ("EC".to_string(), None),
+            }
+        }
+        Ok(PublicKey::DSA(y)) => ("DSA".to_string(), Some(y.len() * 8)),
+        _ => (spki.algorithm.algorithm.to_id_string(), None),
+    }
+}
+
+fn signature_name(oid: &str) -> String {
+    match oid {
+        "1.2.840.113549.1.1.5" => "sha1WithRSAEncryption".to_string(),
+        "1.2.840.113549.1.1.11" => "sha256WithRSAEncryption".to_string(),
+        "1.2.840.113549.1.1.12" => "sha384WithRSAEncryption".to_string(),
+        "1.2.840.113549.1.1.13" => "sha512WithRSAEncryption".to_string(),
+        "1.2.840.113549.1.1.10" => "RSASSA-PSS".to_string(),
+        "1.2.840.10045.4.3.2" => "ecdsa-with-SHA256".to_string(),
+        "1.2.840.10045.4.3.3" => "ecdsa-with-SHA384".to_string(),
+        "1.2.840.10045.4.3.4" => "ecdsa-with-SHA512".to_string(),
+        "1.3.101.112" => "Ed25519".to_string(),
+        "1.3.101.113" => "Ed448".to_string(),
+        oid => oid.to_string(),
+    }
+}
+
+fn ip_from_bytes(bytes: &[u8]) -> Option<std::net::IpAddr> {
+    match bytes.len() {
+        4 => Some(<[u8; 4]>::try_from(bytes).ok()?.into()),
+        16 => Some(<[u8; 16]>::try_from(bytes).ok()?.into()),
+        _ => None,
+    }
+}
+
+fn format_timestamp(secs: i64) -> String {
+    Local
+        .timestamp_opt(secs, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| secs.to_string())
+}
+
+/// 以冒号分隔的大写十六进制指纹
+fn fingerprint(digest: &[u8]) -> String {
+    digest
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+fn to_pem(der: &[u8]) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(der);
+    let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+    for chunk in encoded.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(chunk).unwrap());
+        pem.push('\n');
+    }
+    pem.push_str("-----END CERTIFICATE-----\n");
+    pem
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_rustls::rustls::pki_types::PrivateKeyDer;
+    use tokio_rustls::rustls::ServerConfig;
+    use tokio_rustls::TlsAcceptor;
+
+    fn self_signed(names: &[&str]) -> (CertificateDer<'static>, PrivateKeyDer<'static>) {
+        let names: Vec<String> = names.iter().map(|s| s.to_string()).collect();
+        let certified = rcgen::generate_simple_self_signed(names).unwrap();
+        let key = PrivateKeyDer::Pkcs8(certified.key_pair.serialize_der().into());
+        (certified.cert.der().clone(), key)
+    }
+
+    /// 本地 TLS 替身服务，`smtp` 为真时先模拟 SMTP STARTTLS 交互
+    async fn spawn_tls_server(smtp: bool) -> (u16, CertificateDer<'static>) {
+        let (cert, key) = self_signed(&["localhost", "127.0.0.1"]);
+        let config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert.clone()], key)
+            .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            if smtp {
+                stream
+                    .write_all(b"220 mail.example.test ESMTP\r\n")
+                    .await
+                    .unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                reader
+                    .get_mut()
+                    .write_all(b"250-mail.example.test\r\n250 STARTTLS\r\n")
+                    .await
+                    .unwrap();
+                line.clear();
+                reader.read_line(&mut line).await.unwrap();
+                assert_eq!(line, "STARTTLS\r\n");
+                reader.get_mut().write_all(b"220 Ready\r\n").await.unwrap();
+                stream = reader.into_inner();
+            }
+            let mut tls = acceptor.accept(stream).await.unwrap();
+            let _ = tls.read(&mut [0u8; 1]).await;
+        });
+        (port, cert)
+    }
+
+    fn request(port: u16, starttls: Option<StartTls>) -> TlsInspectRequest {
+        TlsInspectRequest {
+            host: "127.0.0.1".to_string(),
+            port: Some(port),
+            sni: Some("localhost".to_string()),
+            starttls,
+            timeout_ms: Some(3_000),
+            proxy: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inspect_self_signed() {
+        let (port, cert) = spawn_tls_server(false).await;
+        let result = inspect_tls(request(port, None)).await.unwrap();
+        assert_eq!(result.sni.as_deref(), Some("localhost"));
+        assert_eq!(result.protocol_version.as_deref(), Some("TLS 1.3"));
+        assert!(result.cipher_suite.unwrap().starts_with("TLS13_"));
+        assert!(!result.verified);
+        assert!(result.verification_error.is_some());
+
+        let leaf = &result.chain[0];
+        assert_eq!(result.chain.len(), 1);
+        assert_eq!(leaf.sans, vec!["DNS:localhost", "IP:127.0.0.1"]);
+        assert!(leaf.self_signed && !leaf.expired && leaf.days_to_expiry > 0);
+        assert_eq!(leaf.key_type, "EC P-256");
+        assert_eq!(leaf.signature_algorithm, "ecdsa-with-SHA256");
+        assert_eq!(
+            leaf.sha256_fingerprint,
+            fingerprint(&Sha256::digest(cert.as_ref()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_inspect_smtp_starttls() {
+        let (port, _) = spawn_tls_server(true).await;
+        let result = inspect_tls(request(port, Some(StartTls::Smtp)))
+            .await
+            .unwrap();
+        assert_eq!(result.chain.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_certificates_pem_and_der() {
+        let (first, _) = self_signed(&["a.example.test"]);
+        let (second, _) = self_signed(&["b.example.test"]);
+        let bundle = format!("junk\n{}{}", to_pem(&first), to_pem(&second));
+        let certs = parse_certificates(bundle.into_bytes()).unwrap();
+        assert_eq!(certs.len(), 2);
+        assert_eq!(certs[1].sans, vec!["DNS:b.example.test"]);
+
+        let certs = parse_certificates(first.to_vec()).unwrap();
+        assert_eq!(certs[0].sans, vec!["DNS:a.example.test"]);
+        assert!(parse_certificates(b"not a certificate".to_vec()).is_err());
+    }
+}
@@ -0,0 +1,564 @@
+use crate::utils::dns_util::{build_query, parse_message};
+use crate::utils::id_generator::{from_hex, to_hex};
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::{Duration, Instant};
+use tokio::io::Interest;
+use tokio::net::{lookup_host, UdpSocket};
+
+/// 默认等待响应的时间（毫秒）
+const DEFAULT_UDP_TIMEOUT_MS: u64 = 2_000;
+/// 最大重试次数
+const MAX_UDP_RETRIES: u32 = 10;
+/// 返回给前端的响应内容最大字节数
+const MAX_RESPONSE_PREVIEW: usize = 512;
+/// NTP 时间戳起点（1900-01-01）与 Unix 时间戳起点之间的秒数
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+/// SNMP sysDescr.0 的 OID（1.3.6.1.2.1.1.1.0）
+const SYS_DESCR_OID: &[u8] = &[0x2B, 6, 1, 2, 1, 1, 1, 0];
+
+/// UDP 探测报文类型
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UdpProbe {
+    /// 查询根域 NS 记录
+    Dns,
+    /// NTP v3 客户端请求
+    Ntp,
+    /// SNMP v2c 查询 sysDescr.0
+    Snmp { community: Option<String> },
+    /// RFC 3164 格式的测试日志，syslog 服务不会应答
+    Syslog { message: Option<String> },
+    /// 自定义十六进制负载
+    Custom { hex: String },
+}
+
+impl UdpProbe {
+    fn default_port(&self) -> Option<u16> {
+        match self {
+            UdpProbe::Dns => Some(53),
+            UdpProbe::Ntp => Some(123),
+            UdpProbe::Snmp { .. } => Some(161),
+            UdpProbe::Syslog { .. } => Some(514),
+            UdpProbe::Custom { .. } => None,
+        }
+    }
+
+    /// 生成探测负载
+    pub fn payload(&self) -> Result<Vec<u8>, String> {
+        match self {
+            UdpProbe::Dns => build_query(rand::random(), ".", 2),
+            UdpProbe::Ntp => {
+                let mut packet = vec![0u8; 48];
+                packet[0] = 0x1B; // LI=0, VN=3, Mode=3（客户端）
+                Ok(packet)
+            }
+            UdpProbe::Snmp { community } => Ok(snmp_get_request(
+                community.as_deref().unwrap_or("public"),
+                rand::random::<u16>() as u32,
+            )),
+            UdpProbe::Syslog { message } => Ok(format!(
+                "<14>{} lin-toolbox: {}",
+                Local::now().format("%b %e %H:%M:%S"),
+                message.as_deref().unwrap_or("UDP probe")
+            )
+            .into_bytes()),
+            UdpProbe::Custom { hex } => {
+                let hex: String = hex.split_whitespace().collect();
+                let hex = hex.strip_prefix("0x").unwrap_or(&hex);
+                match from_hex(hex) {
+                    Some(bytes) if !bytes.is_empty() => Ok(bytes),
+                    _ => Err("无效的十六进制负载".to_string()),
+                }
+            }
+        }
+    }
+
+    /// 解读响应内容，无法识别时返回 None
+    fn describe(&self, response: &[u8]) -> Option<String> {
+        match self {
+            UdpProbe::Dns => {
+                let message = parse_message(response).ok()?;
+                Some(format!(
+                    "DNS 应答: rcode={}, {} 条应答记录",
+                    message.response_code,
+                    message.answers.len()
+                ))
+            }
+            UdpProbe::Ntp => describe_ntp(response),
+            UdpProbe::Snmp { .. } => {
+                parse_snmp_response(response).map(|descr| format!("SNMP sysDescr: {}", descr))
+            }
+            UdpProbe::Syslog { .. } | UdpProbe::Custom { .. } => None,
+        }
+    }
+}
+
+/// UDP 探测状态
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UdpProbeStatus {
+    /// 收到响应，端口开放
+    Open,
+    /// 收到 ICMP 端口不可达，端口关闭
+    Closed,
+    /// 没有任何响应，端口可能开放也可能被防火墙丢弃
+    OpenOrFiltered,
+    /// 收到 ICMP 主机或网络不可达
+    Unreachable,
+    /// 域名解析失败
+    DnsFailure,
+    /// 其他错误
+    Error,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct UdpProbeResult {
+    status: UdpProbeStatus,
+    ip: Option<String>,
+    port: u16,
+    /// 实际发送的次数
+    attempts: u32,
+    /// 最后一次发送到收到响应或错误的耗时（毫秒）
+    latency_ms: Option<f64>,
+    /// 响应长度
+    response_len: Option<usize>,
+    /// 响应内容（十六进制，最多 512 字节）
+    response_hex: Option<String>,
+    /// 对响应内容的解读
+    summary: Option<String>,
+    os_error: Option<i32>,
+    message: Option<String>,
+}
+
+/// 发送 UDP 探测报文，根据响应、ICMP 错误或沉默判断端口状态
+/// - port: 为空时使用探测类型的默认端口
+/// - timeout_ms: 每次发送后等待响应的时间，默认 2000
+/// - retries: 无响应时的重试次数，默认 1
+#[tauri::command]
+pub async fn udp_probe(
+    host: String,
+    port: Option<u16>,
+    probe: UdpProbe,
+    timeout_ms: Option<u64>,
+    retries: Option<u32>,
+) -> Result<UdpProbeResult, String> {
+    let port = port
+        .or_else(|| probe.default_port())
+        .ok_or_else(|| "自定义负载需要指定端口".to_string())?;
+    if port == 0 {
+        return Err("端口应在 1-65535 之间".to_string());
+    }
+    let retries = retries.unwrap_or(1);
+    if retries > MAX_UDP_RETRIES {
+        return Err(format!("重试次数不能超过 {}", MAX_UDP_RETRIES));
+    }
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_UDP_TIMEOUT_MS).max(1));
+    let payload = probe.payload()?;
+    Ok(run_probe(&host, port, &probe, &payload, timeout, retries).await)
+}
+
+async fn run_probe(
+    host: &str,
+    port: u16,
+    probe: &UdpProbe,
+    payload: &[u8],
+    timeout: Duration,
+    retries: u32,
+) -> UdpProbeResult {
+    let mut result = UdpProbeResult {
+        status: UdpProbeStatus::Error,
+        ip: None,
+        port,
+        attempts: 0,
+        latency_ms: None,
+        response_len: None,
+        response_hex: None,
+        summary: None,
+        os_error: None,
+        message: None,
+    };
+
+    let addr =
+        match tokio::time::timeout(timeout, lookup_host((host.trim_matches(['[', ']']), port)))
+            .await
+        {
+            Ok(Ok(mut addrs)) => addrs.next(),
+            Ok(Err(e)) => {
+                result.message = Some(e.to_string());
+                None
+            }
+            Err(_) => {
+                result.message = Some("域名解析超时".to_string());
+                None
+            }
+        };
+    let Some(addr) = addr else {
+        result.status = UdpProbeStatus::DnsFailure;
+        result
+            .message
+            .get_or_insert_with(|| "未解析到任何地址".to_string());
+        return result;
+    };
+    result.ip = Some(addr.ip().to_string());
+
+    // 已连接的 UDP 套接字才能收到 ICMP 错误
+    let socket = match connect_socket(addr).await {
+        Ok(socket) => socket,
+        Err(e) => return result.with_error(&e),
+    };
+
+    let mut buf = vec![0u8; 65_535];
+    for _ in 0..=retries {
+        result.attempts += 1;
+        let start = Instant::now();
+        if let Err(e) = socket.send(payload).await {
+            result.latency_ms = Some(start.elapsed().as_secs_f64() * 1000.0);
+            return result.with_error(&e);
+        }
+        match tokio::time::timeout(timeout, recv_or_error(&socket, &mut buf)).await {
+            Ok(Ok(n)) => {
+                let response = &buf[..n];
+                result.status = UdpProbeStatus::Open;
+                result.latency_ms = Some(start.elapsed().as_secs_f64() * 1000.0);
+                result.response_len = Some(n);
+                result.response_hex = Some(to_hex(&response[..n.min(MAX_RESPONSE_PREVIEW)]));
+                result.summary = probe
+                    .describe(response)
+                    .or_else(|| printable_preview(response));
+                return result;
+            }
+            Ok(Err(e)) => {
+                result.latency_ms = Some(start.elapsed().as_secs_f64() * 1000.0);
+                return result.with_error(&e);
+            }
+            Err(_) => {}
+        }
+    }
+    result.status = UdpProbeStatus::OpenOrFiltered;
+    result.message = Some("未收到响应".to_string());
+    result
+}
+
+async fn connect_socket(addr: SocketAddr) -> io::Result<UdpSocket> {
+    let bind_addr: SocketAddr = match addr {
+        SocketAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        SocketAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(bind_addr).await?;
+    socket.connect(addr).await?;
+    Ok(socket)
+}
+
+/// 接收响应，同时监听套接字上的 ICMP 错误
+///
+/// 部分 tokio 版本不会让 `recv` 在 EPOLLERR 时返回，这里单独等待错误就绪并取出 SO_ERROR。
+pub(crate) async fn recv_or_error(socket: &UdpSocket, buf: &mut [u8]) -> io::Result<usize> {
+    tokio::select! {
+        result = socket.recv(buf) => result,
+        ready = socket.ready(Interest::ERROR) => {
+            ready?;
+            match socket.take_error()? {
+                Some(e) => Err(e),
+                None => socket.recv(buf).await,
+            }
+        }
+    }
+}
+
+impl UdpProbeResult {
+    fn with_error(mut self, e: &io::Error) -> Self {
+        self.status = match e.kind() {
+            // Linux 上为 ECONNREFUSED，Windows 上为 WSAECONNRESET
+            io::ErrorKind::ConnectionRefused | io::ErrorKind::ConnectionReset => {
+                UdpProbeStatus::Closed
+            }
+            io::ErrorKind::HostUnreachable | io::ErrorKind::NetworkUnreachable => {
+                UdpProbeStatus::Unreachable
+            }
+            _ => UdpProbeStatus::Error,
+        };
+        self.os_error = e.raw_os_error();
+        self.message = Some(e.to_string());
+        self
+    }
+}
+
+/// 响应内容为可打印文本时返回文本预览
+fn printable_preview(response: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(&response[..response.len().min(MAX_RESPONSE_PREVIEW)]).ok()?;
+    let printable = text
+        .chars()
+        .all(|c| !c.is_control() || c.is_ascii_whitespace());
+    (printable && !text.trim().is_empty()).then(|| text.trim().to_string())
+}
+
+fn describe_ntp(response: &[u8]) -> Option<String> {
+    if response.len() < 48 {
+        return None;
+    }
+    let mode = response[0] & 0x07;
+    if mode != 4 {
+        return None;
+    }
+    let stratum = response[1];
+    let seconds = u32::from_be_bytes(response[40..44].try_into().ok()?) as u64;
+    let fraction = u32::from_be_bytes(response[44..48].try_into().ok()?) as u64;
+    let unix_millis = seconds.checked_sub(NTP_UNIX_OFFSET)? * 1000 + ((fraction * 1000) >> 32);
+    let server_time = Local.timestamp_millis_opt(unix_millis as i64).single()?;
+    let offset = server_time.timestamp_millis() - Local::now().timestamp_millis();
+    Some(format!(
+        "NTP 应答: stratum={}, 服务器时间 {}, 与本机相差约 {} ms",
+        stratum,
+        server_time.format("%Y-%m-%d %H:%M:%S%.3f"),
+        offset
+    ))
+}
+
+/// 编码 BER 的 TLV
+fn ber_tlv(tag: u8, value: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    let len = value.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes: Vec<u8> = len
+            .to_be_bytes()
+            .into_iter()
+            .skip_while(|b| *b == 0)
+            .collect();
+        out.push(0x80 | bytes.len() as u8);
+        out.extend(bytes);
+    }
+    out.extend_from_slice(value);
+    out
+}
+
+fn ber_integer(value: u32) -> Vec<u8> {
+    let mut bytes: Vec<u8> = value.to_be_bytes().to_vec();
+    while bytes.len() > 1 && bytes[0] == 0 && bytes[1] & 0x80 == 0 {
+        bytes.remove(0);
+    }
+    if bytes[0] & 0x80 != 0 {
+        bytes.insert(0, 0);
+    }
+    ber_tlv(0x02, &bytes)
+}
+
+/// 构造 SNMP v2c GetRequest（sysDescr.0）
+fn snmp_get_request(community: &str, request_id: u32) -> Vec<u8> {
+    let varbind = ber_tlv(
+        0x30,
+        &[ber_tlv(0x06, SYS_DESCR_OID), vec![0x05, 0x00]].concat(),
+    );
+    let pdu = [
+        ber_integer(request_id),
+        ber_integer(0),
+        ber_integer(0),
+        ber_tlv(0x30, &varbind),
+    ]
+    .concat();
+    let message = [
+        ber_integer(1), // v2c
+        ber_tlv(0x04, community.as_bytes()),
+        ber_tlv(0xA0, &pdu),
+    ]
+    .concat();
+    ber_tlv(0x30, &message)
+}
+
+/// 读取一个 BER TLV，返回 (tag, value, 剩余内容)
+fn read_tlv(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, rest) = rest.split_first()?;
+    let (len, rest) = if first & 0x80 == 0 {
+        (first as usize, rest)
+    } else {
+        let n = (first & 0x7F) as usize;
+        if n == 0 || n > 4 || rest.len() < n {
+            return None;
+        }
+        let len = rest[..n]
+            .iter()
+            .fold(0usize, |acc, b| acc << 8 | *b as usize);
+        (len, &rest[n..])
+    };
+    if rest.len() < len {
+        return None;
+    }
+    Some((tag, &rest[..len], &rest[len..]))
+}
+
+/// 从 SNMP GetResponse 中取出第一个变量的值
+fn parse_snmp_response(response: &[u8]) -> Option<String> {
+    let (0x30, message, _) = read_tlv(response)? else {
+        return None;
+    };
+    let (_, _version, rest) = read_tlv(message)?;
+    let (_, _community, rest) = read_tlv(rest)?;
+    let (0xA2, pdu, _) = read_tlv(rest)? else {
+        return None;
+    };
+    let (_, _request_id, rest) = read_tlv(pdu)?;
+    let (_, error_status, rest) = read_tlv(rest)?;
+    if error_status.iter().any(|b| *b != 0) {
+        return Some(format!(
+            "错误状态 {}",
+            error_status.last().copied().unwrap_or(0)
+        ));
+    }
+    let (_, _error_index, rest) = read_tlv(rest)?;
+    let (_, varbinds, _) = read_tlv(rest)?;
+    let (_, varbind, _) = read_tlv(varbinds)?;
+    let (_, _oid, rest) = read_tlv(varbind)?;
+    let (tag, value, _) = read_tlv(rest)?;
+    Some(match tag {
+        0x04 => String::from_utf8_lossy(value).into_owned(),
+        0x80 => "noSuchObject".to_string(),
+        0x81 => "noSuchInstance".to_string(),
+        _ => to_hex(value),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 本地 UDP 桩服务，对每个请求调用 `respond` 生成应答
+    async fn spawn_stub<F>(respond: F) -> u16
+    where
+        F: Fn(&[u8]) -> Vec<u8> + Send + 'static,
+    {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1500];
+            while let Ok((n, peer)) = socket.recv_from(&mut buf).await {
+                socket.send_to(&respond(&buf[..n]), peer).await.unwrap();
+            }
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_udp_probe_custom_echo() {
+        let port = spawn_stub(|req| req.to_vec()).await;
+        let probe = UdpProbe::Custom {
+            hex: "68 65 6c 6c 6f".to_string(),
+        };
+        let result = udp_probe("127.0.0.1".to_string(), Some(port), probe, None, None)
+            .await
+            .unwrap();
+        assert_eq!(result.status, UdpProbeStatus::Open);
+        assert_eq!(result.response_hex.as_deref(), Some("68656c6c6f"));
+        assert_eq!(result.summary.as_deref(), Some("hello"));
+        assert_eq!(result.attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_udp_probe_closed_and_silent() {
+        // 绑定后立即释放的端口会返回 ICMP 端口不可达
+        let port = UdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let probe = UdpProbe::Custom {
+            hex: "00".to_string(),
+        };
+        let result = udp_probe(
+            "127.0.0.1".to_string(),
+            Some(port),
+            probe.clone(),
+            Some(500),
+            Some(0),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.status, UdpProbeStatus::Closed);
+
+        // 不应答的服务只能判断为 open|filtered
+        let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = silent.local_addr().unwrap().port();
+        let result = udp_probe(
+            "127.0.0.1".to_string(),
+            Some(port),
+            probe,
+            Some(100),
+            Some(2),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.status, UdpProbeStatus::OpenOrFiltered);
+        assert_eq!(result.attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_udp_probe_ntp() {
+        let port = spawn_stub(|req| {
+            assert_eq!(req[0], 0x1B);
+            let mut resp = vec![0u8; 48];
+            resp[0] = 0x1C; // 服务端模式
+            resp[1] = 2;
+            let now = Local::now().timestamp() as u64 + NTP_UNIX_OFFSET;
+            resp[40..44].copy_from_slice(&(now as u32).to_be_bytes());
+            resp
+        })
+        .await;
+        let result = udp_probe(
+            "127.0.0.1".to_string(),
+            Some(port),
+            UdpProbe::Ntp,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(result.summary.unwrap().starts_with("NTP 应答: stratum=2"));
+    }
+
+    #[tokio::test]
+    async fn test_udp_probe_snmp() {
+        let port = spawn_stub(|_| {
+            let varbind = [ber_tlv(0x06, SYS_DESCR_OID), ber_tlv(0x04, b"Linux stub")].concat();
+            let pdu = [
+                ber_integer(1),
+                ber_integer(0),
+                ber_integer(0),
+                ber_tlv(0x30, &ber_tlv(0x30, &varbind)),
+            ]
+            .concat();
+            let message = [
+                ber_integer(1),
+                ber_tlv(0x04, b"public"),
+                ber_tlv(0xA2, &pdu),
+            ]
+            .concat();
+            ber_tlv(0x30, &message)
+        })
+        .await;
+        let probe = UdpProbe::Snmp { community: None };
+        let result = udp_probe("127.0.0.1".to_string(), Some(port), probe, None, None)
+            .await
+            .unwrap();
+        assert_eq!(result.summary.as_deref(), Some("SNMP sysDescr: Linux stub"));
+    }
+
+    #[test]
+    fn test_snmp_request_encoding() {
+        let request = snmp_get_request("public", 1);
+        assert_eq!(
+            to_hex(&request),
+            "302602010104067075626c6963a019020101020100020100300e300c06082b060102010101000500"
+        );
+        assert_eq!(to_hex(&ber_integer(128)), "02020080");
+        assert!(UdpProbe::Custom {
+            hex: "abc".to_string()
+        }
+        .payload()
+        .is_err());
+    }
+}
@@ -0,0 +1,769 @@
+use crate::utils::http_client::{Io, KeyValue};
+use crate::utils::id_generator::to_hex;
+use crate::utils::netcat::{encode_payload, hex_dump, LineEnding, PayloadEncoding};
+use crate::utils::network_util::{self, open_stream, EventSink};
+use crate::utils::tls_inspector::client_config;
+use chrono::Local;
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, Interval};
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::TlsConnector;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::header::{
+    HeaderName, HeaderValue, SEC_WEBSOCKET_PROTOCOL,
+};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{client_async, WebSocketStream};
+use url::Url;
+
+/// 默认连接与握手超时时间（毫秒）
+const DEFAULT_WS_TIMEOUT_MS: u64 = 10_000;
+/// 默认重连间隔（毫秒）
+const DEFAULT_RECONNECT_INTERVAL_MS: u64 = 3_000;
+/// 最小重连间隔（毫秒）
+const MIN_RECONNECT_INTERVAL_MS: u64 = 100;
+/// 关闭会话时等待关闭帧发送完成的时间
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(2);
+
+type WsStream = WebSocketStream<Box<dyn Io>>;
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct WsRequest {
+    /// ws:// 或 wss:// 地址
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<KeyValue>,
+    /// 通过 Sec-WebSocket-Protocol 协商的子协议
+    #[serde(default)]
+    pub subprotocols: Vec<String>,
+    /// 心跳间隔（秒），为空或 0 时不发送 Ping
+    pub ping_interval_secs: Option<u64>,
+    /// 连接断开后是否自动重连
+    #[serde(default)]
+    pub auto_reconnect: bool,
+    /// 重连间隔（毫秒），默认 3000，最小 100
+    pub reconnect_interval_ms: Option<u64>,
+    /// 连续重连的最大次数，为空时不限制
+    pub max_reconnects: Option<u32>,
+    /// 是否校验服务端证书，默认校验
+    pub verify_tls: Option<bool>,
+    /// 连接与握手超时时间（毫秒），默认 10000
+    pub timeout_ms: Option<u64>,
+    /// 代理地址，为空时使用全局代理，`direct` 表示直接连接
+    pub proxy: Option<String>,
+}
+
+/// 发送的消息类型，二进制消息的内容为十六进制
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WsMessageType {
+    #[default]
+    Text,
+    Binary,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct WsSessionInfo {
+    id: String,
+    url: String,
+    /// 服务端选择的子协议
+    protocol: Option<String>,
+    create_time: String,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WsDirection {
+    Sent,
+    Received,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WsEventKind {
+    /// 连接（或重连）成功
+    Open,
+    Text,
+    Binary,
+    Ping,
+    Pong,
+    /// 关闭帧
+    Close,
+    Error,
+    /// 连接断开，准备重连
+    Reconnecting,
+    /// 会话已关闭
+    Closed,
+}
+
+/// 通过 `websocket_event` 事件发送给前端
+#[derive(Serialize, Clone, Debug)]
+pub struct WsEvent {
+    session_id: String,
+    kind: WsEventKind,
+    /// 帧的方向，非帧事件为空
+    direction: Option<WsDirection>,
+    /// 文本消息内容
+    text: Option<String>,
+    /// 二进制消息与 Ping/Pong 的十六进制内容
+    hex: Option<String>,
+    /// 二进制消息的十六进制与 ASCII 对照视图
+    hex_dump: Option<String>,
+    size: usize,
+    message: Option<String>,
+    /// 格式 "YYYY-MM-DD HH:mm:ss.SSS"
+    time: String,
+}
+
+impl WsEvent {
+    fn new(session_id: &str, kind: WsEventKind) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            kind,
+            direction: None,
+            text: None,
+            hex: None,
+            hex_dump: None,
+            size: 0,
+            message: None,
+            time: Local::now().format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+        }
+    }
+
+    /// 帧对应的事件，`Message::Frame` 不会在读取时出现
+    fn frame(session_id: &str, direction: WsDirection, message: &Message) -> Option<Self> {
+        let (kind, payload) = match message {
+            Message::Text(text) => {
+                let mut event = Self::new(session_id, WsEventKind::Text);
+                event.direction = Some(direction);
+                event.size = text.len();
+                event.text = Some(text.to_string());
+                return Some(event);
+            }
+            Message::Binary(data) => (WsEventKind::Binary, data),
+            Message::Ping(data) => (WsEventKind::Ping, data),
+            Message::Pong(data) => (WsEventKind::Pong, data),
+            Message::Close(frame) => {
+                let mut event = Self::new(session_id, WsEventKind::Close);
+                event.direction = Some(direction);
+                event.message = frame.as_ref().map(|f| {
+                    format!("{} {}", u16::from(f.code), f.reason)
+                        .trim()
+                        .to_string()
+                });
+                return Some(event);
+            }
+            Message::Frame(_) => return None,
+        };
+        let mut event = Self::new(session_id, kind);
+        event.direction = Some(direction);
+        event.size = payload.len();
+        event.hex = Some(to_hex(payload));
+        if kind == WsEventKind::Binary {
+            event.hex_dump = Some(hex_dump(payload));
+        }
+        Some(event)
+    }
+
+    fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+enum SessionCommand {
+    Send {
+        message: Message,
+        reply: oneshot::Sender<Result<usize, String>>,
+    },
+    Close,
+}
+
+struct WsSession {
+    info: WsSessionInfo,
+    commands: mpsc::UnboundedSender<SessionCommand>,
+    handle: JoinHandle<()>,
+}
+
+/// 单个连接结束的原因
+enum ConnectionEnd {
+    /// 用户关闭会话
+    Closed,
+    /// 连接断开
+    Lost(String),
+}
+
+/// 全局会话池，Key 是会话 ID
+static WS_SESSION_POOL: Lazy<Mutex<HashMap<String, WsSession>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// 建立 WebSocket 连接，收发的每一帧都通过 `websocket_event` 事件通知前端
+#[tauri::command]
+pub async fn ws_connect(
+    app_handle: AppHandle,
+    request: WsRequest,
+) -> Result<WsSessionInfo, String> {
+    let sink: EventSink<WsEvent> = Arc::new(move |event| {
+        if let Err(e) = app_handle.emit_to("main", "websocket_event", event) {
+            eprintln!("发送 WebSocket 事件失败: {}", e);
+        }
+    });
+    open_session(request, sink).await
+}
+
+/// 发送文本或二进制消息，返回消息字节数
+#[tauri::command]
+pub async fn ws_send(
+    id: String,
+    payload: String,
+    message_type: Option<WsMessageType>,
+) -> Result<usize, String> {
+    let message = match message_type.unwrap_or_default() {
+        WsMessageType::Text => Message::text(payload),
+        WsMessageType::Binary => Message::binary(encode_payload(
+            &payload,
+            PayloadEncoding::Hex,
+            LineEnding::None,
+        )?),
+    };
+    let (reply, result) = oneshot::channel();
+    {
+        let pool = WS_SESSION_POOL.lock().unwrap();
+        let session = pool.get(&id).ok_or_else(|| format!("未找到会话: {}", id))?;
+        session
+            .commands
+            .send(SessionCommand::Send { message, reply })
+            .map_err(|_| "会话已关闭".to_string())?;
+    }
+    result.await.map_err(|_| "会话已关闭".to_string())?
+}
+
+/// 发送关闭帧并关闭会话
+#[tauri::command]
+pub async fn ws_close(app_handle: AppHandle, id: String) -> Result<String, String> {
+    close_session(&id).await?;
+    let _ = app_handle.emit_to(
+        "main",
+        "websocket_event",
+        WsEvent::new(&id, WsEventKind::Closed),
+    );
+    Ok(format!("已关闭会话: {}", id))
+}
+
+/// 当前打开的会话
+#[tauri::command]
+pub fn ws_sessions() -> Vec<WsSessionInfo> {
+    let mut sessions: Vec<WsSessionInfo> = WS_SESSION_POOL
+        .lock()
+        .unwrap()
+        .values()
+        .map(|s| s.info.clone())
+        .collect();
+    sessions.sort_by(|a, b| a.create_time.cmp(&b.create_time));
+    sessions
+}
+
+async fn close_session(id: &str) -> Result<(), String> {
+    let session = WS_SESSION_POOL
+        .lock()
+        .unwrap()
+        .remove(id)
+        .ok_or_else(|| format!("未找到会话: {}", id))?;
+    let _ = session.commands.send(SessionCommand::Close);
+    let mut handle = session.handle;
+    if tokio::time::timeout(CLOSE_TIMEOUT, &mut handle)
+        .await
+        .is_err()
+    {
+        handle.abort();
+    }
+    Ok(())
+}
+
+/// 建立首个连接并加入会话池，首次连接失败时直接返回错误
+async fn open_session(
+    request: WsRequest,
+    sink: EventSink<WsEvent>,
+) -> Result<WsSessionInfo, String> {
+    let (stream, protocol) = connect(&request).await?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let info = WsSessionInfo {
+        id: id.clone(),
+        url: request.url.trim().to_string(),
+        protocol: protocol.clone(),
+        create_time: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    };
+    let (commands, rx) = mpsc::unbounded_channel();
+    // 会话先加入会话池再启动，避免任务立即结束时找不到会话
+    let mut pool = WS_SESSION_POOL.lock().unwrap();
+    let handle = network_util::spawn(run_session(
+        id.clone(),
+        request,
+        (stream, protocol),
+        rx,
+        sink,
+    ));
+    pool.insert(
+        id,
+        WsSession {
+            info: info.clone(),
+            commands,
+            handle,
+        },
+    );
+    Ok(info)
+}
+
+/// 建立连接并完成握手，返回服务端选择的子协议
+async fn connect(request: &WsRequest) -> Result<(WsStream, Option<String>), String> {
+    let timeout = Duration::from_millis(request.timeout_ms.unwrap_or(DEFAULT_WS_TIMEOUT_MS).max(1));
+    tokio::time::timeout(timeout, handshake(request, timeout))
+        .await
+        .map_err(|_| "连接超时".to_string())?
+}
+
+async fn handshake(
+    request: &WsRequest,
+    timeout: Duration,
+) -> Result<(WsStream, Option<String>), String> {
+    let url = Url::parse(request.url.trim()).map_err(|e| format!("无效的地址: {}", e))?;
+    let tls = match url.scheme() {
+        "wss" => true,
+        "ws" => false,
+        scheme => return Err(format!("不支持的协议: {}", scheme)),
+    };
+    let host = url.host_str().ok_or("地址缺少主机名")?;
+    let port = url.port_or_known_default().unwrap_or(80);
+    let stream = open_stream(host, port, timeout, request.proxy.as_deref()).await?;
+
+    let io: Box<dyn Io> = if tls {
+        let config = client_config(request.verify_tls.unwrap_or(true))?;
+        let name = host.trim_matches(['[', ']']).to_string();
+        let server_name =
+            ServerName::try_from(name).map_err(|e| format!("无效的主机名 {}: {}", host, e))?;
+        let tls = TlsConnector::from(Arc::new(config))
+            .connect(server_name, stream)
+            .await
+            .map_err(|e| format!("TLS 握手失败: {}", e))?;
+        Box::new(tls)
+    } else {
+        Box::new(stream)
+    };
+
+    let mut ws_request = url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| e.to_string())?;
+    let headers = ws_request.headers_mut();
+    for header in request
+        .headers
+        .iter()
+        .filter(|h| h.enabled && !h.name.trim().is_empty())
+    {
+        let name = HeaderName::from_bytes(header.name.trim().as_bytes())
+            .map_err(|_| format!("无效的请求头名称: {}", header.name))?;
+        let value = HeaderValue::from_str(&header.value)
+            .map_err(|_| format!("无效的请求头值: {}", header.value))?;
+        headers.insert(name, value);
+    }
+    let protocols: Vec<&str> = request
+        .subprotocols
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if !protocols.is_empty() {
+        let value =
+            HeaderValue::from_str(&protocols.join(", ")).map_err(|_| "无效的子协议".to_string())?;
+        headers.insert(SEC_WEBSOCKET_PROTOCOL, value);
+    }
+
+    let (stream, response) = client_async(ws_request, io)
+        .await
+        .map_err(|e| format!("握手失败: {}", e))?;
+    let protocol = response
+        .headers()
+        .get(SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    Ok((stream, protocol))
+}
+
+async fn run_session(
+    id: String,
+    request: WsRequest,
+    first: (WsStream, Option<String>),
+    mut rx: mpsc::UnboundedReceiver<SessionCommand>,
+    sink: EventSink<WsEvent>,
+) {
+    let ping_interval = request
+        .ping_interval_secs
+        .filter(|secs| *secs > 0)
+        .map(Duration::from_secs);
+    let reconnect_interval = Duration::from_millis(
+        request
+            .reconnect_interval_ms
+            .unwrap_or(DEFAULT_RECONNECT_INTERVAL_MS)
+            .max(MIN_RECONNECT_INTERVAL_MS),
+    );
+    let mut connection = Some(first);
+    let mut attempts = 0u32;
+
+    loop {
+        if let Some((stream, protocol)) = connection.take() {
+            attempts = 0;
+            let message = match protocol {
+                Some(protocol) => format!("已连接，子协议 {}", protocol),
+                None => "已连接".to_string(),
+            };
+            sink(WsEvent::new(&id, WsEventKind::Open).with_message(message));
+            match run_connection(&id, stream, ping_interval, &mut rx, &sink).await {
+                ConnectionEnd::Closed => return,
+                ConnectionEnd::Lost(reason) => {
+                    if !request.auto_reconnect {
+                        sink(WsEvent::new(&id, WsEventKind::Error).with_message(reason));
+                        break;
+                    }
+                    sink(WsEvent::new(&id, WsEventKind::Reconnecting).with_message(reason));
+                }
+            }
+        }
+
+        attempts += 1;
+        if request.max_reconnects.is_some_and(|max| attempts > max) {
+            sink(WsEvent::new(&id, WsEventKind::Error).with_message("已达到最大重连次数"));
+            break;
+        }
+        if wait_reconnect(reconnect_interval, &mut rx).await {
+            return;
+        }
+        match connect(&request).await {
+            Ok(established) => connection = Some(established),
+            Err(e) => sink(
+                WsEvent::new(&id, WsEventKind::Reconnecting)
+                    .with_message(format!("第 {} 次重连失败: {}", attempts, e)),
+            ),
+        }
+    }
+    // 会话自然结束时从会话池移除
+    if WS_SESSION_POOL.lock().unwrap().remove(&id).is_some() {
+        sink(WsEvent::new(&id, WsEventKind::Closed));
+    }
+}
+
+/// 等待重连，期间发送的消息直接返回错误，收到关闭命令时返回 true
+async fn wait_reconnect(
+    interval: Duration,
+    rx: &mut mpsc::UnboundedReceiver<SessionCommand>,
+) -> bool {
+    let deadline = tokio::time::sleep(interval);
+    tokio::pin!(deadline);
+    loop {
+        tokio::select! {
+            _ = &mut deadline => return false,
+            command = rx.recv() => match command {
+                Some(SessionCommand::Send { reply, .. }) => {
+                    let _ = reply.send(Err("连接已断开，正在重连".to_string()));
+                }
+                Some(SessionCommand::Close) | None => return true,
+            },
+        }
+    }
+}
+
+async fn run_connection(
+    id: &str,
+    stream: WsStream,
+    ping_interval: Option<Duration>,
+    rx: &mut mpsc::UnboundedReceiver<SessionCommand>,
+    sink: &EventSink<WsEvent>,
+) -> ConnectionEnd {
+    let (mut writer, mut reader) = stream.split();
+    let mut ping =
+        ping_interval.map(|period| tokio::time::interval_at(Instant::now() + period, period));
+
+    loop {
+        tokio::select! {
+            frame = reader.next() => match frame {
+                Some(Ok(message)) => {
+                    if let Some(event) = WsEvent::frame(id, WsDirection::Received, &message) {
+                        sink(event);
+                    }
+                    // 服务端的关闭帧由 tungstenite 自动回复
+                    if let Message::Close(_) = message {
+                        return ConnectionEnd::Lost("服务端关闭了连接".to_string());
+                    }
+                }
+                Some(Err(e)) => return ConnectionEnd::Lost(format!("连接断开: {}", e)),
+                None => return ConnectionEnd::Lost("连接已断开".to_string()),
+            },
+            _ = tick(ping.as_mut()) => {
+                let message = Message::Ping(Local::now().timestamp_millis().to_string().into_bytes().into());
+                if let Err(e) = send(id, &mut writer, message, sink).await {
+                    return ConnectionEnd::Lost(e);
+                }
+            }
+            command = rx.recv() => match command {
+                Some(SessionCommand::Send { message, reply }) => {
+                    let result = send(id, &mut writer, message, sink).await;
+                    let _ = reply.send(result.clone());
+                    if let Err(e) = result {
+                        return ConnectionEnd::Lost(e);
+                    }
+                }
+                Some(SessionCommand::Close) | None => {
+                    let message = Message::Close(None);
+                    let _ = send(id, &mut writer, message, sink).await;
+                    close_gracefully(id, &mut reader, sink).await;
+                    return ConnectionEnd::Closed;
+                }
+            },
+        }
+    }
+}
+
+/// 等待服务端回复关闭帧
+async fn close_gracefully(id: &str, reader: &mut SplitStream<WsStream>, sink: &EventSink<WsEvent>) {
+    let _ = tokio::time::timeout(CLOSE_TIMEOUT, async {
+        while let Some(Ok(message)) = reader.next().await {
+            if let Some(event) = WsEvent::frame(id, WsDirection::Received, &message) {
+                sink(event);
+            }
+        }
+    })
+    .await;
+}
+
+async fn send(
+    id: &str,
+    writer: &mut SplitSink<WsStream, Message>,
+    message: Message,
+    sink: &EventSink<WsEvent>,
+) -> Result<usize, String> {
+    let size = message.len();
+    let event = WsEvent::frame(id, WsDirection::Sent, &message);
+    writer
+        .send(message)
+        .await
+        .map_err(|e| format!("发送失败: {}", e))?;
+    if let Some(event) = event {
+        sink(event);
+    }
+    Ok(size)
+}
+
+/// 未设置心跳时永远不会返回
+async fn tick(interval: Option<&mut Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::network_util::event_sink::{channel_sink, next_event};
+    use crate::utils::network_util::stand_in::{Behavior, StandIn};
+    use crate::utils::network_util::with_connector;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+    use tokio_tungstenite::{accept_async, accept_hdr_async};
+
+    /// 回显服务，`drop_first` 为 true 时握手后立即关闭第一个连接
+    #[allow(clippy::result_large_err)] // 握手回调的签名由 tungstenite 决定
+    async fn spawn_echo(drop_first: bool) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut first = true;
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let callback = |request: &Request, mut response: Response| {
+                    if request
+                        .headers()
+                        .get("x-token")
+                        .is_some_and(|v| v == "secret")
+                    {
+                        response
+                            .headers_mut()
+                            .insert(SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_static("chat"));
+                    }
+                    Ok(response)
+                };
+                let mut ws = if drop_first {
+                    accept_async(stream).await.unwrap()
+                } else {
+                    accept_hdr_async(stream, callback).await.unwrap()
+                };
+                if std::mem::take(&mut first) && drop_first {
+                    ws.close(None).await.unwrap();
+                    continue;
+                }
+                tokio::spawn(async move {
+                    while let Some(Ok(message)) = ws.next().await {
+                        if message.is_text() || message.is_binary() {
+                            ws.send(message).await.unwrap();
+                        }
+                    }
+                });
+            }
+        });
+        port
+    }
+
+    async fn send_text(
+        id: &str,
+        payload: &str,
+        message_type: WsMessageType,
+    ) -> Result<usize, String> {
+        ws_send(id.to_string(), payload.to_string(), Some(message_type)).await
+    }
+
+    #[tokio::test]
+    async fn test_echo_with_headers_and_ping() {
+        let port = spawn_echo(false).await;
+        let (sink, mut events) = channel_sink();
+        let request = WsRequest {
+            url: format!("ws://127.0.0.1:{}/echo", port),
+            headers: vec![KeyValue::new("X-Token", "secret")],
+            subprotocols: vec!["chat".to_string(), "superchat".to_string()],
+            ping_interval_secs: Some(1),
+            ..Default::default()
+        };
+        let info = open_session(request, sink).await.unwrap();
+        assert_eq!(info.protocol.as_deref(), Some("chat"));
+        let open = next_event(&mut events, |e| {
+            e.kind == WsEventKind::Open && e.direction.is_none()
+        })
+        .await;
+        assert_eq!(open.message.as_deref(), Some("已连接，子协议 chat"));
+
+        assert_eq!(
+            send_text(&info.id, "hello 世界", WsMessageType::Text)
+                .await
+                .unwrap(),
+            12
+        );
+        let echo = next_event(&mut events, |e| {
+            e.kind == WsEventKind::Text && e.direction == Some(WsDirection::Received)
+        })
+        .await;
+        assert_eq!(echo.text.as_deref(), Some("hello 世界"));
+
+        assert_eq!(
+            send_text(&info.id, "0x01 ff", WsMessageType::Binary)
+                .await
+                .unwrap(),
+            2
+        );
+        let echo = next_event(&mut events, |e| {
+            e.kind == WsEventKind::Binary && e.direction == Some(WsDirection::Received)
+        })
+        .await;
+        assert_eq!(echo.hex.as_deref(), Some("01ff"));
+        assert!(send_text(&info.id, "zz", WsMessageType::Binary)
+            .await
+            .is_err());
+
+        // 服务端自动回复心跳
+        next_event(&mut events, |e| {
+            e.kind == WsEventKind::Ping && e.direction == Some(WsDirection::Sent)
+        })
+        .await;
+        next_event(&mut events, |e| {
+            e.kind == WsEventKind::Pong && e.direction == Some(WsDirection::Received)
+        })
+        .await;
+
+        close_session(&info.id).await.unwrap();
+        next_event(&mut events, |e| {
+            e.kind == WsEventKind::Close && e.direction == Some(WsDirection::Sent)
+        })
+        .await;
+        assert!(ws_sessions().iter().all(|s| s.id != info.id));
+        assert!(send_text(&info.id, "x", WsMessageType::Text).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auto_reconnect() {
+        let port = spawn_echo(true).await;
+        // 重连同样经过会话启动时的连接方式
+        let connector = StandIn::new()
+            .await
+            .host("ws.example.test", &["192.0.2.30"])
+            .route(
+                "192.0.2.30:80",
+                Behavior::Forward(([127, 0, 0, 1], port).into()),
+            )
+            .build();
+        let (sink, mut events) = channel_sink();
+        let request = WsRequest {
+            url: "ws://ws.example.test".to_string(),
+            auto_reconnect: true,
+            reconnect_interval_ms: Some(MIN_RECONNECT_INTERVAL_MS),
+            ..Default::default()
+        };
+        let info = with_connector(connector, open_session(request, sink))
+            .await
+            .unwrap();
+        next_event(&mut events, |e| {
+            e.kind == WsEventKind::Open && e.direction.is_none()
+        })
+        .await;
+        next_event(&mut events, |e| {
+            e.kind == WsEventKind::Close && e.direction == Some(WsDirection::Received)
+        })
+        .await;
+        next_event(&mut events, |e| {
+            e.kind == WsEventKind::Reconnecting && e.direction.is_none()
+        })
+        .await;
+        next_event(&mut events, |e| {
+            e.kind == WsEventKind::Open && e.direction.is_none()
+        })
+        .await;
+
+        send_text(&info.id, "again", WsMessageType::Text)
+            .await
+            .unwrap();
+        let echo = next_event(&mut events, |e| {
+            e.kind == WsEventKind::Text && e.direction == Some(WsDirection::Received)
+        })
+        .await;
+        assert_eq!(echo.text.as_deref(), Some("again"));
+        close_session(&info.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_connect_errors() {
+        let (sink, _events) = channel_sink();
+        let request = |url: &str| WsRequest {
+            url: url.to_string(),
+            timeout_ms: Some(2_000),
+            ..Default::default()
+        };
+        let err = open_session(request("http://127.0.0.1/"), sink.clone())
+            .await
+            .unwrap_err();
+        assert!(err.contains("不支持的协议"), "{}", err);
+
+        // 服务端未返回请求的子协议时握手失败
+        let port = spawn_echo(false).await;
+        let mut with_protocol = request(&format!("ws://127.0.0.1:{}", port));
+        with_protocol.subprotocols = vec!["chat".to_string()];
+        let err = open_session(with_protocol, sink).await.unwrap_err();
+        assert!(err.contains("握手失败"), "{}", err);
+    }
+}
@@ -0,0 +1,219 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+use tokio::net::{lookup_host, UdpSocket};
+
+/// 默认广播地址
+const DEFAULT_BROADCAST: &str = "255.255.255.255";
+/// 默认端口，也有设备使用 7 端口
+const DEFAULT_PORT: u16 = 9;
+/// 托盘菜单中设备项的 ID 前缀
+pub const TRAY_MENU_PREFIX: &str = "wol:";
+
+/// 显示在托盘菜单中的已保存设备，由前端从数据库读取后同步
+static TRAY_DEVICES: Lazy<Mutex<Vec<WolDevice>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct WolRequest {
+    pub mac: String,
+    /// 广播地址，默认 255.255.255.255，跨网段唤醒时填写目标网段的定向广播地址
+    pub broadcast: Option<String>,
+    /// 端口，默认 9
+    pub port: Option<u16>,
+    /// SecureOn 密码，6 字节，格式同 MAC 地址
+    pub password: Option<String>,
+}
+
+/// 已保存的设备
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WolDevice {
+    pub id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub request: WolRequest,
+}
+
+/// 发送网络唤醒魔术包
+#[tauri::command]
+pub async fn wake_on_lan(request: WolRequest) -> Result<String, String> {
+    send_wol(&request).await
+}
+
+/// 同步托盘菜单中的设备列表
+#[tauri::command]
+pub fn set_wol_tray_devices(app_handle: AppHandle, devices: Vec<WolDevice>) -> Result<(), String> {
+    *TRAY_DEVICES.lock().unwrap() = devices;
+    crate::refresh_tray_menu(&app_handle).map_err(|e| format!("更新托盘菜单失败: {}", e))
+}
+
+/// 托盘菜单中的设备，返回菜单项ID与名称
+pub fn tray_devices() -> Vec<(String, String)> {
+    TRAY_DEVICES
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|d| (format!("{}{}", TRAY_MENU_PREFIX, d.id), d.name.clone()))
+        .collect()
+}
+
+/// 处理托盘菜单的唤醒点击，结果以系统通知显示
+pub fn wake_from_tray(app_handle: AppHandle, menu_id: &str) {
+    let Some(device) = menu_id.strip_prefix(TRAY_MENU_PREFIX).and_then(|id| {
+        TRAY_DEVICES
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|d| d.id == id)
+            .cloned()
+    }) else {
+        return;
+    };
+    tauri::async_runtime::spawn(async move {
+        let body = match send_wol(&device.request).await {
+            Ok(message) => message,
+            Err(e) => format!("唤醒 {} 失败: {}", device.name, e),
+        };
+        let _ = app_handle
+            .notification()
+            .builder()
+            .title("网络唤醒")
+            .body(body)
+            .show();
+    });
+}
+
+/// 解析目标地址并发送魔术包
+pub async fn send_wol(request: &WolRequest) -> Result<String, String> {
+    let mac = parse_mac(&request.mac)?;
+    let password = match request.password.as_deref().map(str::trim) {
+        Some(password) if !password.is_empty() => Some(
+            parse_mac(password)
+                .map_err(|_| "SecureOn 密码应为 6 字节，格式同 MAC 地址".to_string())?,
+        ),
+        _ => None,
+    };
+    let host = request
+        .broadcast
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or(DEFAULT_BROADCAST);
+    let port = request.port.unwrap_or(DEFAULT_PORT);
+    let target = lookup_host((host, port))
+        .await
+        .map_err(|e| format!("无法解析地址 {}: {}", host, e))?
+        .next()
+        .ok_or_else(|| format!("无法解析地址: {}", host))?;
+
+    let bind: SocketAddr = if target.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    }
+    .parse()
+    .unwrap();
+    let socket = UdpSocket::bind(bind)
+        .await
+        .map_err(|e| format!("绑定本地端口失败: {}", e))?;
+    socket
+        .set_broadcast(true)
+        .map_err(|e| format!("开启广播失败: {}", e))?;
+    socket
+        .send_to(&magic_packet(&mac, password.as_ref()), target)
+        .await
+        .map_err(|e| format!("发送唤醒包失败: {}", e))?;
+    Ok(format!("已向 {} 发送唤醒包: {}", target, format_mac(&mac)))
+}
+
+/// 解析 MAC 地址，支持 `:`、`-`、`.` 分隔或不分隔
+pub fn parse_mac(input: &str) -> Result<[u8; 6], String> {
+    let hex: String = input
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-' | '.') && !c.is_whitespace())
+        .collect();
+    let invalid = || format!("无效的 MAC 地址: {}", input);
+    if hex.len() != 12 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let mut mac = [0u8; 6];
+    for (i, byte) in mac.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(mac)
+}
+
+fn format_mac(mac: &[u8; 6]) -> String {
+    mac.iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// 魔术包：6 个 0xFF，MAC 地址重复 16 次，有 SecureOn 密码时追加在末尾
+pub fn magic_packet(mac: &[u8; 6], password: Option<&[u8; 6]>) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(6 + 16 * 6 + 6);
+    packet.extend_from_slice(&[0xFF; 6]);
+    for _ in 0..16 {
+        packet.extend_from_slice(mac);
+    }
+    if let Some(password) = password {
+        packet.extend_from_slice(password);
+    }
+    packet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mac_and_packet() {
+        let expected = [0x3C, 0x22, 0xFB, 0x01, 0xAB, 0xCD];
+        for input in [
+            "3c:22:fb:01:ab:cd",
+            "3C-22-FB-01-AB-CD",
+            "3c22.fb01.abcd",
+            "3c22fb01abcd",
+        ] {
+            assert_eq!(parse_mac(input), Ok(expected), "{}", input);
+        }
+        assert!(parse_mac("3c:22:fb:01:ab").is_err());
+        assert!(parse_mac("3c:22:fb:01:ab:zz").is_err());
+        assert!(parse_mac("3c:22:fb:01:ab:cd:ef").is_err());
+
+        let packet = magic_packet(&expected, None);
+        assert_eq!(packet.len(), 102);
+        assert_eq!(&packet[..6], &[0xFF; 6]);
+        assert!(packet[6..].chunks(6).all(|chunk| chunk == expected));
+        let packet = magic_packet(&expected, Some(&[1, 2, 3, 4, 5, 6]));
+        assert_eq!(packet.len(), 108);
+        assert_eq!(&packet[102..], &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[tokio::test]
+    async fn test_send_wol_local() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = receiver.local_addr().unwrap().port();
+        let request = WolRequest {
+            mac: "3c-22-fb-01-ab-cd".to_string(),
+            broadcast: Some("127.0.0.1".to_string()),
+            port: Some(port),
+            password: Some("00:11:22:33:44:55".to_string()),
+        };
+        let message = send_wol(&request).await.unwrap();
+        assert!(message.contains("3C:22:FB:01:AB:CD"));
+        let mut buf = [0u8; 256];
+        let (len, _) = receiver.recv_from(&mut buf).await.unwrap();
+        assert_eq!(len, 108);
+        assert_eq!(&buf[102..108], &[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+
+        let invalid = WolRequest {
+            password: Some("123".to_string()),
+            ..request
+        };
+        assert!(send_wol(&invalid).await.is_err());
+    }
+}
@@ -54,6 +54,94 @@ const router: Router = createRouter({
                     },
                     component: () => import('@/views/network/Network.vue')
                 },
+                {
+                    path: '/local_ports',
+                    name: 'localPorts',
+                    meta: {
+                        name: '本机端口'
+                    },
+                    component: () => import('@/views/ports/LocalPorts.vue')
+                },
+                {
+                    path: '/interfaces',
+                    name: 'interfaces',
+                    meta: {
+                        name: '网卡信息'
+                    },
+                    component: () => import('@/views/interfaces/Interfaces.vue')
+                },
+                {
+                    path: '/wol',
+                    name: 'wol',
+                    meta: {
+                        name: '网络唤醒'
+                    },
+                    component: () => import('@/views/wol/WakeOnLan.vue')
+                },
+                {
+                    path: '/subnet',
+                    name: 'subnet',
+                    meta: {
+                        name: '子网计算'
+                    },
+                    component: () => import('@/views/subnet/Subnet.vue')
+                },
+                {
+                    path: '/netcat',
+                    name: 'netcat',
+                    meta: {
+                        name: 'TCP/UDP调试'
+                    },
+                    component: () => import('@/views/netcat/Netcat.vue')
+                },
+                {
+                    path: '/port_forward',
+                    name: 'portForward',
+                    meta: {
+                        name: '端口转发'
+                    },
+                    component: () => import('@/views/forward/PortForward.vue')
+                },
+                {
+                    path: '/websocket',
+                    name: 'websocket',
+                    meta: {
+                        name: 'WebSocket'
+                    },
+                    component: () => import('@/views/websocket/WebSocketClient.vue')
+                },
+                {
+                    path: '/http_client',
+                    name: 'httpClient',
+                    meta: {
+                        name: 'HTTP请求'
+                    },
+                    component: () => import('@/views/http/HttpClient.vue')
+                },
+                {
+                    path: '/db_check',
+                    name: 'dbCheck',
+                    meta: {
+                        name: '数据库连接'
+                    },
+                    component: () => import('@/views/database/DbChecker.vue')
+                },
+                {
+                    path: '/monitor',
+                    name: 'monitor',
+                    meta: {
+                        name: '服务监控'
+                    },
+                    component: () => import('@/views/monitor/Monitor.vue')
+                },
+                {
+                    path: '/proxy',
+                    name: 'proxy',
+                    meta: {
+                        name: '代理设置'
+                    },
+                    component: () => import('@/views/proxy/ProxySettings.vue')
+                },
                 {
                     path: '/todo_list',
                     name: 'todolist',
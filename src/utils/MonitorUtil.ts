@@ -0,0 +1,51 @@
+/**
+ * monitor表映射对象
+ */
+export interface MonitorItem {
+    id: string;
+    name: string;
+    kind: 'tcp' | 'http';
+    host: string | null;
+    port: number | null;
+    url: string | null;
+    expected_status: number | null;
+    verify_tls: number | null;
+    interval_secs: number;
+    timeout_ms: number | null;
+    last_state: 'up' | 'down' | null;
+    effective: number;
+    create_time: string;
+    update_time: string;
+}
+
+/**
+ * 单次检测结果，对应rust后端的monitor_checked事件
+ */
+export interface MonitorCheck {
+    monitor_id: string;
+    state: 'up' | 'down';
+    latency_ms: number | null;
+    message: string | null;
+    check_time: string;
+}
+
+// monitor表记录转换为rust后端的监控对象
+export const toMonitorTask = (item: MonitorItem) => {
+    const base = {
+        id: (BigInt(item.id)).toString(),
+        name: item.name,
+        interval_secs: item.interval_secs,
+        timeout_ms: item.timeout_ms,
+        last_state: item.last_state,
+    }
+    if (item.kind === 'tcp') {
+        return {...base, kind: 'tcp', host: item.host, port: item.port}
+    }
+    return {
+        ...base,
+        kind: 'http',
+        url: item.url,
+        expected_status: item.expected_status,
+        verify_tls: item.verify_tls === null ? null : item.verify_tls === 1,
+    }
+}
@@ -0,0 +1,71 @@
+import Database from "@tauri-apps/plugin-sql";
+import {invoke} from "@tauri-apps/api/core";
+import {dayjs} from "element-plus";
+
+/**
+ * 代理设置，保存在app_setting表的proxy项中
+ */
+export interface ProxySetting {
+    enabled: boolean;
+    kind: 'http' | 'socks5';
+    host: string;
+    port: number;
+    username: string;
+    password: string;
+    // 是否将密码明文保存到本地数据库，否则密码只保留在本次运行的后端内存中
+    remember_password: boolean;
+    no_proxy: string;
+}
+
+// app_setting表中代理设置的名称
+const SETTING_NAME = 'proxy'
+
+export const defaultProxySetting = (): ProxySetting => ({
+    enabled: false,
+    kind: 'http',
+    host: '',
+    port: 8080,
+    username: '',
+    password: '',
+    remember_password: false,
+    no_proxy: 'localhost, 127.0.0.0/8, ::1',
+})
+
+// 代理设置转换为rust后端的ProxyConfig，未启用时为null
+export const toProxyConfig = (setting: ProxySetting) => setting.enabled ? {
+    kind: setting.kind,
+    host: setting.host.trim(),
+    port: setting.port,
+    username: setting.username || null,
+    password: setting.username ? setting.password : null,
+    no_proxy: setting.no_proxy.split(/[,\s]+/).filter(s => s),
+} : null
+
+export const loadProxySetting = async (db: Database): Promise<ProxySetting> => {
+    const rows = (await db.select('select value from app_setting where name = $1', [SETTING_NAME])) as { value: string }[] || []
+    if (!rows.length || !rows[0].value) {
+        return defaultProxySetting()
+    }
+    const saved = JSON.parse(rows[0].value)
+    // 旧版本的设置总是保存密码
+    return {...defaultProxySetting(), remember_password: !!saved.password, ...saved}
+}
+
+// 保存代理设置并同步到rust后端，后端校验失败时不保存；未勾选记住密码时不写入密码
+export const saveProxySetting = async (db: Database, setting: ProxySetting) => {
+    await invoke('set_proxy_config', {config: toProxyConfig(setting)})
+    const stored = {...setting, password: setting.remember_password ? setting.password : ''}
+    await db.execute('INSERT OR REPLACE into app_setting (name, value, update_time) VALUES ($1, $2, $3)',
+        [SETTING_NAME, JSON.stringify(stored), dayjs().format('YYYY-MM-DD HH:mm:ss')])
+}
+
+// 未保存密码时，取本次运行中后端内存里的密码
+export const currentProxyPassword = async (): Promise<string> => {
+    const config = await invoke<{ password: string | null } | null>('get_proxy_config')
+    return config?.password || ''
+}
+
+// 启动时将保存的代理设置同步到rust后端
+export const syncProxy = async (db: Database) => {
+    await invoke('set_proxy_config', {config: toProxyConfig(await loadProxySetting(db))})
+}
@@ -0,0 +1,37 @@
+import Database from "@tauri-apps/plugin-sql";
+import {invoke} from "@tauri-apps/api/core";
+
+/**
+ * wol_device表映射对象
+ */
+export interface WolDeviceItem {
+    id: string;
+    name: string;
+    mac: string;
+    broadcast: string | null;
+    port: number | null;
+    password: string | null;
+    show_in_tray: number | null;
+    create_time: string;
+    update_time: string;
+}
+
+// wol_device表记录转换为rust后端的唤醒请求
+export const toWolRequest = (item: WolDeviceItem) => ({
+    mac: item.mac,
+    broadcast: item.broadcast || null,
+    port: item.port,
+    password: item.password || null,
+})
+
+// 将需要显示在托盘菜单中的设备同步到rust后端
+export const syncWolTray = async (db: Database) => {
+    const devices = (await db.select('select * from wol_device where show_in_tray = 1 order by create_time')) as WolDeviceItem[] || []
+    await invoke('set_wol_tray_devices', {
+        devices: devices.map(item => ({
+            id: (BigInt(item.id)).toString(),
+            name: item.name,
+            ...toWolRequest(item),
+        }))
+    })
+}